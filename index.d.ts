@@ -169,8 +169,82 @@ export declare class Blame {
    *
    * @param {Buffer} buffer - Buffer containing file content to blame
    * @returns A new Blame object for the buffer content
+   * @throws If the blame ignores revisions or is created by `blameFileAsync`
    */
   buffer(buffer: Buffer): Blame
+  /**
+   * Gets the lines of the blamed file with their hunks as an iterator
+   *
+   * Lines which are not blamed, e.g. outside of `minLine` and `maxLine`, are
+   * skipped.
+   *
+   * @category Blame/Methods
+   * @signature
+   * ```ts
+   * class Blame {
+   *   iterLines(): Generator<BlameLine>;
+   * }
+   * ```
+   *
+   * @returns Iterator of the lines of the blamed file
+   * @example
+   * ```ts
+   * for (const { lineNumber, content, hunk } of blame.iterLines()) {
+   *   console.log(`${hunk.finalCommitId.slice(0, 8)} ${lineNumber}) ${content}`);
+   * }
+   * ```
+   */
+  iterLines(): BlameLines
+  /**
+   * Gets the lines of the blamed file with their hunks
+   *
+   * Each line has the line number and the path of the file where it was
+   * originally written, after following renames and copies. Lines which are
+   * not blamed are skipped.
+   *
+   * @category Blame/Methods
+   * @signature
+   * ```ts
+   * class Blame {
+   *   lines(): BlameLine[];
+   * }
+   * ```
+   *
+   * @returns Lines of the blamed file
+   * @example
+   * ```ts
+   * const blame = repo.blameFile('renamed.txt', { trackCopiesSameCommitMoves: true });
+   * for (const line of blame.lines()) {
+   *   console.log(`${line.origPath}:${line.origLineNumber} ${line.content}`);
+   * }
+   * ```
+   */
+  lines(): Array<BlameLine>
+  /**
+   * Formats the blame like `git blame --porcelain`
+   *
+   * Information of each commit is written once at the first line blamed to
+   * the commit, unless `lineFormat` is set to write it for every line like
+   * `git blame --line-porcelain`.
+   *
+   * @category Blame/Methods
+   * @signature
+   * ```ts
+   * class Blame {
+   *   toPorcelain(options?: BlamePorcelainOptions): string;
+   * }
+   * ```
+   *
+   * @param {BlamePorcelainOptions} [options] - Options for the output format
+   * @returns Blame in the porcelain format
+   * @throws If commits of the blame can't be found
+   * @example
+   * ```ts
+   * const blame = repo.blameFile('path/to/file.js');
+   * const output = blame.toPorcelain({ lineFormat: true });
+   * ```
+   */
+  toPorcelain(options?: BlamePorcelainOptions | undefined | null): string
 }
 
 /**
@@ -201,6 +275,20 @@ export declare class BlameHunksByLine extends Iterator<BlameHunk, void, void> {
   next(value?: void): IteratorResult<BlameHunk, void>
 }
 
+/**
+ * An iterator over lines of a blamed file.
+ *
+ * This type extends JavaScript's `Iterator`, and so has the iterator helper
+ * methods. It may extend the upcoming TypeScript `Iterator` class in the future.
+ *
+ * @see https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Iterator#iterator_helper_methods
+ * @see https://www.typescriptlang.org/docs/handbook/release-notes/typescript-5-6.html#iterator-helper-methods
+ */
+export declare class BlameLines extends Iterator<BlameLine, void, void> {
+
+  next(value?: void): IteratorResult<BlameLine, void>
+}
+
 /**
  * A class to represent a git [blob][1].
  * [1]: https://git-scm.com/book/en/Git-Internals-Git-Objects
@@ -489,6 +577,40 @@ export declare class Commit {
    * @throws If the message is not valid utf-8.
    */
   message(): string
+  /**
+   * Get the full raw message of a commit, exactly as stored in the commit
+   * object.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   messageRaw(): string;
+   * }
+   * ```
+   *
+   * @returns Full raw message of this commit.
+   * @throws If the message is not valid utf-8.
+   */
+  messageRaw(): string
+  /**
+   * Get the encoding for the message of a commit, as a string representing
+   * a standard encoding name.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   messageEncoding(): string | null;
+   * }
+   * ```
+   *
+   * @returns Encoding of the message. Returns `null` if the encoding is not
+   * specified, in which case UTF-8 is assumed.
+   */
+  messageEncoding(): string | null
   /**
    * Get the short "summary" of the git commit message.
    *
@@ -547,6 +669,22 @@ export declare class Commit {
    * @returns Commit time of a commit.
    */
   time(): Date
+  /**
+   * Get the timezone offset of the commit time (i.e. committer time) of a
+   * commit.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   timeOffset(): number;
+   * }
+   * ```
+   *
+   * @returns Timezone offset in minutes from UTC.
+   */
+  timeOffset(): number
   /**
    * Get the id of the tree pointed to by this commit.
    *
@@ -579,6 +717,122 @@ export declare class Commit {
    * @returns Tree pointed to by a commit.
    */
   tree(): Tree
+  /**
+   * Get the number of parents of a commit.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   parentCount(): number;
+   * }
+   * ```
+   *
+   * @returns Number of parents of this commit.
+   */
+  parentCount(): number
+  /**
+   * Get the ids of the parents of a commit.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   parentIds(): string[];
+   * }
+   * ```
+   *
+   * @returns IDs(SHA1) of the parents of this commit.
+   */
+  parentIds(): Array<string>
+  /**
+   * Get the parents of a commit.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   parents(): Commit[];
+   * }
+   * ```
+   *
+   * @returns Parent commits of this commit.
+   */
+  parents(): Array<Commit>
+  /**
+   * Get the specified parent of a commit.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   parent(n: number): Commit;
+   * }
+   * ```
+   *
+   * @param {number} n - Position of the parent. (0-based)
+   * @returns Parent commit at the given position.
+   * @throws Throws error if the parent does not exist.
+   */
+  parent(n: number): Commit
+  /**
+   * Get the commit object that is the n-th generation ancestor of a commit,
+   * following only the first parents.
+   *
+   * Passing `0` as the generation number returns another instance of the
+   * same commit.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   nthGenAncestor(n: number): Commit;
+   * }
+   * ```
+   *
+   * @param {number} n - Requested generation.
+   * @returns Ancestor commit of the given generation.
+   * @throws Throws error if the ancestor does not exist.
+   */
+  nthGenAncestor(n: number): Commit
+  /**
+   * Create a diff between the tree of the specified parent and the tree of
+   * a commit.
+   *
+   * For a root commit, the diff is created against an empty tree.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   diffToParent(n?: number | null | undefined, options?: DiffOptions | null | undefined): Diff;
+   * }
+   * ```
+   *
+   * @param {number} [n] - Position of the parent. (0-based) Defaults to `0`.
+   * @param {DiffOptions} [options] - Options for creating the diff.
+   * @returns Diff from the parent to this commit.
+   * @throws Throws error if the parent does not exist, or if the commit is
+   * not looked up from a repository.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('.');
+   * const commit = repo.getCommit(repo.head().target()!);
+   * for (const delta of commit.diffToParent().deltas()) {
+   *   console.log(delta.status(), delta.newFile().path());
+   * }
+   * ```
+   */
+  diffToParent(n?: number | undefined | null, options?: DiffOptions | undefined | null): Diff
   /**
    * Casts this Commit to be usable as an `GitObject`.
    *
@@ -594,6 +848,41 @@ export declare class Commit {
    * @returns `GitObject` that casted from this commit.
    */
   asObject(): GitObject
+  /**
+   * Get an arbitrary header field of a commit.
+   *
+   * Continuation lines of multi-line fields are joined with newlines.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   headerField(name: string): string | null;
+   * }
+   * ```
+   *
+   * @param {string} name - Name of the header field. (e.g. "encoding", "gpgsig")
+   * @returns Value of the header field. Returns `null` if the field does not exist.
+   * @throws If the value is not valid utf-8.
+   */
+  headerField(name: string): string | null
+  /**
+   * Get the full raw text of the commit header.
+   *
+   * @category Commit/Methods
+   *
+   * @signature
+   * ```ts
+   * class Commit {
+   *   rawHeader(): string;
+   * }
+   * ```
+   *
+   * @returns Raw header of this commit, without the message.
+   * @throws If the header is not valid utf-8.
+   */
+  rawHeader(): string
   /**
    * Amend this existing commit with all non-nullable values
    *
@@ -891,6 +1180,79 @@ export declare class Config {
    * @returns The value of a path config variable.
    */
   getPath(name: string): string
+  /**
+   * Get the value of a path config variable.
+   *
+   * A leading '~' will be expanded to the global search path (which
+   * defaults to the user's home directory).
+   *
+   * @category Config/Methods
+   * @signature
+   * ```ts
+   * class Config {
+   *   findPath(name: string): string | null;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of config entry.
+   * @returns The value of a path config variable. Returns `null` if the
+   * variable does not exist.
+   */
+  findPath(name: string): string | null
+  /**
+   * Get the value of a color config variable.
+   *
+   * The value is parsed in the same syntax as git. (e.g. `bold red blue`)
+   *
+   * @category Config/Methods
+   * @signature
+   * ```ts
+   * class Config {
+   *   getColor(name: string): ConfigColor;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of config entry.
+   * @returns The value of a color config variable.
+   * @throws Throws error if the variable does not exist or is not a valid color.
+   */
+  getColor(name: string): ConfigColor
+  /**
+   * Find the value of a color config variable.
+   *
+   * @category Config/Methods
+   * @signature
+   * ```ts
+   * class Config {
+   *   findColor(name: string): ConfigColor | null;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of config entry.
+   * @returns The value of a color config variable. Returns `null` if the
+   * variable does not exist or is not a valid color.
+   */
+  findColor(name: string): ConfigColor | null
+  /**
+   * Get the value of a duration config variable, such as `gc.pruneExpire`.
+   *
+   * Relative durations like `2.weeks.ago` or `90 days` and plain numbers of
+   * seconds are supported. Months are counted as 30 days and years as 365
+   * days.
+   *
+   * @category Config/Methods
+   * @signature
+   * ```ts
+   * class Config {
+   *   getDuration(name: string): number | null;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of config entry.
+   * @returns The duration in seconds. Returns `null` if the value is `never`.
+   * @throws Throws error if the variable does not exist or is not a valid duration.
+   */
+  getDuration(name: string): number | null
   /**
    * Get the entry for a config variable.
    *
@@ -973,30 +1335,63 @@ export declare class Config {
    */
   multivar(name: string, regexp?: string | undefined | null): ConfigEntries
   /**
-   * Set the value of a boolean config variable in the config file with the
-   * highest level (usually the local one).
+   * Get the values of a multivar, interpreted as the given type.
    *
    * @category Config/Methods
    * @signature
    * ```ts
    * class Config {
-   *   setBool(name: string, value: boolean): void;
+   *   multivarValues(
+   *     name: string,
+   *     regexp?: string | null | undefined,
+   *     valueType?: ConfigValueType | null | undefined,
+   *   ): Array<string | number | boolean>;
    * }
    * ```
    *
    * @param {string} name - The name of config entry.
-   * @param {boolean} value - The value of config entry.
+   * @param {string} [regexp] - If `regexp` is provided, only values which match the pattern
+   * will be returned.
+   * @param {ConfigValueType} [valueType] - Type to interpret the values. Defaults to `String`.
+   * @returns Values of the multivar, in the order of their defined level.
+   * @throws Throws error if a value cannot be interpreted as the given type.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('.');
+   * const config = repo.config();
+   * const refspecs = config.multivarValues('remote.origin.fetch');
+   * const pruneTags = config.multivarValues('remote.origin.prunetags', null, 'Bool');
+   * ```
    */
-  setBool(name: string, value: boolean): void
+  multivarValues(name: string, regexp?: string | undefined | null, valueType?: ConfigValueType | undefined | null): Array<string | number | boolean>
   /**
-   * Set the value of an integer config variable in the config file with the
+   * Set the value of a boolean config variable in the config file with the
    * highest level (usually the local one).
    *
    * @category Config/Methods
    * @signature
    * ```ts
    * class Config {
-   *   setI32(name: string, value: number): void;
+   *   setBool(name: string, value: boolean): void;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of config entry.
+   * @param {boolean} value - The value of config entry.
+   */
+  setBool(name: string, value: boolean): void
+  /**
+   * Set the value of an integer config variable in the config file with the
+   * highest level (usually the local one).
+   *
+   * @category Config/Methods
+   * @signature
+   * ```ts
+   * class Config {
+   *   setI32(name: string, value: number): void;
    * }
    * ```
    *
@@ -1053,6 +1448,98 @@ export declare class Config {
    * @param {string} value - The value of config entry.
    */
   setString(name: string, value: string): void
+  /**
+   * Add an on-disk config file instance to an existing config.
+   *
+   * The on-disk file pointed at by `path` will be opened and parsed; it's
+   * expected to be a native Git config file following the default Git
+   * config syntax (see man git-config).
+   *
+   * Further queries on this config object will access each of the config
+   * file instances in order (instances with a higher priority level will
+   * be accessed first).
+   *
+   * @category Config/Methods
+   * @signature
+   * ```ts
+   * class Config {
+   *   addFile(path: string, level: ConfigLevel, force?: boolean | null | undefined): void;
+   * }
+   * ```
+   *
+   * @param {string} path - Path to config file.
+   * @param {ConfigLevel} level - The priority level of the config file.
+   * @param {boolean} [force] - Replace the config file which already exists at the same level.
+   * Defaults to `false`.
+   */
+  addFile(path: string, level: ConfigLevel, force?: boolean | undefined | null): void
+  /**
+   * Build a single-level focused config object from a multi-level one.
+   *
+   * The returned config object can be used to perform get/set/delete
+   * operations on a single specific level. This is useful to write values
+   * to a chosen file, e.g. repository local config without touching the
+   * global config.
+   *
+   * @category Config/Methods
+   * @signature
+   * ```ts
+   * class Config {
+   *   openLevel(level: ConfigLevel): Config;
+   * }
+   * ```
+   *
+   * @param {ConfigLevel} level - The level of config file to open.
+   * @returns Config instance which only contains the given level.
+   * @throws Throws error if there is no config file at the given level.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('.');
+   * const local = repo.config().openLevel('Local');
+   * local.setString('user.email', 'seokju.me@toss.im');
+   * ```
+   */
+  openLevel(level: ConfigLevel): Config
+  /**
+   * Open the global/XDG configuration file according to git's rules.
+   *
+   * Git allows you to store your global configuration at `$HOME/.gitconfig`
+   * or `$XDG_CONFIG_HOME/git/config`. For backwards compatibility, the XDG
+   * file shouldn't be used unless the user has created it explicitly. With
+   * this function you'll open the correct one to write to.
+   *
+   * @category Config/Methods
+   * @signature
+   * ```ts
+   * class Config {
+   *   openGlobal(): Config;
+   * }
+   * ```
+   *
+   * @returns Config instance which only contains the global configuration file.
+   */
+  openGlobal(): Config
+  /**
+   * Create a snapshot of the current state of a configuration, which allows
+   * you to look into a consistent view of the configuration for looking up
+   * complex values (e.g. a remote, submodule).
+   *
+   * The snapshot is read-only.
+   *
+   * @category Config/Methods
+   * @signature
+   * ```ts
+   * class Config {
+   *   snapshot(): Config;
+   * }
+   * ```
+   *
+   * @returns Read-only snapshot of this config.
+   */
+  snapshot(): Config
 }
 
 /**
@@ -1103,6 +1590,26 @@ export declare class Describe {
    * @returns Formatted string for this describe.
    */
   format(options?: DescribeFormatOptions | undefined | null): string
+  /**
+   * Gets the structured result of this describe.
+   *
+   * @category Describe/Methods
+   * @signature
+   * ```ts
+   * class Describe {
+   *   details(options?: DescribeDetailsOptions | null | undefined): DescribeDetails;
+   * }
+   * ```
+   *
+   * @param {DescribeDetailsOptions} [options] - Options for the abbreviated commit id.
+   * @returns Tag name, distance, abbreviated commit id and dirty flag of this describe.
+   *
+   * @example
+   * ```ts
+   * const { tag, distance, abbreviatedOid, dirty } = repo.describe({ describeTags: true }).details();
+   * ```
+   */
+  details(options?: DescribeDetailsOptions | undefined | null): DescribeDetails
 }
 
 /**
@@ -1798,6 +2305,85 @@ export declare class Index {
    * @throws This method will fail in bare index instances.
    */
   updateAll(pathspecs: Array<string>, options?: IndexUpdateAllOptions | undefined | null): void
+  /**
+   * Stage only the selected hunks or lines of a file.
+   *
+   * The selection refers to the diff between the index and the working
+   * directory for the path (as in `diffIndexToWorkdir`). New content is
+   * built from the index version with selected changes applied and written
+   * into this index. The working directory file is not touched.
+   *
+   * Like `git add`, the working directory file is read with clean filters
+   * (e.g. `core.autocrlf`) applied, and a new file is staged with its
+   * executable bit.
+   *
+   * @category Index/Methods
+   * @signature
+   * ```ts
+   * class Index {
+   *   stageHunks(
+   *     repo: Repository,
+   *     path: string,
+   *     selection: HunkSelection[],
+   *     options?: DiffOptions | null | undefined,
+   *   ): void;
+   * }
+   * ```
+   *
+   * @param {Repository} repo - Repository which owns this index.
+   * @param {string} path - Relative file path to the repository's working directory.
+   * @param {HunkSelection[]} selection - Hunks or lines to stage.
+   * @param {DiffOptions} [options] - Options used to generate the diff. Must match the options
+   * used to display hunks, so that hunk and line indices refer to the same lines.
+   *
+   * @throws This method will fail in bare repositories.
+   *
+   * @example
+   *
+   * Stage the first added line of the second hunk.
+   *
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('.');
+   * const index = repo.index();
+   * index.stageHunks(repo, 'README.md', [{ hunk: 1, lines: [3] }]);
+   * index.write();
+   * ```
+   */
+  stageHunks(repo: Repository, path: string, selection: Array<HunkSelection>, options?: DiffOptions | undefined | null): void
+  /**
+   * Unstage only the selected hunks or lines of a file.
+   *
+   * The selection refers to the diff between the `HEAD` tree and the index
+   * for the path. Selected changes are reverted to the `HEAD` version in
+   * this index. The working directory file is not touched.
+   *
+   * If the file doesn't exist in `HEAD` and every line is unstaged, the
+   * file is removed from this index.
+   *
+   * @category Index/Methods
+   * @signature
+   * ```ts
+   * class Index {
+   *   unstageHunks(
+   *     repo: Repository,
+   *     path: string,
+   *     selection: HunkSelection[],
+   *     options?: DiffOptions | null | undefined,
+   *   ): void;
+   * }
+   * ```
+   *
+   * @param {Repository} repo - Repository which owns this index.
+   * @param {string} path - Relative file path to the repository's working directory.
+   * @param {HunkSelection[]} selection - Hunks or lines to unstage.
+   * @param {DiffOptions} [options] - Options used to generate the diff. Must match the options
+   * used to display hunks, so that hunk and line indices refer to the same lines.
+   *
+   * @throws Throws error if the path does not exist in the index.
+   */
+  unstageHunks(repo: Repository, path: string, selection: Array<HunkSelection>, options?: DiffOptions | undefined | null): void
   /**
    * Get the count of entries currently in the index.
    *
@@ -2667,13 +3253,14 @@ export declare class Remote {
    *     refspecs: string[],
    *     options?: FetchRemoteOptions | null | undefined,
    *     signal?: AbortSignal | null | undefined,
-   *   ): Promise<void>;
+   *   ): Promise<UpdatedTip[]>;
    * }
    * ```
    *
    * @param {string[]} refspecs - Refspecs to fetch from remote.
    * @param {FetchRemoteOptions} [options] - Options for fetch remote.
    * @param {AbortSignal} [signal] Abort signal.
+   * @returns References updated by the fetch.
    *
    * @example
    * ```ts
@@ -2687,15 +3274,23 @@ export declare class Remote {
    *
    * // Providing an empty array fetches data using the default Refspec configured for the remote
    * await remote.fetch([]);
+   *
+   * // Check which references are updated
+   * const tips = await remote.fetch([]);
+   * console.log(tips);
+   * // [{ refname: 'refs/remotes/origin/main', oldOid: '...', newOid: '...', status: 'FastForward' }]
    * ```
    */
-  fetch(refspecs: Array<string>, options?: FetchRemoteOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<void>
+  fetch(refspecs: Array<string>, options?: FetchRemoteOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<Array<UpdatedTip>>
   /**
    * Perform a push.
    *
    * Perform all the steps for a push.
    * If no refspecs are passed, then the configured refspecs will be used.
    *
+   * Atomic pushes (`git push --atomic`) are not supported, because libgit2
+   * can't request the `atomic` capability of the remote.
+   *
    * @category Remote/Methods
    * @signature
    * ```ts
@@ -2704,13 +3299,17 @@ export declare class Remote {
    *     refspecs: string[],
    *     options?: PushOptions | null | undefined,
    *     signal?: AbortSignal | null | undefined,
-   *   ): Promise<void>;
+   *   ): Promise<PushRefResult[]>;
    * }
    * ```
    *
    * @param {string[]} refspecs - Refspecs to push to remote.
    * @param {FetchRemoteOptions} [options] - Options for push remote.
    * @param {AbortSignal} [signal] Abort signal.
+   * @returns Results of each pushed reference. References rejected by the remote are returned with
+   * the `Rejected` status only if `throwOnRejected` is `false`.
+   * @throws If the remote rejects any of the reference updates, unless `throwOnRejected` is `false`.
+   * @throws If `atomic` is `true`, since atomic pushes are not supported.
    *
    * @example
    * ```ts
@@ -2729,9 +3328,15 @@ export declare class Remote {
    *     password: '<personal access token>',
    *   },
    * });
+   *
+   * // Push only if the remote "main" branch is not changed by others.
+   * const results = await remote.push(['+refs/heads/main:refs/heads/main'], {
+   *   leases: [{ refname: 'refs/heads/main', expected: '<oid of the remote branch>' }],
+   * });
+   * console.log(results); // [{ refname: 'refs/heads/main', status: 'Ok' }]
    * ```
    */
-  push(refspecs: Array<string>, options?: PushOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<void>
+  push(refspecs: Array<string>, options?: PushOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<Array<PushRefResult>>
   /**
    * Prune tracking refs that are no longer present on remote.
    *
@@ -2775,6 +3380,89 @@ export declare class Remote {
    * ```
    */
   defaultBranch(signal?: AbortSignal | undefined | null): Promise<string>
+  /**
+   * Open a connection to the remote and receive its advertised references.
+   *
+   * No objects are downloaded. The connection is closed once the references have been
+   * received; use `list()` to read them afterwards.
+   *
+   * @category Remote/Methods
+   * @signature
+   * ```ts
+   * class Remote {
+   *   connect(
+   *     direction: Direction,
+   *     options?: ConnectRemoteOptions | null | undefined,
+   *     signal?: AbortSignal | null | undefined,
+   *   ): Promise<void>;
+   * }
+   * ```
+   *
+   * @param {Direction} direction - Whether the connection is for fetching or pushing.
+   * @param {ConnectRemoteOptions} [options] - Options for connecting to the remote.
+   * @param {AbortSignal} [signal] Abort signal.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * const remote = repo.getRemote('origin');
+   *
+   * await remote.connect('Fetch');
+   * const main = remote.list().find(x => x.name === 'refs/heads/main');
+   * console.log(main?.oid);
+   * ```
+   */
+  connect(direction: Direction, options?: ConnectRemoteOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<void>
+  /**
+   * Get the remote repository's reference advertisement list.
+   *
+   * The list is available after `connect()` has been called, and is replaced on every new connection.
+   * Peeled entries of annotated tags (`refs/tags/v1^{}`) are folded into the `peeled` field of the tag
+   * they belong to.
+   *
+   * The capabilities advertised by the server are not exposed, because libgit2 doesn't provide
+   * them through its public API.
+   *
+   * @category Remote/Methods
+   * @signature
+   * ```ts
+   * class Remote {
+   *   list(): RemoteHead[];
+   * }
+   * ```
+   *
+   * @returns References advertised by the remote, in the order they were advertised.
+   * @throws Throws error if the remote has never connected.
+   */
+  list(): Array<RemoteHead>
+  /**
+   * Get the statistics of the data transferred by the last fetch.
+   *
+   * @category Remote/Methods
+   * @signature
+   * ```ts
+   * class Remote {
+   *   stats(): TransferStats;
+   * }
+   * ```
+   *
+   * @returns Statistics of the transferred data.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * const remote = repo.getRemote('origin');
+   *
+   * await remote.fetch([]);
+   * const stats = remote.stats();
+   * console.log(`received ${stats.receivedObjects} objects (${stats.receivedBytes} bytes)`);
+   * ```
+   */
+  stats(): TransferStats
 }
 
 /**
@@ -2923,6 +3611,42 @@ export declare class Repository {
    * @throws If the file doesn't exist or can't be opened
    */
   blameFile(path: string, options?: BlameOptions | undefined | null): Blame
+  /**
+   * Asynchronously creates a blame object for the file at the given path.
+   *
+   * The `progress` callback of options is called every thousand lines with
+   * the number of blamed lines and the total number of lines to blame, while
+   * the lines are attributed to commits. Lines changed by ignored revisions
+   * are blamed again in this step, which takes most of the time then.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   blameFileAsync(
+   *     path: string,
+   *     options?: BlameOptions,
+   *     signal?: AbortSignal,
+   *   ): Promise<Blame>;
+   * }
+   * ```
+   *
+   * @param {string} path - Path to the file to blame
+   * @param {BlameOptions} [options] - Options to control blame behavior
+   * @param {AbortSignal} [signal] - Optional AbortSignal to cancel the operation.
+   * @returns Blame object for the specified file
+   * @throws If the file doesn't exist or can't be opened
+   *
+   * @example
+   * ```ts
+   * const blame = await repo.blameFileAsync('path/to/file.js', {
+   *   progress: (linesBlamed, totalLines) => {
+   *     console.log(`${linesBlamed}/${totalLines}`);
+   *   },
+   * });
+   * ```
+   */
+  blameFileAsync(path: string, options?: BlameOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<Blame>
   /**
    * Create a new branch pointing at a target commit
    *
@@ -3054,6 +3778,42 @@ export declare class Repository {
    * @param {CheckoutOptions} [options] - Options for checkout.
    */
   checkoutTree(treeish: GitObject, options?: CheckoutOptions | undefined | null): void
+  /**
+   * Check which files would be touched by checking out the tree pointed at
+   * by the treeish, without making any actual changes.
+   *
+   * Only files with the `Conflict`, `Dirty` and `Updated` types are listed.
+   * Conflicts do not throw an error, but are listed with the `Conflict`
+   * type instead.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   checkoutDryRun(
+   *     treeish?: GitObject | undefined | null,
+   *     options?: CheckoutOptions | undefined | null,
+   *   ): CheckoutNotify[];
+   * }
+   * ```
+   *
+   * @param {GitObject} [treeish] - Git object which tree pointed. If not given, `HEAD` will be used.
+   * @param {CheckoutOptions} [options] - Options for checkout. `dryRun` is always set.
+   * @returns Notifications for each file which would be touched by the checkout.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('.');
+   * const branch = repo.getBranch('main', 'Local');
+   * const result = repo.checkoutDryRun(branch.reference().peelToCommit().asObject());
+   * for (const conflict of result.filter(x => x.type === 'Conflict')) {
+   *   console.log(`${conflict.path} prevents switching branches`);
+   * }
+   * ```
+   */
+  checkoutDryRun(treeish?: GitObject | undefined | null, options?: CheckoutOptions | undefined | null): Array<CheckoutNotify>
   /**
    * Cherrypicks the given commit onto HEAD and updates the working tree and index.
    * This method prepares the index and tree as if the commit were applied, but does not actually make a new commit.
@@ -3203,27 +3963,132 @@ export declare class Repository {
    */
   commit(tree: Tree, message: string, options?: CommitOptions | undefined | null): string
   /**
-   * Get the configuration file for this repository.
+   * Create a commit object and return its raw content, without writing it
+   * to the object database.
+   *
+   * The returned buffer can be signed out-of-process and written with
+   * `commitSigned`.
    *
    * @category Repository/Methods
+   *
    * @signature
    * ```ts
    * class Repository {
-   *   config(): Config;
+   *   createCommitBuffer(
+   *     tree: Tree,
+   *     parents: string[],
+   *     author: SignaturePayload,
+   *     committer: SignaturePayload,
+   *     message: string,
+   *     options?: CreateCommitBufferOptions | null | undefined,
+   *   ): string;
    * }
    * ```
    *
-   * @returns If a configuration file has not been set, the default config set for the
-   * repository will be returned, including global and system configurations
-   * (if they are available).
+   * @param {Tree} tree - Tree of the commit.
+   * @param {string[]} parents - IDs of the parent commits.
+   * @param {SignaturePayload} author - Signature for author.
+   * @param {SignaturePayload} committer - Signature for committer.
+   * @param {string} message - Full message for the commit.
+   * @param {CreateCommitBufferOptions} [options] - Options for creating the commit buffer.
+   * @returns Raw content of the commit object.
+   * @throws If a header name is empty or contains a space or a newline.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('.');
+   * const signature = { name: 'Seokju Na', email: 'seokju.me@toss.im' };
+   * const buffer = repo.createCommitBuffer(
+   *   repo.head().peelToTree(),
+   *   [repo.head().target()!],
+   *   signature,
+   *   signature,
+   *   'commit message',
+   *   { headers: [{ name: 'change-id', value: 'I1234' }] },
+   * );
+   * const oid = repo.commitSigned(buffer, await signExternally(buffer));
+   * ```
    */
-  config(): Config
+  createCommitBuffer(tree: Tree, parents: Array<string>, author: SignaturePayload, committer: SignaturePayload, message: string, options?: CreateCommitBufferOptions | undefined | null): string
   /**
-   * Describes a commit
+   * Create a commit object from the given buffer and signature.
    *
-   * Performs a describe operation on the current commit and the worktree.
-   * After performing a describe on `HEAD`, a status is run and description is
-   * considered to be dirty if there are.
+   * Given the unsigned commit object's contents, its signature and the
+   * header field in which to store the signature, attach the signature to
+   * the commit and write it into the object database. No reference is
+   * updated.
+   *
+   * @category Repository/Methods
+   *
+   * @signature
+   * ```ts
+   * class Repository {
+   *   commitSigned(buffer: string, signature: string, field?: string | null | undefined): string;
+   * }
+   * ```
+   *
+   * @param {string} buffer - Raw content of the unsigned commit.
+   * @param {string} signature - Signature to attach to the commit.
+   * @param {string} [field] - Header field for the signature. Defaults to `gpgsig`.
+   * @returns ID(SHA1) of created commit.
+   */
+  commitSigned(buffer: string, signature: string, field?: string | undefined | null): string
+  /**
+   * Get the configuration file for this repository.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   config(): Config;
+   * }
+   * ```
+   *
+   * @returns If a configuration file has not been set, the default config set for the
+   * repository will be returned, including global and system configurations
+   * (if they are available).
+   */
+  config(): Config
+  /**
+   * Evaluate the condition of an `includeIf` section for this repository.
+   *
+   * Supported conditions are `gitdir:`, `gitdir/i:`, `onbranch:` and
+   * `hasconfig:remote.*.url:`. Conditions of other kinds never match.
+   *
+   * Included files with `gitdir:` and `onbranch:` conditions are already
+   * loaded by `config()`, and their values can be told apart with
+   * `ConfigEntry.includeDepth`. Files with `hasconfig:remote.*.url:`
+   * conditions are not loaded, but can be checked with this method.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   matchesIncludeIf(condition: string): boolean;
+   * }
+   * ```
+   *
+   * @param {string} condition - Condition of the section. (e.g. `gitdir:~/work/`)
+   * @returns Returns `true` if the condition matches.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('.');
+   * repo.matchesIncludeIf('onbranch:feature/');
+   * repo.matchesIncludeIf('hasconfig:remote.*.url:https://github.com/toss/**');
+   * ```
+   */
+  matchesIncludeIf(condition: string): boolean
+  /**
+   * Describes a commit
+   *
+   * Performs a describe operation on the current commit and the worktree.
+   * After performing a describe on `HEAD`, a status is run and description is
+   * considered to be dirty if there are.
    *
    * @category Repository/Methods
    * @signature
@@ -3237,6 +4102,32 @@ export declare class Repository {
    * @returns Instance of describe.
    */
   describe(options?: DescribeOptions | undefined | null): Describe
+  /**
+   * Names a commit relative to the references which contain it
+   *
+   * This behaves like git-name-rev. The name is like `tags/v1.0~2` or
+   * `main~1^2`, starting from the best reference which contains the commit.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   nameRev(oid: string, options?: NameRevOptions | null | undefined): string | null;
+   * }
+   * ```
+   *
+   * @param {string} oid - Id of the commit to name.
+   * @param {NameRevOptions} [options] - Options for naming the commit.
+   * @returns Name of the commit, or `null` if no reference contains the commit.
+   *
+   * @example
+   * ```ts
+   * repo.nameRev(oid); // 'main~2'
+   * repo.nameRev(oid, { tagsOnly: true }); // 'tags/v1.0~1'
+   * repo.nameRev(oid, { refs: ['v1.*'] }); // 'tags/v1.0~1'
+   * ```
+   */
+  nameRev(oid: string, options?: NameRevOptions | undefined | null): string | null
   /**
    * Create a diff with the difference between two tree objects.
    *
@@ -3467,6 +4358,38 @@ export declare class Repository {
    * @returns The index file for this repository.
    */
   index(): Index
+  /**
+   * Discard only the selected hunks or lines of a file in the working
+   * directory.
+   *
+   * The selection refers to the diff between the index and the working
+   * directory for the path (as in `diffIndexToWorkdir`). Selected changes
+   * are reverted to the index version in the working directory file. The
+   * index is not touched.
+   *
+   * The working directory file is read with clean filters and written by a
+   * checkout with smudge filters, like `git checkout -p`.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   discardHunks(
+   *     path: string,
+   *     selection: HunkSelection[],
+   *     options?: DiffOptions | null | undefined,
+   *   ): void;
+   * }
+   * ```
+   *
+   * @param {string} path - Relative file path to the repository's working directory.
+   * @param {HunkSelection[]} selection - Hunks or lines to discard.
+   * @param {DiffOptions} [options] - Options used to generate the diff. Must match the options
+   * used to display hunks, so that hunk and line indices refer to the same lines.
+   *
+   * @throws Throws error if the path does not exist in the index or the repository is bare.
+   */
+  discardHunks(path: string, selection: Array<HunkSelection>, options?: DiffOptions | undefined | null): void
   /**
    * Gets this repository's mailmap.
    *
@@ -4099,6 +5022,199 @@ export declare class Repository {
    * @returns Created remote.
    */
   createRemote(name: string, url: string, options?: CreateRemoteOptions | undefined | null): Remote
+  /**
+   * Create an anonymous remote.
+   *
+   * Create a remote with the given URL in-memory. You can use this when you have a URL instead of a remote's name.
+   * Nothing is written to the repository's configuration.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   createAnonymousRemote(url: string): Remote;
+   * }
+   * ```
+   *
+   * @param {string} url - Remote url.
+   * @returns Created anonymous remote.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * const remote = repo.createAnonymousRemote('https://github.com/toss/es-git');
+   * await remote.fetch(['main']);
+   * ```
+   */
+  createAnonymousRemote(url: string): Remote
+  /**
+   * Create a detached remote.
+   *
+   * Create a remote with the given URL in-memory, which is not attached to this repository.
+   * Detached remotes can be used to query a remote (e.g. list its references) without any local repository data.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   createDetachedRemote(url: string): Remote;
+   * }
+   * ```
+   *
+   * @param {string} url - Remote url.
+   * @returns Created detached remote.
+   */
+  createDetachedRemote(url: string): Remote
+  /**
+   * Give a remote a new name.
+   *
+   * All remote-tracking branches and configuration settings for the remote are updated.
+   *
+   * A temporary in-memory remote cannot be given a name with this method.
+   *
+   * No loaded instances of the remote with the old name will change their name or their list of refspecs.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   renameRemote(name: string, newName: string): string[];
+   * }
+   * ```
+   *
+   * @param {string} name - The current name of the remote.
+   * @param {string} newName - The new name the remote should bear.
+   * @returns Non-default refspecs which cannot be renamed and are returned for further processing by the caller.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * repo.renameRemote('origin', 'upstream');
+   * console.log(repo.remoteNames()); // ["upstream"]
+   * ```
+   */
+  renameRemote(name: string, newName: string): Array<string>
+  /**
+   * Delete an existing persisted remote.
+   *
+   * All remote-tracking branches and configuration settings for the remote will be removed.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   deleteRemote(name: string): void;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of the remote to delete.
+   * @throws Throws error if remote does not exist.
+   */
+  deleteRemote(name: string): void
+  /**
+   * Set the remote's URL in the configuration.
+   *
+   * Remote objects already in memory will not be affected. This assumes the common case of a single-url remote and will otherwise throw an error.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   remoteSetUrl(name: string, url: string): void;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of the remote.
+   * @param {string} url - The URL to set.
+   */
+  remoteSetUrl(name: string, url: string): void
+  /**
+   * Set the remote's URL for pushing in the configuration.
+   *
+   * Remote objects already in memory will not be affected. This assumes the common case of a single-url remote and will otherwise throw an error.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   remoteSetPushurl(name: string, pushurl?: string | null | undefined): void;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of the remote.
+   * @param {string} [pushurl] - The push URL to set. If not provided, the push URL is removed.
+   */
+  remoteSetPushurl(name: string, pushurl?: string | undefined | null): void
+  /**
+   * Add a fetch refspec to the remote's configuration.
+   *
+   * Add the given refspec to the fetch list in the configuration. No loaded remote instances will be affected.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   remoteAddFetch(name: string, refspec: string): void;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of the remote.
+   * @param {string} refspec - The fetch refspec to add.
+   */
+  remoteAddFetch(name: string, refspec: string): void
+  /**
+   * Add a push refspec to the remote's configuration.
+   *
+   * Add the given refspec to the push list in the configuration. No loaded remote instances will be affected.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   remoteAddPush(name: string, refspec: string): void;
+   * }
+   * ```
+   *
+   * @param {string} name - The name of the remote.
+   * @param {string} refspec - The push refspec to add.
+   */
+  remoteAddPush(name: string, refspec: string): void
+  /**
+   * Iterate over each entry of `FETCH_HEAD` calling `callback` on each.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   fetchheadForeach(
+   *     callback: (refname: string, remoteUrl: string, oid: string, isMerge: boolean) => boolean,
+   *   ): void;
+   * }
+   * ```
+   *
+   * @param {(refname: string, remoteUrl: string, oid: string, isMerge: boolean) => boolean} callback - Called with
+   * the reference name, the remote URL, the object id and whether the entry is to be merged. If you wish to stop
+   * iteration, return `false` in the callback.
+   * @throws Throws error if `FETCH_HEAD` does not exist, or the error thrown by the callback.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * await repo.getRemote('origin').fetch(['main']);
+   * repo.fetchheadForeach((refname, remoteUrl, oid, isMerge) => {
+   *   console.log(refname, remoteUrl, oid, isMerge);
+   *   // "refs/heads/main" "https://github.com/toss/es-git" "..." true
+   *   return true;
+   * });
+   * ```
+   */
+  fetchheadForeach(callback: (refname: string, remoteUrl: string, oid: string, isMerge: boolean) => boolean): void
   /**
    * Tests whether this repository is a bare repository or not.
    *
@@ -4127,6 +5243,33 @@ export declare class Repository {
    * @returns Returns `true` if repository is a shallow clone.
    */
   isShallow(): boolean
+  /**
+   * Get the commits at which the history of this shallow repository is cut off.
+   *
+   * These are the object ids listed in the `shallow` file of the git directory, which is shared
+   * by all worktrees of the repository.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   shallowRoots(): string[];
+   * }
+   * ```
+   *
+   * @returns Object ids of the shallow roots. Returns an empty array if the repository is not shallow.
+   *
+   * @example
+   * ```ts
+   * import { cloneRepository } from 'es-git';
+   *
+   * const repo = await cloneRepository('https://github.com/toss/es-git', '/path/to/clone', {
+   *   fetch: { depth: 1 },
+   * });
+   * console.log(repo.shallowRoots()); // ["a01e9888e46729ef4aa68953ba19b02a7a64eb82"]
+   * ```
+   */
+  shallowRoots(): Array<string>
   /**
    * Tests whether this repository is empty.
    *
@@ -4333,54 +5476,119 @@ export declare class Repository {
    */
   extractSignature(oid: string): ExtractedSignature | null
   /**
-   * Remove all the metadata associated with an ongoing command like merge,
-   * revert, cherry-pick, etc. For example: `MERGE_HEAD`, `MERGE_MSG`, etc.
+   * Verify the signature of a commit.
+   *
+   * The signature is verified with the program matching its format: `gpg`
+   * for OpenPGP, `gpgsm` for X.509 and `ssh-keygen` for SSH signatures.
    *
    * @category Repository/Methods
    * @signature
    * ```ts
    * class Repository {
-   *   cleanupState(): void;
+   *   verifyCommitSignature(
+   *     oid: string,
+   *     options?: VerifySignatureOptions | null | undefined,
+   *   ): SignatureVerification | null;
    * }
    * ```
    *
+   * @param {string} oid - Commit ID(SHA1) to verify.
+   * @param {VerifySignatureOptions} [options] - Options for verifying the signature.
+   * @returns Result of the verification. Returns `null` if the commit is not signed.
+   *
    * @example
    * ```ts
    * import { openRepository } from 'es-git';
    *
-   * const repo = await openRepository('./repo');
-   * // After revert or merge operations:
-   * if (repo.state() !== 'Clean') {
-   *   repo.cleanupState();
+   * const repo = await openRepository('.');
+   * const result = repo.verifyCommitSignature(repo.head().target()!, {
+   *   allowedSigners: '/path/to/allowed_signers',
+   * });
+   * if (result?.valid) {
+   *   console.log(`Signed by ${result.signer} (${result.fingerprint})`);
    * }
    * ```
    */
-  cleanupState(): void
+  verifyCommitSignature(oid: string, options?: VerifySignatureOptions | undefined | null): SignatureVerification | null
   /**
-   * Reverts the given commit, applying the inverse of its changes to the
-   * HEAD commit and the working directory.
+   * Verify the signature of an annotated tag.
    *
    * @category Repository/Methods
    * @signature
    * ```ts
    * class Repository {
-   *   revert(
-   *     commit: Commit,
-   *     options?: RevertOptions | undefined | null,
-   *   ): void;
+   *   verifyTagSignature(
+   *     oid: string,
+   *     options?: VerifySignatureOptions | null | undefined,
+   *   ): SignatureVerification | null;
    * }
    * ```
    *
-   * @param {Commit} commit - The commit to revert.
-   * @param {RevertOptions} [options] - Options for the revert operation.
-   * @throws {Error} If the commit is a merge commit and no mainline is specified.
-   * @throws {Error} If there are conflicts during the revert operation.
+   * @param {string} oid - Tag ID(SHA1) or tag name to verify.
+   * @param {VerifySignatureOptions} [options] - Options for verifying the signature.
+   * @returns Result of the verification. Returns `null` if the tag is not signed.
    *
    * @example
    * ```ts
    * import { openRepository } from 'es-git';
    *
-   * const repo = await openRepository('./path/to/repo');
+   * const repo = await openRepository('.');
+   * const result = repo.verifyTagSignature('v1.0.0');
+   * if (!result?.valid || result.trust !== 'ultimate') {
+   *   throw new Error('Release tag is not signed by a trusted key');
+   * }
+   * ```
+   */
+  verifyTagSignature(oid: string, options?: VerifySignatureOptions | undefined | null): SignatureVerification | null
+  /**
+   * Remove all the metadata associated with an ongoing command like merge,
+   * revert, cherry-pick, etc. For example: `MERGE_HEAD`, `MERGE_MSG`, etc.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   cleanupState(): void;
+   * }
+   * ```
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('./repo');
+   * // After revert or merge operations:
+   * if (repo.state() !== 'Clean') {
+   *   repo.cleanupState();
+   * }
+   * ```
+   */
+  cleanupState(): void
+  /**
+   * Reverts the given commit, applying the inverse of its changes to the
+   * HEAD commit and the working directory.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   revert(
+   *     commit: Commit,
+   *     options?: RevertOptions | undefined | null,
+   *   ): void;
+   * }
+   * ```
+   *
+   * @param {Commit} commit - The commit to revert.
+   * @param {RevertOptions} [options] - Options for the revert operation.
+   * @throws {Error} If the commit is a merge commit and no mainline is specified.
+   * @throws {Error} If there are conflicts during the revert operation.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('./path/to/repo');
    * const last = repo.head().target()!;
    * const commit = repo.getCommit(last);
    *
@@ -4651,6 +5859,67 @@ export declare class Repository {
    * ```
    */
   stashList(): StashList
+  /**
+   * Get the changes recorded in a stash, like `git stash show -p`.
+   *
+   * The diff is between the commit at which the stash was created and the
+   * stashed state of the working directory.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   stashShow(index: number, options?: StashShowOptions): Diff;
+   * }
+   * ```
+   *
+   * @param {number} index - The index of the stash to show (0 is the most recent).
+   * @param {StashShowOptions} [options] - Options for showing the stash.
+   * @returns {Diff} Diff of the changes recorded in the stash.
+   * @throws {Error} If the stash index is invalid.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('./path/to/repo');
+   * const diff = repo.stashShow(0, { includeUntracked: true });
+   * for (const delta of diff.deltas()) {
+   *   console.log(delta.newFile().path());
+   * }
+   * ```
+   */
+  stashShow(index: number, options?: StashShowOptions | undefined | null): Diff
+  /**
+   * Create and check out a new branch from the commit at which the stash was
+   * created, and apply the stash on it, like `git stash branch`.
+   *
+   * The index state recorded in the stash is also restored. If the stash is
+   * applied successfully, it is removed from the stash list.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   stashBranch(name: string, index: number, options?: StashApplyOptions): void;
+   * }
+   * ```
+   *
+   * @param {string} name - Name of the branch to create.
+   * @param {number} index - The index of the stash to apply (0 is the most recent).
+   * @param {StashApplyOptions} [options] - Options for applying the stash.
+   * @throws {Error} If the stash index is invalid, the branch already exists or if there are
+   * conflicts during application.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('./path/to/repo');
+   * repo.stashBranch('wip-feature', 0);
+   * ```
+   */
+  stashBranch(name: string, index: number, options?: StashApplyOptions | undefined | null): void
   /**
    * Test if the ignore rules apply to a given file.
    *
@@ -4869,6 +6138,34 @@ export declare class Repository {
    * ```
    */
   submoduleStatus(name: string, ignore: SubmoduleIgnore): number
+  /**
+   * Summarize the submodules of the repository, like `git submodule status`
+   * and `git submodule summary`.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   submoduleSummary(options?: SubmoduleSummaryOptions | null | undefined): SubmoduleSummary[];
+   * }
+   * ```
+   *
+   * @param {SubmoduleSummaryOptions} [options] - Options to summarize submodules.
+   * @returns Summaries of the submodules.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * for (const summary of repo.submoduleSummary({ recursive: true })) {
+   *   if (summary.ahead || summary.behind) {
+   *     console.log(`${summary.path}: +${summary.ahead} -${summary.behind}`);
+   *   }
+   * }
+   * ```
+   */
+  submoduleSummary(options?: SubmoduleSummaryOptions | undefined | null): Array<SubmoduleSummary>
   /**
    * Set the ignore rule for the submodule in the configuration
    *
@@ -4939,6 +6236,103 @@ export declare class Repository {
    * @param {string} branchName - Branch that should be used for the submodule
    */
   submoduleSetBranch(name: string, branchName: string): void
+  /**
+   * Add a submodule, like `git submodule add`.
+   *
+   * This sets up the submodule, clones the repository, checks out the branch
+   * and stages the submodule and `.gitmodules` in the index to be ready to
+   * commit.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   submoduleAdd(
+   *     url: string,
+   *     path: string,
+   *     options?: SubmoduleAddOptions | null | undefined,
+   *     signal?: AbortSignal | null | undefined,
+   *   ): Promise<Submodule>;
+   * }
+   * ```
+   *
+   * @param {string} url - URL for the submodule's remote.
+   * @param {string} path - Path at which the submodule should be created.
+   * @param {SubmoduleAddOptions} [options] - Options to add the submodule.
+   * @param {AbortSignal} [signal] - Optional AbortSignal to cancel the operation.
+   * @returns The added submodule.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * await repo.submoduleAdd('https://github.com/toss/es-toolkit', 'vendor/es-toolkit', {
+   *   branch: 'main',
+   *   depth: 1,
+   * });
+   * ```
+   */
+  submoduleAdd(url: string, path: string, options?: SubmoduleAddOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<Submodule>
+  /**
+   * Update all submodules of the repository, like `git submodule update`.
+   *
+   * Submodules which are not initialized are skipped, unless `init` is set.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   submoduleUpdateAll(
+   *     options?: SubmoduleUpdateAllOptions | null | undefined,
+   *     signal?: AbortSignal | null | undefined,
+   *   ): Promise<void>;
+   * }
+   * ```
+   *
+   * @param {SubmoduleUpdateAllOptions} [options] - Options to update submodules.
+   * @param {AbortSignal} [signal] - Optional AbortSignal to cancel the operation.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * // Same as `git submodule update --init --recursive`
+   * await repo.submoduleUpdateAll({ init: true, recursive: true });
+   * ```
+   */
+  submoduleUpdateAll(options?: SubmoduleUpdateAllOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<void>
+  /**
+   * Call the callback for each submodule which is checked out, like
+   * `git submodule foreach`.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   submoduleForeach(
+   *     callback: (submodule: SubmoduleForeachEntry) => boolean | void,
+   *     options?: SubmoduleForeachOptions | null | undefined,
+   *   ): void;
+   * }
+   * ```
+   *
+   * @param {(submodule: SubmoduleForeachEntry) => boolean | void} callback - Callback for each
+   * submodule. Returning `false` stops the iteration.
+   * @param {SubmoduleForeachOptions} [options] - Options to iterate submodules.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * repo.submoduleForeach(({ path, oid }) => {
+   *   console.log(`${path}: ${oid}`);
+   * }, { recursive: true });
+   * ```
+   */
+  submoduleForeach(callback: (submodule: SubmoduleForeachEntry) => boolean | void, options?: SubmoduleForeachOptions | undefined | null): void
   /**
    * Lookup a tag object by prefix hash from the repository.
    *
@@ -5206,6 +6600,62 @@ export declare class Repository {
    * @throws Throws error if listing worktrees fails (e.g., filesystem errors or repository corruption).
    */
   worktrees(): Array<string>
+  /**
+   * List the main worktree and linked worktrees of the repository with their
+   * details, like `git worktree list --porcelain`.
+   *
+   * @category Repository/Methods
+   *
+   * @signature
+   * ```ts
+   * class Repository {
+   *   worktreeList(): WorktreeInfo[];
+   * }
+   * ```
+   *
+   * @returns Details of the worktrees. The main worktree comes first.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('.');
+   * for (const worktree of repo.worktreeList()) {
+   *   console.log(worktree.path, worktree.branch ?? worktree.head);
+   * }
+   * ```
+   */
+  worktreeList(): Array<WorktreeInfo>
+  /**
+   * Repair the links between the repository and its worktrees, like
+   * `git worktree repair`.
+   *
+   * The `.git` file of each worktree is fixed to point to the repository. If
+   * a worktree was moved manually, pass its new path to fix the repository's
+   * link to the worktree.
+   *
+   * @category Repository/Methods
+   *
+   * @signature
+   * ```ts
+   * class Repository {
+   *   worktreeRepair(paths?: string[] | null | undefined): string[];
+   * }
+   * ```
+   *
+   * @param {string[]} [paths] - New paths of worktrees which were moved manually.
+   * @returns Names of the repaired worktrees.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('.');
+   * // The worktree is moved with `mv ../feature ../feature-moved`.
+   * repo.worktreeRepair(['../feature-moved']);
+   * ```
+   */
+  worktreeRepair(paths?: Array<string> | undefined | null): Array<string>
   /**
    * Tests whether this repository is a worktree.
    *
@@ -5237,6 +6687,151 @@ export declare class Repository {
    * @throws Throws error if the worktree is not found or if opening fails.
    */
   findWorktree(name: string): Worktree
+  /**
+   * Get the sparse checkout of this repository.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   get sparseCheckout(): SparseCheckout;
+   * }
+   * ```
+   *
+   * @returns Sparse checkout of this repository.
+   */
+  get sparseCheckout(): SparseCheckout
+  /**
+   * Run a [hook][1] of the repository.
+   *
+   * Hooks are looked up in the directory of `core.hooksPath` config, or the
+   * `hooks` directory of the git directory. The hook runs in the root of the
+   * working tree.
+   *
+   * [1]: https://git-scm.com/docs/githooks
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   runHook(
+   *     name: string,
+   *     args?: string[] | null | undefined,
+   *     options?: RunHookOptions | null | undefined,
+   *   ): HookResult | null;
+   * }
+   * ```
+   *
+   * @param {string} name - Name of the hook (e.g. `pre-commit`).
+   * @param {string[]} [args] - Arguments passed to the hook.
+   * @param {RunHookOptions} [options] - Options for running the hook.
+   * @returns Result of the hook. Returns `null` if the hook does not exist or is not executable.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * const result = repo.runHook('pre-push', ['origin', 'https://github.com/toss/es-git'], {
+   *   stdin: 'refs/heads/main 67e2f2b refs/heads/main 0000000\n',
+   * });
+   * if (result != null && result.exitCode !== 0) {
+   *   console.error(result.stderr);
+   * }
+   * ```
+   */
+  runHook(name: string, args?: Array<string> | undefined | null, options?: RunHookOptions | undefined | null): HookResult | null
+  /**
+   * Serve a request of the upload-pack service, which sends objects to
+   * clients fetching from this repository.
+   *
+   * Requests and responses are in the format of smart HTTP with protocol
+   * version 0 or 2. If the request is not provided or empty, returns the
+   * reference advertisement (the response of
+   * `info/refs?service=git-upload-pack`), or the capability advertisement for
+   * protocol version 2. Otherwise, the request is the body of
+   * `POST git-upload-pack`.
+   *
+   * Only objects which are advertised or reachable from advertised commits
+   * can be wanted. Shallow and partial fetches are not supported.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   serveUploadPack(
+   *     request?: Buffer | null | undefined,
+   *     options?: ServeUploadPackOptions | null | undefined,
+   *   ): Buffer;
+   * }
+   * ```
+   *
+   * @param {Buffer} [request] - Body of the request.
+   * @param {ServeUploadPackOptions} [options] - Options for upload-pack.
+   * @returns Body of the response, or an empty buffer if `onData` is set.
+   *
+   * @example
+   * ```ts
+   * import { openRepository, registerTransport } from 'es-git';
+   *
+   * const server = await openRepository('/path/to/server');
+   * registerTransport('myproto', (url, service, body) => {
+   *   switch (service) {
+   *     case 'UploadPackLs':
+   *     case 'UploadPack':
+   *       return server.serveUploadPack(body);
+   *     case 'ReceivePackLs':
+   *     case 'ReceivePack':
+   *       return server.serveReceivePack(body);
+   *   }
+   * });
+   * ```
+   */
+  serveUploadPack(request?: Buffer | undefined | null, options?: ServeUploadPackOptions | undefined | null): Buffer
+  /**
+   * Serve a request of the receive-pack service, which receives objects and
+   * updates references from clients pushing to this repository.
+   *
+   * Requests and responses are in the format of smart HTTP with protocol
+   * version 0. If the request is not provided or empty, returns the reference
+   * advertisement (the response of `info/refs?service=git-receive-pack`).
+   * Otherwise, the request is the body of `POST git-receive-pack`.
+   *
+   * Protocol version 2 doesn't define receive-pack, so pushes always use
+   * protocol version 0 as git does.
+   *
+   * Updating the checked out branch of a non-bare repository is rejected.
+   *
+   * @category Repository/Methods
+   * @signature
+   * ```ts
+   * class Repository {
+   *   serveReceivePack(
+   *     request?: Buffer | null | undefined,
+   *     options?: ServeReceivePackOptions | null | undefined,
+   *   ): Buffer;
+   * }
+   * ```
+   *
+   * @param {Buffer} [request] - Body of the request.
+   * @param {ServeReceivePackOptions} [options] - Options for receive-pack.
+   * @returns Body of the response.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const server = await openRepository('/path/to/server');
+   * const response = server.serveReceivePack(request, {
+   *   onUpdate: update => {
+   *     if (update.refname === 'refs/heads/main' && update.newOid === '0000000000000000000000000000000000000000') {
+   *       return 'deleting main branch is not allowed';
+   *     }
+   *   },
+   * });
+   * ```
+   */
+  serveReceivePack(request?: Buffer | undefined | null, options?: ServeReceivePackOptions | undefined | null): Buffer
 }
 
 /**
@@ -5422,34 +7017,153 @@ export declare class Revwalk {
    * The OIDs pointed to by the references that match the given glob pattern
    * and their ancestors will be hidden from the output on the revision walk.
    *
-   * @category Revwalk/Methods
+   * @category Revwalk/Methods
+   * @signature
+   * ```ts
+   * class Revwalk {
+   *   hideGlob(glob: string): this;
+   * }
+   * ```
+   *
+   * @param {string} glob - A leading 'refs/' is implied if not present as well as a trailing `/ \
+   * *` if the glob lacks '?', ' \ *' or '['.
+   * Any references matching this glob which do not point to a commitish
+   * will be ignored.
+   */
+  hideGlob(glob: string): this
+  /**
+   * Hide the OID pointed to by a reference.
+   *
+   * @category Revwalk/Methods
+   * @signature
+   * ```ts
+   * class Revwalk {
+   *   hideRef(reference: string): this;
+   * }
+   * ```
+   *
+   * @param {string} reference - The reference must point to a commitish.
+   */
+  hideRef(reference: string): this
+}
+
+/**
+ * A class to manage the [sparse checkout][1] of a repository.
+ *
+ * Patterns are stored in `info/sparse-checkout` of the git directory, so
+ * they are shared with the `git sparse-checkout` command.
+ *
+ * Sparse checkout only limits the files in the working tree. Partial clone
+ * filters (the `filter` fetch option) are not supported because libgit2
+ * can't negotiate them, so clones and fetches still download all objects.
+ *
+ * [1]: https://git-scm.com/docs/git-sparse-checkout
+ */
+export declare class SparseCheckout {
+  /**
+   * Check if sparse checkout is enabled.
+   *
+   * @category SparseCheckout/Methods
+   * @signature
+   * ```ts
+   * class SparseCheckout {
+   *   isEnabled(): boolean;
+   * }
+   * ```
+   *
+   * @returns Returns `true` if the `core.sparseCheckout` config is enabled.
+   */
+  isEnabled(): boolean
+  /**
+   * Enable sparse checkout and replace the patterns with the given ones.
+   *
+   * Files which are no longer matched are removed from the working tree
+   * (unless they have local modifications), and files which are matched are
+   * checked out.
+   *
+   * @category SparseCheckout/Methods
+   * @signature
+   * ```ts
+   * class SparseCheckout {
+   *   set(patterns: string[], options?: SparseCheckoutSetOptions | null | undefined): void;
+   * }
+   * ```
+   *
+   * @param {string[]} patterns - Directories in cone mode, or gitignore-style patterns otherwise.
+   * @param {SparseCheckoutSetOptions} [options] - Options for setting sparse checkout.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('/path/to/repo');
+   * repo.sparseCheckout.set(['packages/app', 'tools']);
+   * console.log(repo.sparseCheckout.list()); // ["packages/app", "tools"]
+   * ```
+   */
+  set(patterns: Array<string>, options?: SparseCheckoutSetOptions | undefined | null): void
+  /**
+   * Add patterns to the sparse checkout.
+   *
+   * The patterns are interpreted the same way as the existing patterns,
+   * depending on whether cone mode is in use.
+   *
+   * @category SparseCheckout/Methods
+   * @signature
+   * ```ts
+   * class SparseCheckout {
+   *   add(patterns: string[]): void;
+   * }
+   * ```
+   *
+   * @param {string[]} patterns - Patterns to add.
+   * @throws Throws error if sparse checkout is not enabled.
+   */
+  add(patterns: Array<string>): void
+  /**
+   * List the sparse checkout patterns.
+   *
+   * @category SparseCheckout/Methods
+   * @signature
+   * ```ts
+   * class SparseCheckout {
+   *   list(): string[];
+   * }
+   * ```
+   *
+   * @returns Directories in cone mode, or the patterns otherwise.
+   * @throws Throws error if sparse checkout is not enabled.
+   */
+  list(): Array<string>
+  /**
+   * Update the working tree to match the sparse checkout patterns again.
+   *
+   * This is useful when files outside of the patterns were brought back
+   * by other operations.
+   *
+   * @category SparseCheckout/Methods
    * @signature
    * ```ts
-   * class Revwalk {
-   *   hideGlob(glob: string): this;
+   * class SparseCheckout {
+   *   reapply(): void;
    * }
    * ```
-   *
-   * @param {string} glob - A leading 'refs/' is implied if not present as well as a trailing `/ \
-   * *` if the glob lacks '?', ' \ *' or '['.
-   * Any references matching this glob which do not point to a commitish
-   * will be ignored.
    */
-  hideGlob(glob: string): this
+  reapply(): void
   /**
-   * Hide the OID pointed to by a reference.
+   * Disable sparse checkout and restore all files in the working tree.
    *
-   * @category Revwalk/Methods
+   * The patterns are kept, so that sparse checkout can be enabled again.
+   *
+   * @category SparseCheckout/Methods
    * @signature
    * ```ts
-   * class Revwalk {
-   *   hideRef(reference: string): this;
+   * class SparseCheckout {
+   *   disable(): void;
    * }
    * ```
-   *
-   * @param {string} reference - The reference must point to a commitish.
    */
-  hideRef(reference: string): this
+  disable(): void
 }
 
 /**
@@ -5539,6 +7253,31 @@ export declare class StashEntry {
    * ```
    */
   message(): string | null
+  /**
+   * Get the diff between the commit at which this stash was created and the
+   * stash, like `git stash show`.
+   *
+   * @category Stash/Methods
+   * @signature
+   * ```ts
+   * class StashEntry {
+   *   diff(options?: StashShowOptions): Diff;
+   * }
+   * ```
+   *
+   * @param {StashShowOptions} [options] - Options for showing the stash.
+   * @returns {Diff} Diff of the changes stashed in this entry.
+   *
+   * @example
+   * ```ts
+   * import { openRepository } from 'es-git';
+   *
+   * const repo = await openRepository('./path/to/repo');
+   * const stash = repo.stashList().get(0);
+   * const diff = stash?.diff({ includeUntracked: true });
+   * ```
+   */
+  diff(options?: StashShowOptions | undefined | null): Diff
 }
 
 /**
@@ -6122,6 +7861,28 @@ export declare class Submodule {
    * @returns The newly created repository object.
    */
   clone(options?: SubmoduleUpdateOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<Repository>
+  /**
+   * Deinitialize the submodule, like `git submodule deinit`.
+   *
+   * This removes the working tree of the submodule (leaving an empty
+   * directory) and the `submodule.<name>.*` entries from the repository
+   * configuration. The submodule is kept in `.gitmodules` and the index, so
+   * it can be initialized again with `init()`.
+   *
+   * @category Submodule/Methods
+   * @signature
+   * ```ts
+   * class Submodule {
+   *   deinit(options?: SubmoduleDeinitOptions | null | undefined): void;
+   * }
+   * ```
+   *
+   * @param {SubmoduleDeinitOptions} [options] - Options to deinitialize the submodule.
+   * @throws If the working tree of the submodule contains local modifications,
+   * unless `force` is set.
+   * @throws If the working tree of the submodule contains a `.git` directory.
+   */
+  deinit(options?: SubmoduleDeinitOptions | undefined | null): void
 }
 
 /**
@@ -6187,6 +7948,36 @@ export declare class Tag {
    * @returns Git object for this tag.
    */
   peel(): GitObject
+  /**
+   * Get the signature of a signed tag.
+   *
+   * @category Tag/Methods
+   * @signature
+   * ```ts
+   * class Tag {
+   *   signature(): string | null;
+   * }
+   * ```
+   *
+   * @returns Armored PGP, X.509 or SSH signature block of this tag.
+   * Returns `null` if the tag is not signed.
+   */
+  signature(): string | null
+  /**
+   * Get the payload of a signed tag, which is the raw tag object without
+   * its signature.
+   *
+   * @category Tag/Methods
+   * @signature
+   * ```ts
+   * class Tag {
+   *   signedPayload(): string | null;
+   * }
+   * ```
+   *
+   * @returns Data which is signed. Returns `null` if the tag is not signed.
+   */
+  signedPayload(): string | null
   /**
    * Get the tagger (author) of a tag.
    *
@@ -6630,6 +8421,39 @@ export declare class Worktree {
    * @throws Throws error if checking fails.
    */
   isPrunable(worktreePruneOptions?: WorktreePruneOptions | undefined | null): boolean
+  /**
+   * Move the worktree to a new path, like `git worktree move`.
+   *
+   * @category Worktree/Methods
+   *
+   * @signature
+   * ```ts
+   * class Worktree {
+   *   move(newPath: string): void;
+   * }
+   * ```
+   *
+   * @param {string} newPath - New path of the worktree, which must not exist.
+   * @throws Throws error if the worktree is locked or invalid, or if the new path already exists.
+   */
+  move(newPath: string): void
+  /**
+   * Remove the worktree and delete its working tree, like `git worktree remove`.
+   *
+   * @category Worktree/Methods
+   *
+   * @signature
+   * ```ts
+   * class Worktree {
+   *   remove(options?: WorktreeRemoveOptions | null | undefined): void;
+   * }
+   * ```
+   *
+   * @param {WorktreeRemoveOptions} [options] - Options for removing the worktree.
+   * @throws Throws error if the worktree is locked or contains modified or untracked files,
+   * unless `force` is set.
+   */
+  remove(options?: WorktreeRemoveOptions | undefined | null): void
 }
 
 export interface AddMailmapEntryData {
@@ -6661,6 +8485,23 @@ export interface AmendOptions {
    * If NULL, no encoding header is written and UTF-8 is assumed.
    */
   messageEncoding?: string
+  /**
+   * Signer to create a signed commit.
+   *
+   * Only available for commits looked up from a repository.
+   */
+  signer?: SignerOptions
+  /**
+   * Run the `pre-commit`, `prepare-commit-msg`, `commit-msg`, `post-commit`
+   * and `post-rewrite` hooks.
+   *
+   * Amending is aborted if `pre-commit`, `prepare-commit-msg` or `commit-msg`
+   * exits with non-zero status. Only available for commits looked up from a repository.
+   *
+   * If the tree to amend with is the tree of the index, the index is written before
+   * `pre-commit` runs and changes of the hook to the index are committed.
+   */
+  runHooks?: boolean
 }
 
 /**
@@ -6730,6 +8571,26 @@ export interface BlameHunk {
   isBoundary: boolean
 }
 
+/** A line of a blamed file. */
+export interface BlameLine {
+  /** The 1-based line number in the final file. */
+  lineNumber: number
+  /** Content of the line, without the line terminator. */
+  content: string
+  /**
+   * The 1-based line number in the original file where this line was
+   * written.
+   */
+  origLineNumber: number
+  /**
+   * The path to the file where this line was originally written, after
+   * following renames.
+   */
+  origPath?: string
+  /** The hunk which the line belongs to. */
+  hunk: BlameHunk
+}
+
 /** Options for controlling blame behavior */
 export interface BlameOptions {
   /** The minimum line number to blame (1-based index) */
@@ -6768,6 +8629,36 @@ export interface BlameOptions {
   trackCopiesSameCommitMoves?: boolean
   /** Use mailmap file to map author and committer names and email addresses to canonical real names and email addresses. */
   useMailmap?: boolean
+  /**
+   * Revisions to ignore, like `git blame --ignore-rev`. Lines changed by
+   * these revisions are attributed to the previous commits which changed them.
+   */
+  ignoreRevs?: Array<string>
+  /**
+   * Path to a file listing revisions to ignore, like
+   * `git blame --ignore-revs-file`. The path is relative to the working
+   * directory of the repository.
+   *
+   * Revisions in the file set by the `blame.ignoreRevsFile` configuration
+   * are always ignored. Unlike this option, a missing file set by the
+   * configuration is skipped.
+   */
+  ignoreRevsFile?: string
+  /**
+   * Callback which is called to report the progress of blame.
+   *
+   * Only used by `blameFileAsync`.
+   */
+  progress?: (linesBlamed: number, totalLines: number) => void
+}
+
+/** Options for formatting blame like `git blame --porcelain`. */
+export interface BlamePorcelainOptions {
+  /**
+   * Repeat the information of the commit for each line, like
+   * `git blame --line-porcelain`.
+   */
+  lineFormat?: boolean
 }
 
 export interface BranchesFilter {
@@ -6795,6 +8686,48 @@ export interface BranchRenameOptions {
 export type BranchType =  'Local'|
 'Remote';
 
+/** A notification emitted for a file during checkout. */
+export interface CheckoutNotify {
+  /** Type of the notification. */
+  type: CheckoutNotifyType
+  /** Path of the file relative to the working directory. */
+  path: string
+  /** The file in the baseline tree. (usually `HEAD`) */
+  baseline?: CheckoutNotifyFile
+  /** The file in the target tree. */
+  target?: CheckoutNotifyFile
+  /** The file in the working directory. */
+  workdir?: CheckoutNotifyFile
+}
+
+/** A side of a file reported by a checkout notification. */
+export interface CheckoutNotifyFile {
+  /** Object ID(SHA1) of the file. */
+  id: string
+  /** Path of the file relative to the working directory. */
+  path?: string
+  /** Size of the file, in bytes. */
+  size: bigint
+  /** Mode of the file. */
+  mode: FileMode
+}
+
+/**
+ * Types of notifications emitted during checkout.
+ *
+ * - `Conflict` : A file conflicts with the checkout and prevents it.
+ * - `Dirty` : A file is modified in the working directory but is not
+ *   changed by the checkout.
+ * - `Updated` : A file is changed by the checkout.
+ * - `Untracked` : An untracked file in the working directory.
+ * - `Ignored` : An ignored file in the working directory.
+ */
+export type CheckoutNotifyType =  'Conflict'|
+'Dirty'|
+'Updated'|
+'Untracked'|
+'Ignored';
+
 export interface CheckoutOptions {
   /**
    * Indicate that this checkout should perform a dry run by checking for
@@ -6936,6 +8869,38 @@ export interface CheckoutOptions {
   ourLabel?: string
   /** The name of the common their side of conflicts */
   theirLabel?: string
+  /**
+   * Types of notifications for which `notify` is called.
+   *
+   * If not provided, `notify` is called for every type of notification.
+   */
+  notifyOn?: Array<CheckoutNotifyType>
+  /**
+   * Callback which is called for each file with a notification during
+   * checkout.
+   *
+   * Returning `false` cancels the checkout. An error thrown by the callback
+   * also cancels the checkout, and is thrown by the checkout.
+   *
+   * Only used by `checkoutHead`, `checkoutIndex`, `checkoutTree` and
+   * `checkoutDryRun`.
+   */
+  notify?: (notify: CheckoutNotify) => boolean | void
+  /**
+   * Callback which is called to report the progress of checkout.
+   *
+   * Only used by `checkoutHead`, `checkoutIndex`, `checkoutTree` and
+   * `checkoutDryRun`.
+   */
+  progress?: (path: string | null, completed: number, total: number) => void
+  /**
+   * Run the `post-checkout` hook after checkout.
+   *
+   * Only used by `checkoutHead`, `checkoutIndex` and `checkoutTree`.
+   *
+   * `checkoutTree` doesn't move `HEAD`, so the hook is run as a file checkout.
+   */
+  runHooks?: boolean
 }
 
 /** Options for cherrypick behavior. */
@@ -6953,6 +8918,27 @@ export interface CherrypickOptions {
   checkoutOptions?: CheckoutOptions
 }
 
+/**
+ * - `Auto` : Auto-detect (default). Local clone optimizations are used for local paths, but not for `file://` URLs.
+ * - `Local` : Bypass the git-aware transport even for `file://` URLs, and hardlink the objects where possible.
+ * - `None` : Never use local clone optimizations.
+ * - `NoLinks` : Bypass the git-aware transport, but don't hardlink the objects.
+ */
+export type CloneLocal =  'Auto'|
+'Local'|
+'None'|
+'NoLinks';
+
+/** Options to customize the remote created by a clone. */
+export interface CloneRemoteOptions {
+  /** Fetch refspecs of the remote, which replace the default one. */
+  fetchRefspecs?: Array<string>
+  /** Push refspecs of the remote. */
+  pushRefspecs?: Array<string>
+  /** Push URL of the remote. */
+  pushurl?: string
+}
+
 /**
  * Clone a remote repository.
  *
@@ -7018,6 +9004,21 @@ export interface CherrypickOptions {
  */
 export declare function cloneRepository(url: string, path: string, options?: RepositoryCloneOptions | undefined | null, signal?: AbortSignal | undefined | null): Promise<Repository>
 
+export interface CommitHeaderField {
+  /**
+   * Name of the header field. (e.g. "change-id")
+   *
+   * It can't be empty or contain a space or a newline.
+   */
+  name: string
+  /**
+   * Value of the header field.
+   *
+   * Multi-line values will be written as continuation lines.
+   */
+  value: string
+}
+
 export interface CommitOptions {
   updateRef?: string
   /**
@@ -7047,6 +9048,33 @@ export interface CommitOptions {
    * If not provided, the default signature field (gpgsig) will be used.
    */
   signatureField?: string
+  /**
+   * Signer to create a signed commit.
+   *
+   * Ignored if `signature` is provided.
+   */
+  signer?: SignerOptions
+  /**
+   * Run the `pre-commit`, `prepare-commit-msg`, `commit-msg` and `post-commit` hooks.
+   *
+   * The commit is aborted if `pre-commit`, `prepare-commit-msg` or `commit-msg`
+   * exits with non-zero status. The message may be edited by the hooks, and
+   * an edited message is cleaned up by `commit.cleanup` config like `git commit -m`.
+   *
+   * If the tree is written from the index, the index is written before
+   * `pre-commit` runs and changes of the hook to the index are committed.
+   */
+  runHooks?: boolean
+}
+
+/** A color value of a config variable. (e.g. `color.diff.new`) */
+export interface ConfigColor {
+  /** Foreground color. (e.g. `red`, `brightblue`, `#ff0000`, `208`) */
+  foreground?: string
+  /** Background color. */
+  background?: string
+  /** Attributes of the color. (e.g. `bold`, `ul`, `nobold`) */
+  attributes: Array<string>
 }
 
 export interface ConfigEntry {
@@ -7060,6 +9088,20 @@ export interface ConfigEntry {
   includeDepth: number
 }
 
+/** A config file to add to a config stack. */
+export interface ConfigFile {
+  /** Path to config file. */
+  path: string
+  /** The priority level of the config file. */
+  level: ConfigLevel
+  /**
+   * Replace the config file which already exists at the same level.
+   *
+   * Defaults to `false`.
+   */
+  force?: boolean
+}
+
 /**
  * - `ProgramData` : System-wide on Windows, for compatibility with portable git.
  * - `System` : System-wide configuration file. (e.g. `/etc/gitconfig`)
@@ -7079,6 +9121,26 @@ export type ConfigLevel =  'ProgramData'|
 'App'|
 'Highest';
 
+/**
+ * Type to interpret values of config variables.
+ *
+ * - `String` : Value as is.
+ * - `Bool` : Boolean value. (e.g. `true`, `yes`, `on`, `1`)
+ * - `Int` : Integer value, with suffixes like k, M, or G.
+ * - `Path` : Path value, with a leading `~/` expanded to the home directory.
+ */
+export type ConfigValueType =  'String'|
+'Bool'|
+'Int'|
+'Path';
+
+/** Options to control how a connection to a remote is established. */
+export interface ConnectRemoteOptions {
+  credential?: Credential
+  /** Set the proxy options to use for the connection. */
+  proxy?: ProxyOptions
+}
+
 export interface CreateAnnotationTagOptions {
   /**
    * Signature for tagger.
@@ -7087,6 +9149,8 @@ export interface CreateAnnotationTagOptions {
    * If there is no default signature set for the repository, an error will occur.
    */
   tagger?: SignaturePayload
+  /** Signer to create a signed tag. */
+  signer?: SignerOptions
 }
 
 export interface CreateBranchOptions {
@@ -7097,6 +9161,17 @@ export interface CreateBranchOptions {
   force?: boolean
 }
 
+export interface CreateCommitBufferOptions {
+  /**
+   * The encoding for the message in the commit, represented with a standard encoding name.
+   * E.g. "UTF-8".
+   * If not provided, no encoding header is written and UTF-8 is assumed.
+   */
+  messageEncoding?: string
+  /** Extra header fields to write after the standard headers of the commit. */
+  headers?: Array<CommitHeaderField>
+}
+
 export interface CreateLightweightTagOptions {
   /** If `force` is true and a reference already exists with the given name, it'll be replaced. */
   force?: boolean
@@ -7194,6 +9269,8 @@ export interface CreateTagOptions {
   tagger?: SignaturePayload
   /** If `force` is true and a reference already exists with the given name, it'll be replaced. */
   force?: boolean
+  /** Signer to create a signed tag. */
+  signer?: SignerOptions
 }
 
 /** A interface to represent git credentials in libgit2. */
@@ -7274,6 +9351,42 @@ export type DeltaType =  'Unmodified'|
 'Unreadable'|
 'Conflicted';
 
+/** Structured result of a `describe` operation. */
+export interface DescribeDetails {
+  /**
+   * Name of the tag or the reference which describes the commit.
+   *
+   * `null` if no tag or reference is found, and the abbreviated commit id is
+   * shown as a fallback.
+   */
+  tag?: string
+  /**
+   * Number of commits between the commit and the tag.
+   *
+   * With the `contains` option, this is the number of commits from the tag
+   * down to the commit.
+   */
+  distance?: number
+  /** Abbreviated id of the described commit. */
+  abbreviatedOid: string
+  /**
+   * Whether the working directory is dirty. This is always `false` for
+   * descriptions of objects or with the `contains` option.
+   */
+  dirty: boolean
+}
+
+/** Options for the structured result of a `describe` operation. */
+export interface DescribeDetailsOptions {
+  /**
+   * Sets the size of the abbreviated commit id to use.
+   *
+   * The value is the lower bound for the length of the abbreviated string,
+   * and the default is 7.
+   */
+  abbreviatedSize?: number
+}
+
 export interface DescribeFormatOptions {
   /**
    * Sets the size of the abbreviated commit id to use.
@@ -7320,6 +9433,15 @@ export interface DescribeOptions {
    */
   showCommitOidAsFallback?: boolean
   pattern?: string
+  /**
+   * Describe the commit by the tag which contains it, like the
+   * `--contains` option to git-describe.
+   *
+   * The commit is named relative to the tag like `v1.0~2` or `v1.0~1^2`, and
+   * lightweight tags are also considered. With `describeAll`, any reference
+   * is considered. The working directory is not considered to be dirty.
+   */
+  contains?: boolean
 }
 
 export interface DiffFindOptions {
@@ -7645,6 +9767,38 @@ export interface FetchOptions {
    * everything (effectively the same as not declaring a limit depth).
    */
   depth?: number
+  /**
+   * Convert a shallow repository to a complete one by fetching all of the
+   * missing history.
+   *
+   * This takes precedence over `depth`.
+   */
+  unshallow?: boolean
+  /**
+   * Deepen the history of a shallow repository by the given number of
+   * commits from its current shallow boundary (`git fetch --deepen`).
+   *
+   * This takes precedence over `depth`.
+   */
+  deepen?: number
+  /**
+   * Deepen the history of a shallow repository until it reaches commits
+   * older than the given date (`git fetch --shallow-since`).
+   *
+   * libgit2 can only request a number of commits from the remote, so the
+   * history is deepened step by step and a few commits older than the date
+   * may be fetched as well.
+   */
+  shallowSince?: Date
+  /**
+   * Deepen the history of a shallow repository until it reaches commits
+   * reachable from the given revisions (`git fetch --shallow-exclude`).
+   *
+   * The revisions are resolved in the local repository, so they are usually
+   * remote-tracking branches or tags. As with `shallowSince`, a few commits
+   * reachable from the revisions may be fetched as well.
+   */
+  shallowExclude?: Array<string>
   /**
    * Set how to behave regarding tags on the remote, such as auto-downloading
    * tags for objects we're downloading or downloading all of them.
@@ -7662,6 +9816,15 @@ export interface FetchOptions {
   followRedirects?: RemoteRedirect
   /** Set extra headers for this fetch operation. */
   customHeaders?: Array<string>
+  /**
+   * Object filter of a partial clone, like `git clone --filter` (e.g.
+   * `blob:none`, `blob:limit=1m` or `tree:0`).
+   *
+   * Partial clones are not supported, because libgit2 can't negotiate
+   * filters with the remote nor fetch missing objects on demand. An error is
+   * thrown if this is set.
+   */
+  filter?: string
 }
 
 /**
@@ -7805,6 +9968,35 @@ export declare function hashFileOid(objType: ObjectType, path: string): string
  */
 export declare function hashObjectOid(objType: ObjectType, bytes: Buffer): string
 
+/** Result of running a hook. */
+export interface HookResult {
+  /** Name of the hook. */
+  name: string
+  /** Path of the executed hook. */
+  path: string
+  /** Exit code of the hook. `null` if the hook is terminated by a signal. */
+  exitCode?: number
+  /** Standard output of the hook. */
+  stdout: string
+  /** Standard error of the hook. */
+  stderr: string
+}
+
+export interface HunkSelection {
+  /** Index of the hunk in the diff of the file. */
+  hunk: number
+  /**
+   * Indices of lines in the hunk to select.
+   *
+   * Indices count every line of the hunk (context lines included), in the
+   * same order as they are printed by `Diff.print()`. Only added and
+   * deleted lines are affected by the selection.
+   *
+   * If not provided, every line in the hunk is selected.
+   */
+  lines?: Array<number>
+}
+
 export interface IndexAddAllOptions {
   /**
    * Files that are ignored will be skipped (unlike `addPath`). If a file is
@@ -8125,6 +10317,12 @@ export interface MergeOptions {
   patience?: boolean
   /** Take extra time to find minimal diff */
   minimal?: boolean
+  /**
+   * Run the `post-merge` hook after a merge without conflicts.
+   *
+   * Only used by `merge`.
+   */
+  runHooks?: boolean
 }
 
 export interface MergePreference {
@@ -8145,6 +10343,22 @@ export interface MergePreference {
   fastForwardOnly: boolean
 }
 
+/** Options for naming a commit with `nameRev`. */
+export interface NameRevOptions {
+  /**
+   * Only use references matching any of the glob patterns, like the
+   * `--refs` option to git-name-rev. A pattern matches the full name of the
+   * reference or its trailing path components (e.g. `v1.*` matches
+   * `refs/tags/v1.0`).
+   */
+  refs?: Array<string>
+  /**
+   * Only use tags to name the commit, like the `--tags` option to
+   * git-name-rev.
+   */
+  tagsOnly?: boolean
+}
+
 /**
  * Normalize reference name and check validity.
  *
@@ -8231,18 +10445,45 @@ export type ObjectType =  'Any'|
 'Tag';
 
 /**
- * Create a new config instance containing a single on-disk file
+ * Create a new config instance containing a single on-disk file
+ *
+ * @category Config
+ * @signature
+ * ```ts
+ * function openConfig(path: string): Config;
+ * ```
+ *
+ * @param {string} path - Path to config file.
+ * @returns Config instance representing a git configuration key/value store.
+ */
+export declare function openConfig(path: string): Config
+
+/**
+ * Create a new config instance composed of several on-disk files.
+ *
+ * Files with a higher priority level will be accessed first.
  *
  * @category Config
  * @signature
  * ```ts
- * function openConfig(path: string): Config;
+ * function openConfigStack(files: ConfigFile[]): Config;
  * ```
  *
- * @param {string} path - Path to config file.
+ * @param {ConfigFile[]} files - Config files to compose.
  * @returns Config instance representing a git configuration key/value store.
+ *
+ * @example
+ * ```ts
+ * import { openConfigStack } from 'es-git';
+ *
+ * const config = openConfigStack([
+ *   { path: '/home/user/.gitconfig', level: 'Global' },
+ *   { path: '/path/to/repo/.git/config', level: 'Local' },
+ * ]);
+ * console.log(config.getString('user.email'));
+ * ```
  */
-export declare function openConfig(path: string): Config
+export declare function openConfigStack(files: Array<ConfigFile>): Config
 
 /**
  * Open the global, XDG and system configuration files
@@ -8426,6 +10667,18 @@ export interface PruneOptions {
   credential?: Credential
 }
 
+/** Expected state of a remote reference for a push with lease. */
+export interface PushLease {
+  /** Name of the remote reference (e.g. `refs/heads/main`). */
+  refname: string
+  /**
+   * Object id which the remote reference is expected to point to.
+   *
+   * Use the zero object id to expect that the reference does not exist.
+   */
+  expected: string
+}
+
 /** Options to control the behavior of a git push. */
 export interface PushOptions {
   credential?: Credential
@@ -8452,8 +10705,56 @@ export interface PushOptions {
   customHeaders?: Array<string>
   /** Set "push options" to deliver to the remote. */
   remoteOptions?: Array<string>
+  /**
+   * Expected states of the remote references, like `git push --force-with-lease`.
+   *
+   * The push is rejected if any of the remote references does not point to
+   * the expected object, or if a lease does not match any pushed reference.
+   */
+  leases?: Array<PushLease>
+  /**
+   * Throw an error if the remote rejects any of the reference updates.
+   *
+   * If `false`, rejected references are returned with the `Rejected` status
+   * instead.
+   *
+   * Default: `true`.
+   */
+  throwOnRejected?: boolean
+  /**
+   * Run the `pre-push` hook before pushing.
+   *
+   * The push is aborted if the hook exits with non-zero status.
+   */
+  runHooks?: boolean
+  /**
+   * Update all references atomically, like `git push --atomic`.
+   *
+   * Atomic pushes are not supported, because libgit2 can't request the
+   * `atomic` capability of the remote. An error is thrown if this is `true`.
+   */
+  atomic?: boolean
+}
+
+/** Result of pushing a reference. */
+export interface PushRefResult {
+  /** Name of the remote reference. */
+  refname: string
+  /** Status of the reference update. */
+  status: PushStatus
+  /** Message from the remote, if the update was rejected. */
+  message?: string
 }
 
+/**
+ * - `Ok` : The remote reference was updated.
+ * - `Rejected` : The remote reference update was rejected by the remote.
+ * - `UpToDate` : The remote reference already pointed to the pushed object.
+ */
+export type PushStatus =  'Ok'|
+'Rejected'|
+'UpToDate';
+
 export interface RebaseCommitOptions {
   /**
    * Signature for author.
@@ -8464,6 +10765,12 @@ export interface RebaseCommitOptions {
   committer: SignaturePayload
   /** To keep the message from the original commit leave this as empty. */
   message?: string
+  /**
+   * Signer to create a signed commit.
+   *
+   * Not available for in-memory rebases.
+   */
+  signer?: SignerOptions
 }
 
 /**
@@ -8548,6 +10855,32 @@ export interface RebaseOptions {
    * semantics.
    */
   checkoutOptions?: CheckoutOptions
+  /**
+   * Run the `pre-rebase` hook before the rebase begins, and the
+   * `post-rewrite` hook with rebased commits in `finish()`.
+   *
+   * The rebase is aborted if `pre-rebase` exits with non-zero status.
+   * `post-rewrite` is not run for in-memory rebases.
+   */
+  runHooks?: boolean
+}
+
+/** Update of a reference requested to receive-pack. */
+export interface ReceivePackUpdate {
+  /** Name of the reference (e.g. `refs/heads/main`). */
+  refname: string
+  /**
+   * Object id which the reference currently points to.
+   *
+   * The zero object id if the reference is created.
+   */
+  oldOid: string
+  /**
+   * Object id which the reference is updated to.
+   *
+   * The zero object id if the reference is deleted.
+   */
+  newOid: string
 }
 
 /**
@@ -8594,6 +10927,63 @@ export interface Refspec {
   force: boolean
 }
 
+/**
+ * Register a custom transport for the URL scheme.
+ *
+ * The transport works like the smart HTTP protocol: the handler is called
+ * once for each request with the URL of the remote, the requested service
+ * and the request body, and returns the whole response body. Responses of
+ * `UploadPackLs` and `ReceivePackLs` must start with the
+ * `# service=git-upload-pack` (or `git-receive-pack`) packet line followed by
+ * a flush packet, as the `info/refs` response of smart HTTP.
+ *
+ * The handler is called synchronously while the remote operation waits for
+ * the response, so the transport is only available for asynchronous
+ * operations like `fetch`, `push`, `connect` and `cloneRepository`.
+ * Synchronous operations on the thread which registered the transport fail
+ * instead of waiting for the handler.
+ *
+ * Registering a handler for a scheme which is already registered replaces
+ * the handler. `file://` and local paths are supported without registering
+ * a transport.
+ *
+ * @category Transport
+ * @signature
+ * ```ts
+ * function registerTransport(
+ *   scheme: string,
+ *   handler: (url: string, service: TransportService, body: Buffer) => Buffer,
+ * ): void;
+ * ```
+ *
+ * @param {string} scheme - URL scheme without `://` (e.g. `myproto`).
+ * @param {(url: string, service: TransportService, body: Buffer) => Buffer} handler - Handler which
+ * responds to requests of the smart protocol.
+ *
+ * @example
+ * ```ts
+ * import { cloneRepository, registerTransport } from 'es-git';
+ *
+ * registerTransport('myproto', (url, service, body) => {
+ *   return myServer.handle(url, service, body);
+ * });
+ * const repo = await cloneRepository('myproto://example/repo', '/path/to/clone');
+ * ```
+ */
+export declare function registerTransport(scheme: string, handler: (url: string, service: TransportService, body: Buffer) => Buffer): void
+
+/** A reference advertised by a remote. */
+export interface RemoteHead {
+  /** The name of the reference. */
+  name: string
+  /** The object id the reference points to. */
+  oid: string
+  /** The target of the reference, if it is a symbolic reference (e.g. `HEAD`). */
+  symrefTarget?: string
+  /** The object id an annotated tag peels to, if the remote advertised it. */
+  peeled?: string
+}
+
 /**
  * - `None` : Do not follow any off-site redirects at any stage of the fetch or push.
  * - `Initial` : Allow off-site redirects only upon the initial request. This is the default.
@@ -8633,6 +11023,35 @@ export interface RepositoryCloneOptions {
   recursive?: boolean
   /** Options which can be specified to various fetch operations. */
   fetch?: FetchOptions
+  /**
+   * Create a mirror of the remote repository.
+   *
+   * This implies `bare`. All references of the remote are fetched into the
+   * same names, and `remote.<name>.mirror` is set in the configuration.
+   *
+   * This is similar to `git clone --mirror`.
+   */
+  mirror?: boolean
+  /**
+   * Set whether the local clone optimizations are used when cloning from a
+   * repository on the local filesystem.
+   */
+  local?: CloneLocal
+  /**
+   * Do not check out the working tree after the clone.
+   *
+   * This is similar to `git clone --no-checkout`.
+   */
+  noCheckout?: boolean
+  /** Name of the remote to create. Defaults to `origin`. */
+  remoteName?: string
+  /**
+   * Callback to customize the remote before fetching.
+   *
+   * Called with the name and URL of the remote. If options are returned,
+   * they are applied to the created remote.
+   */
+  remoteCreate?: (name: string, url: string) => CloneRemoteOptions | null | undefined | void
 }
 
 /** Mode options for `RepositoryInitOptions`. */
@@ -8876,6 +11295,43 @@ export declare enum RevwalkSort {
   Reverse = 4
 }
 
+/** Options for running a hook. */
+export interface RunHookOptions {
+  /** Data written to the standard input of the hook. */
+  stdin?: string
+  /** Additional environment variables for the hook. */
+  env?: Record<string, string>
+}
+
+export interface ServeReceivePackOptions {
+  /**
+   * Callback which is called for each reference update before it is
+   * applied, like the `update` hook of git.
+   *
+   * Returning a message rejects the update with the message.
+   */
+  onUpdate?: (update: ReceivePackUpdate) => string | null | undefined | void
+}
+
+export interface ServeUploadPackOptions {
+  /**
+   * Version of the protocol, `0` or `2`.
+   *
+   * Clients request version 2 with `version=2` in the `Git-Protocol` header
+   * of smart HTTP.
+   *
+   * Default: `0`.
+   */
+  protocolVersion?: number
+  /**
+   * Callback which receives the response in chunks while it is generated,
+   * so the packfile doesn't have to be held in memory.
+   *
+   * If provided, the returned buffer is empty.
+   */
+  onData?: (chunk: Buffer) => void
+}
+
 /**
  * A Signature is used to indicate authorship of various actions throughout the
  * library.
@@ -8905,6 +11361,95 @@ export interface SignatureTimeOptions {
   offset?: number
 }
 
+export interface SignatureVerification {
+  /** Format of the signature. */
+  format: SigningFormat
+  /**
+   * Whether the signature is cryptographically good.
+   *
+   * For `Ssh`, the key must also be found in the allowed signers file, if
+   * one is configured.
+   */
+  valid: boolean
+  /**
+   * Identity of the signer.
+   *
+   * User id for `OpenPgp` and `X509`, principal from the allowed signers
+   * file for `Ssh`.
+   */
+  signer?: string
+  /** Id of the key which made the signature. */
+  key?: string
+  /** Fingerprint of the key which made the signature. */
+  fingerprint?: string
+  /**
+   * Trust level of the key.
+   *
+   * One of `undefined`, `never`, `marginal`, `fully` or `ultimate`. SSH keys
+   * found in the allowed signers file are `fully` trusted.
+   */
+  trust?: string
+  /** Raw output of the verification program. */
+  output: string
+}
+
+export interface SignerOptions {
+  /**
+   * Callback which signs the given payload and returns the armored
+   * signature.
+   *
+   * If provided, all other options are ignored.
+   */
+  callback?: (payload: string) => string
+  /**
+   * Format of the signature.
+   *
+   * If not provided, `gpg.format` config will be used. Defaults to `OpenPgp`.
+   */
+  format?: SigningFormat
+  /**
+   * Key to sign with.
+   *
+   * For `OpenPgp` and `X509`, this is the key id passed to the signing
+   * program. For `Ssh`, this is the path to the private key, or a public
+   * key (file or literal `ssh-*` / `key::` string) whose private key is
+   * held by `ssh-agent`.
+   *
+   * If not provided, `user.signingKey` config will be used.
+   */
+  key?: string
+  /**
+   * Program used to create the signature.
+   *
+   * If not provided, `gpg.<format>.program` config (or `gpg.program` for
+   * `OpenPgp`) will be used. Defaults to `gpg`, `gpgsm` or `ssh-keygen`
+   * depending on the format.
+   */
+  program?: string
+}
+
+/**
+ * Format of signatures. Same as the `gpg.format` git config.
+ *
+ * - `OpenPgp` : Sign with OpenPGP keys using `gpg`. (default)
+ * - `X509` : Sign with X.509 certificates using `gpgsm`.
+ * - `Ssh` : Sign with SSH keys using `ssh-keygen`.
+ */
+export type SigningFormat =  'OpenPgp'|
+'X509'|
+'Ssh';
+
+/** Options for setting sparse checkout patterns. */
+export interface SparseCheckoutSetOptions {
+  /**
+   * Use cone mode, where patterns are directories which are checked out
+   * recursively, along with the files directly in their parent directories.
+   *
+   * Defaults to the `core.sparseCheckoutCone` config, or `true` if it is not set.
+   */
+  cone?: boolean
+}
+
 /**
  * Options for applying a stash.
  *
@@ -8920,7 +11465,10 @@ export interface SignatureTimeOptions {
  * repo.stashApply(0);
  *
  * // With options
- * repo.stashApply(0, { reinstantiateIndex: true });
+ * repo.stashApply(0, {
+ *   reinstantiateIndex: true,
+ *   progress: stage => console.log(stage),
+ * });
  * ```
  */
 export interface StashApplyOptions {
@@ -8930,8 +11478,39 @@ export interface StashApplyOptions {
    * Default: false
    */
   reinstantiateIndex?: boolean
+  /**
+   * Options for the checkout of the stashed files.
+   * If the checkout is prevented by conflicts, the paths of the conflicting
+   * files are listed in the error message.
+   */
+  checkout?: CheckoutOptions
+  /**
+   * Callback which is called to report the progress of applying the stash.
+   * Returning `false` cancels applying the stash. An error thrown by the
+   * callback also cancels applying the stash, and is thrown.
+   */
+  progress?: (progress: StashApplyProgress) => boolean | void
 }
 
+/**
+ * Stages of applying a stash.
+ *
+ * - `LoadingStash` : Loading the stashed data from the object database.
+ * - `AnalyzeIndex` : The stored index is being analyzed.
+ * - `AnalyzeModified` : The modified files are being analyzed.
+ * - `AnalyzeUntracked` : The untracked and ignored files are being analyzed.
+ * - `CheckoutUntracked` : The untracked files are being written to disk.
+ * - `CheckoutModified` : The modified files are being written to disk.
+ * - `Done` : The stash was applied successfully.
+ */
+export type StashApplyProgress =  'LoadingStash'|
+'AnalyzeIndex'|
+'AnalyzeModified'|
+'AnalyzeUntracked'|
+'CheckoutUntracked'|
+'CheckoutModified'|
+'Done';
+
 /**
  * Options for saving a stash.
  *
@@ -8983,6 +11562,28 @@ export interface StashSaveOptions {
    * Default: false
    */
   keepIndex?: boolean
+  /**
+   * Pathspecs of the files to stash, like `git stash push -- <paths>`.
+   * If not provided, all changes are stashed.
+   */
+  paths?: Array<string>
+  /**
+   * Whether to stash only the changes staged in the index, like
+   * `git stash push --staged`. Unstaged changes remain in the working directory.
+   * Can't be used with `includeUntracked` or `includeIgnored`.
+   * Default: false
+   */
+  stagedOnly?: boolean
+}
+
+/** Options for showing a stash. */
+export interface StashShowOptions {
+  /**
+   * Whether to include the untracked files of the stash in the diff, like
+   * `git stash show --include-untracked`.
+   * Default: false
+   */
+  includeUntracked?: boolean
 }
 
 export interface Status {
@@ -9051,6 +11652,58 @@ export type StatusShow =  'Index'|
 'Workdir'|
 'IndexAndWorkdir';
 
+/** Options to add a submodule. */
+export interface SubmoduleAddOptions {
+  /**
+   * Branch of the submodule repository to check out, which is recorded in
+   * `.gitmodules`.
+   *
+   * If not provided, the default branch of the remote is checked out.
+   */
+  branch?: string
+  /**
+   * Create a shallow clone with a history truncated to the specified number
+   * of commits.
+   */
+  depth?: number
+  /** Options which control the fetch, including callbacks. */
+  fetch?: FetchOptions
+  /**
+   * Should the workdir contain a gitlink to the repo in `.git/modules` vs.
+   * repo directly in workdir.
+   *
+   * Default: `true`.
+   */
+  useGitlink?: boolean
+}
+
+/** Options to deinitialize a submodule. */
+export interface SubmoduleDeinitOptions {
+  /**
+   * Remove the working tree of the submodule even if it contains local
+   * modifications.
+   */
+  force?: boolean
+}
+
+/** A submodule visited by `submoduleForeach`. */
+export interface SubmoduleForeachEntry {
+  /** Name of the submodule. */
+  name: string
+  /** Path of the submodule, relative to the top-level repository. */
+  path: string
+  /** Commit id of the submodule recorded in the immediate superproject. */
+  oid?: string
+  /** Absolute path of the top-level repository. */
+  toplevel: string
+}
+
+/** Options to iterate submodules. */
+export interface SubmoduleForeachOptions {
+  /** Iterate submodules of the submodules recursively. */
+  recursive?: boolean
+}
+
 /**
  * Submodule ignore values
  *
@@ -9143,6 +11796,65 @@ export declare enum SubmoduleStatus {
  */
 export declare function submoduleStatusContains(source: number, target: number): boolean
 
+/**
+ * Summary of a submodule, like `git submodule status` and
+ * `git submodule summary`.
+ */
+export interface SubmoduleSummary {
+  /** Name of the submodule. */
+  name: string
+  /** Path of the submodule, relative to the top-level repository. */
+  path: string
+  /**
+   * Commit id of the submodule recorded in the index of the superproject,
+   * or `HEAD` if the submodule is not in the index.
+   */
+  recordedOid?: string
+  /** Commit id of `HEAD` of the submodule which is checked out. */
+  checkedOutOid?: string
+  /**
+   * Number of commits the checked out commit is ahead of the recorded
+   * commit.
+   *
+   * `null` if either commit is missing in the submodule repository.
+   */
+  ahead?: number
+  /**
+   * Number of commits the checked out commit is behind the recorded commit.
+   *
+   * `null` if either commit is missing in the submodule repository.
+   */
+  behind?: number
+  /**
+   * Whether the submodule is initialized, which means the URL of the
+   * submodule is in the repository configuration.
+   */
+  initialized: boolean
+  /** Whether the submodule is checked out in the working directory. */
+  checkedOut: boolean
+  /**
+   * Whether the working directory or the index of the submodule has
+   * modified files.
+   */
+  modified: boolean
+  /** Whether the working directory of the submodule has untracked files. */
+  untracked: boolean
+}
+
+/** Options to summarize submodules. */
+export interface SubmoduleSummaryOptions {
+  /** Summarize submodules of the submodules recursively. */
+  recursive?: boolean
+  /**
+   * The ignore rules to follow when checking the working tree of
+   * submodules.
+   *
+   * Default: `Unspecified`, which uses the `submodule.<name>.ignore`
+   * configuration.
+   */
+  ignore?: SubmoduleIgnore
+}
+
 /**
  * Submodule update values
  *
@@ -9177,6 +11889,31 @@ export type SubmoduleUpdate = /**
  */
 'Default';
 
+/** Options to update all submodules. */
+export interface SubmoduleUpdateAllOptions {
+  /** Initialize submodules which are not initialized yet. */
+  init?: boolean
+  /** Update submodules of the submodules recursively. */
+  recursive?: boolean
+  /**
+   * Update submodules to the latest commit of the remote branch instead of
+   * the commit recorded in the superproject, like `git submodule update --remote`.
+   *
+   * The branch is the `branch` of the submodule, or the default branch of
+   * the remote if not set. `HEAD` of the submodule is detached at the commit.
+   */
+  remote?: boolean
+  /** These options are passed to the checkout step. */
+  checkout?: CheckoutOptions
+  /** Options which control the fetch, including callbacks. */
+  fetch?: FetchOptions
+  /**
+   * Allow fetching from the submodule's default remote if the target commit isn't found.
+   * Default: `true`.
+   */
+  allowFetch?: boolean
+}
+
 /** Options to update a submodule. */
 export interface SubmoduleUpdateOptions {
   /** These options are passed to the checkout step. */
@@ -9230,6 +11967,37 @@ export type TraceLevel =  'None'|
  */
 export declare function traceSet(level: TraceLevel, callback: (level: TraceLevel, message: string) => void): void
 
+/** Statistics of the data transferred from a remote. */
+export interface TransferStats {
+  /** Number of objects in the packfile being downloaded. */
+  totalObjects: number
+  /** Received objects that have been hashed. */
+  indexedObjects: number
+  /** Objects which have been downloaded. */
+  receivedObjects: number
+  /** Locally-available objects that have been injected in order to fix a thin pack. */
+  localObjects: number
+  /** Number of deltas in the packfile being downloaded. */
+  totalDeltas: number
+  /** Received deltas that have been hashed. */
+  indexedDeltas: number
+  /** Size of the packfile received up to now. */
+  receivedBytes: number
+}
+
+/**
+ * Services of the smart protocol requested to a transport.
+ *
+ * - `UploadPackLs` : List references for fetching.
+ * - `UploadPack` : Negotiate and receive a packfile for fetching.
+ * - `ReceivePackLs` : List references for pushing.
+ * - `ReceivePack` : Send references updates and a packfile for pushing.
+ */
+export type TransportService =  'UploadPackLs'|
+'UploadPack'|
+'ReceivePackLs'|
+'ReceivePack';
+
 /**
  * - `PreOrder` : Runs the traversal in pre-order.
  * - `PostOrder` : Runs the traversal in post-order.
@@ -9237,6 +12005,60 @@ export declare function traceSet(level: TraceLevel, callback: (level: TraceLevel
 export type TreeWalkMode =  'PreOrder'|
 'PostOrder';
 
+/**
+ * Unregister the custom transport for the URL scheme.
+ *
+ * Remote operations for the scheme fail after unregistering, until a
+ * transport is registered again.
+ *
+ * @category Transport
+ * @signature
+ * ```ts
+ * function unregisterTransport(scheme: string): void;
+ * ```
+ *
+ * @param {string} scheme - URL scheme without `://` (e.g. `myproto`).
+ */
+export declare function unregisterTransport(scheme: string): void
+
+/** A reference updated by a fetch. */
+export interface UpdatedTip {
+  /** Name of the updated reference. */
+  refname: string
+  /** Object id the reference pointed to before the fetch. `null` if the reference is created. */
+  oldOid?: string
+  /** Object id the reference points to after the fetch. `null` if the reference is deleted. */
+  newOid?: string
+  /** How the reference is updated. */
+  status: UpdatedTipStatus
+}
+
+/**
+ * - `New` : The reference was created.
+ * - `FastForward` : The reference was updated to a descendant of the old object.
+ * - `Forced` : The reference was updated to an object which is not a descendant of the old object.
+ * - `Deleted` : The reference was deleted.
+ */
+export type UpdatedTipStatus =  'New'|
+'FastForward'|
+'Forced'|
+'Deleted';
+
+export interface VerifySignatureOptions {
+  /**
+   * Path to the allowed signers file used to verify SSH signatures.
+   *
+   * If not provided, `gpg.ssh.allowedSignersFile` config will be used.
+   */
+  allowedSigners?: string
+  /**
+   * Program used to verify the signature.
+   *
+   * If not provided, same program as for signing will be used.
+   */
+  program?: string
+}
+
 /** Options for adding a worktree. */
 export interface WorktreeAddOptions {
   /**
@@ -9259,6 +12081,30 @@ export interface WorktreeAddOptions {
   refName?: string
 }
 
+/** Details of a worktree, like `git worktree list --porcelain`. */
+export interface WorktreeInfo {
+  /** Name of the worktree. `null` for the main worktree. */
+  name?: string
+  /** Path of the worktree. */
+  path: string
+  /** Commit id of `HEAD` of the worktree. */
+  head?: string
+  /** Full name of the branch checked out in the worktree. */
+  branch?: string
+  /** Whether the worktree is the main worktree of a bare repository. */
+  bare: boolean
+  /** Whether `HEAD` of the worktree is detached. */
+  detached: boolean
+  /** Whether the worktree is locked. */
+  locked: boolean
+  /** Reason why the worktree is locked, if given. */
+  lockReason?: string
+  /** Whether the worktree can be pruned, because the working tree is missing. */
+  prunable: boolean
+  /** Reason why the worktree can be pruned. */
+  prunableReason?: string
+}
+
 /** Lock Status of a worktree */
 export interface WorktreeLockStatus {
   /** Worktree is Unlocked */
@@ -9295,6 +12141,17 @@ export interface WorktreePruneOptions {
   workingTree?: boolean
 }
 
+/** Options to remove a worktree. */
+export interface WorktreeRemoveOptions {
+  /**
+   * Remove the worktree even if it is locked or contains modified or
+   * untracked files.
+   *
+   * Defaults to `false`.
+   */
+  force?: boolean
+}
+
 /**
  * Creates an all zero Oid structure.
  *
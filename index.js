@@ -576,6 +576,7 @@ module.exports.AnnotatedCommit = nativeBinding.AnnotatedCommit
 module.exports.Blame = nativeBinding.Blame
 module.exports.BlameHunks = nativeBinding.BlameHunks
 module.exports.BlameHunksByLine = nativeBinding.BlameHunksByLine
+module.exports.BlameLines = nativeBinding.BlameLines
 module.exports.Blob = nativeBinding.Blob
 module.exports.Branch = nativeBinding.Branch
 module.exports.Branches = nativeBinding.Branches
@@ -602,6 +603,7 @@ module.exports.ReflogIter = nativeBinding.ReflogIter
 module.exports.Remote = nativeBinding.Remote
 module.exports.Repository = nativeBinding.Repository
 module.exports.Revwalk = nativeBinding.Revwalk
+module.exports.SparseCheckout = nativeBinding.SparseCheckout
 module.exports.StashEntry = nativeBinding.StashEntry
 module.exports.StashList = nativeBinding.StashList
 module.exports.StashListIter = nativeBinding.StashListIter
@@ -617,8 +619,11 @@ module.exports.Worktree = nativeBinding.Worktree
 module.exports.ApplyLocation = nativeBinding.ApplyLocation
 module.exports.AutotagOption = nativeBinding.AutotagOption
 module.exports.BranchType = nativeBinding.BranchType
+module.exports.CheckoutNotifyType = nativeBinding.CheckoutNotifyType
+module.exports.CloneLocal = nativeBinding.CloneLocal
 module.exports.cloneRepository = nativeBinding.cloneRepository
 module.exports.ConfigLevel = nativeBinding.ConfigLevel
+module.exports.ConfigValueType = nativeBinding.ConfigValueType
 module.exports.createMailmapFromBuffer = nativeBinding.createMailmapFromBuffer
 module.exports.createSignature = nativeBinding.createSignature
 module.exports.CredentialType = nativeBinding.CredentialType
@@ -646,6 +651,7 @@ module.exports.isZeroOid = nativeBinding.isZeroOid
 module.exports.normalizeReferenceName = nativeBinding.normalizeReferenceName
 module.exports.ObjectType = nativeBinding.ObjectType
 module.exports.openConfig = nativeBinding.openConfig
+module.exports.openConfigStack = nativeBinding.openConfigStack
 module.exports.openDefaultConfig = nativeBinding.openDefaultConfig
 module.exports.openRepository = nativeBinding.openRepository
 module.exports.openRepositoryFromWorktree = nativeBinding.openRepositoryFromWorktree
@@ -653,15 +659,19 @@ module.exports.openWorktreeFromRepository = nativeBinding.openWorktreeFromReposi
 module.exports.parseConfigBool = nativeBinding.parseConfigBool
 module.exports.parseConfigI32 = nativeBinding.parseConfigI32
 module.exports.parseConfigI64 = nativeBinding.parseConfigI64
+module.exports.PushStatus = nativeBinding.PushStatus
 module.exports.RebaseOperationType = nativeBinding.RebaseOperationType
 module.exports.ReferenceFormat = nativeBinding.ReferenceFormat
 module.exports.ReferenceType = nativeBinding.ReferenceType
+module.exports.registerTransport = nativeBinding.registerTransport
 module.exports.RemoteRedirect = nativeBinding.RemoteRedirect
 module.exports.RepositoryInitMode = nativeBinding.RepositoryInitMode
 module.exports.RepositoryState = nativeBinding.RepositoryState
 module.exports.RevparseMode = nativeBinding.RevparseMode
 module.exports.revparseModeContains = nativeBinding.revparseModeContains
 module.exports.RevwalkSort = nativeBinding.RevwalkSort
+module.exports.SigningFormat = nativeBinding.SigningFormat
+module.exports.StashApplyProgress = nativeBinding.StashApplyProgress
 module.exports.StatusShow = nativeBinding.StatusShow
 module.exports.SubmoduleIgnore = nativeBinding.SubmoduleIgnore
 module.exports.SubmoduleStatus = nativeBinding.SubmoduleStatus
@@ -670,6 +680,9 @@ module.exports.SubmoduleUpdate = nativeBinding.SubmoduleUpdate
 module.exports.traceClear = nativeBinding.traceClear
 module.exports.TraceLevel = nativeBinding.TraceLevel
 module.exports.traceSet = nativeBinding.traceSet
+module.exports.TransportService = nativeBinding.TransportService
 module.exports.TreeWalkMode = nativeBinding.TreeWalkMode
+module.exports.unregisterTransport = nativeBinding.unregisterTransport
+module.exports.UpdatedTipStatus = nativeBinding.UpdatedTipStatus
 module.exports.WorktreeLockStatusType = nativeBinding.WorktreeLockStatusType
 module.exports.zeroOid = nativeBinding.zeroOid
@@ -1,15 +1,17 @@
+use crate::js::{JsCallback, JsCallbackExt};
 use crate::repository::Repository;
 use crate::signature::Signature;
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
-use std::ops::Deref;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 /// Represents a hunk of a blame operation, which is a range of lines
 /// and information about who last modified them.
 #[napi(object)]
+#[derive(Clone)]
 pub struct BlameHunk {
   /// The oid of the commit where this line was last changed.
   pub final_commit_id: String,
@@ -33,7 +35,7 @@ pub struct BlameHunk {
 }
 
 /// Options for controlling blame behavior
-#[napi(object)]
+#[napi(object, object_to_js = false)]
 #[derive(Default)]
 pub struct BlameOptions {
   /// The minimum line number to blame (1-based index)
@@ -64,17 +66,76 @@ pub struct BlameOptions {
   pub track_copies_same_commit_moves: Option<bool>,
   /// Use mailmap file to map author and committer names and email addresses to canonical real names and email addresses.
   pub use_mailmap: Option<bool>,
+  /// Revisions to ignore, like `git blame --ignore-rev`. Lines changed by
+  /// these revisions are attributed to the previous commits which changed them.
+  pub ignore_revs: Option<Vec<String>>,
+  /// Path to a file listing revisions to ignore, like
+  /// `git blame --ignore-revs-file`. The path is relative to the working
+  /// directory of the repository.
+  ///
+  /// Revisions in the file set by the `blame.ignoreRevsFile` configuration
+  /// are always ignored. Unlike this option, a missing file set by the
+  /// configuration is skipped.
+  pub ignore_revs_file: Option<String>,
+  #[napi(ts_type = "(linesBlamed: number, totalLines: number) => void")]
+  /// Callback which is called to report the progress of blame.
+  ///
+  /// Only used by `blameFileAsync`.
+  pub progress: Option<BlameProgressCallback>,
+}
+
+pub type BlameProgressCallback = JsCallback<FnArgs<(u32, u32)>>;
+
+/// A line of a blamed file.
+#[napi(object)]
+pub struct BlameLine {
+  /// The 1-based line number in the final file.
+  pub line_number: u32,
+  /// Content of the line, without the line terminator.
+  pub content: String,
+  /// The 1-based line number in the original file where this line was
+  /// written.
+  pub orig_line_number: u32,
+  /// The path to the file where this line was originally written, after
+  /// following renames.
+  pub orig_path: Option<String>,
+  /// The hunk which the line belongs to.
+  pub hunk: BlameHunk,
+}
+
+/// Options for formatting blame like `git blame --porcelain`.
+#[napi(object)]
+pub struct BlamePorcelainOptions {
+  /// Repeat the information of the commit for each line, like
+  /// `git blame --line-porcelain`.
+  pub line_format: Option<bool>,
 }
 
 /// A wrapper around git2::Blame providing Node.js bindings
 #[napi]
 pub struct Blame {
+  pub(crate) repo: Reference<Repository>,
   pub(crate) inner: BlameInner,
+  /// Content of the blamed file, which is used to list lines.
+  pub(crate) content: Option<Rc<Vec<u8>>>,
+  /// Whether signatures are mapped by the mailmap.
+  pub(crate) use_mailmap: bool,
 }
 
 /// Inner implementation of Blame that handles shared references
 pub(crate) enum BlameInner {
   Repo(SharedReference<Repository, git2::Blame<'static>>),
+  /// Hunks which are computed apart from libgit2, when revisions are ignored
+  /// or the blame is computed asynchronously.
+  Hunks(Rc<Vec<BlameHunk>>),
+}
+
+/// An iterator over lines of a blamed file.
+#[napi(iterator)]
+pub struct BlameLines {
+  pub(crate) inner: Blame,
+  pub(crate) line: u32,
+  pub(crate) pos: usize,
 }
 
 /// An iterator over blame hunks.
@@ -96,7 +157,7 @@ pub struct BlameHunksByLine {
 
 impl From<&BlameOptions> for git2::BlameOptions {
   fn from(options: &BlameOptions) -> Self {
-    let mut git_opts = git2::BlameOptions::new();
+    let mut git_opts = options.history_options();
 
     if let Some(min_line) = options.min_line {
       git_opts.min_line(min_line as usize);
@@ -112,37 +173,47 @@ impl From<&BlameOptions> for git2::BlameOptions {
       }
     }
 
-    if let Some(ref oldest_commit) = options.oldest_commit {
+    git_opts
+  }
+}
+
+impl BlameOptions {
+  /// Options to search the history, without the range of lines and the
+  /// newest commit to blame.
+  fn history_options(&self) -> git2::BlameOptions {
+    let mut git_opts = git2::BlameOptions::new();
+
+    if let Some(ref oldest_commit) = self.oldest_commit {
       if let Ok(oid) = git2::Oid::from_str(oldest_commit) {
         git_opts.oldest_commit(oid);
       }
     }
 
-    if let Some(track_lines_movement) = options.track_lines_movement {
+    if let Some(track_lines_movement) = self.track_lines_movement {
       git_opts.track_copies_same_file(track_lines_movement);
     }
 
-    if let Some(first_parent) = options.first_parent {
+    if let Some(first_parent) = self.first_parent {
       git_opts.first_parent(first_parent);
     }
 
-    if let Some(ignore_whitespace) = options.ignore_whitespace {
+    if let Some(ignore_whitespace) = self.ignore_whitespace {
       git_opts.ignore_whitespace(ignore_whitespace);
     }
 
-    if let Some(track_copies_any_commit_copies) = options.track_copies_any_commit_copies {
+    if let Some(track_copies_any_commit_copies) = self.track_copies_any_commit_copies {
       git_opts.track_copies_any_commit_copies(track_copies_any_commit_copies);
     }
 
-    if let Some(track_copies_same_commit_copies) = options.track_copies_same_commit_copies {
+    if let Some(track_copies_same_commit_copies) = self.track_copies_same_commit_copies {
       git_opts.track_copies_same_commit_copies(track_copies_same_commit_copies);
     }
 
-    if let Some(track_copies_same_commit_moves) = options.track_copies_same_commit_moves {
+    if let Some(track_copies_same_commit_moves) = self.track_copies_same_commit_moves {
       git_opts.track_copies_same_commit_moves(track_copies_same_commit_moves);
     }
 
-    if let Some(use_mailmap) = options.use_mailmap {
+    if let Some(use_mailmap) = self.use_mailmap {
       git_opts.use_mailmap(use_mailmap);
     }
 
@@ -150,22 +221,52 @@ impl From<&BlameOptions> for git2::BlameOptions {
   }
 }
 
-impl Deref for BlameInner {
-  type Target = git2::Blame<'static>;
+impl BlameInner {
+  fn clone_with_env(&self, env: Env) -> crate::Result<Self> {
+    match self {
+      Self::Repo(repo) => Ok(Self::Repo(repo.clone(env)?)),
+      Self::Hunks(hunks) => Ok(Self::Hunks(hunks.clone())),
+    }
+  }
+
+  fn len(&self) -> usize {
+    match self {
+      Self::Repo(blame) => blame.len(),
+      Self::Hunks(hunks) => hunks.len(),
+    }
+  }
 
-  fn deref(&self) -> &Self::Target {
+  fn get_index(&self, index: usize) -> Option<BlameHunk> {
     match self {
-      Self::Repo(repo) => repo.deref(),
+      Self::Repo(blame) => blame.get_index(index).map(|x| BlameHunk::from(&x)),
+      Self::Hunks(hunks) => hunks.get(index).cloned(),
     }
   }
-}
 
-impl BlameInner {
-  fn clone_with_env(&self, env: Env) -> crate::Result<Self> {
+  fn get_line(&self, line: usize) -> Option<BlameHunk> {
     match self {
-      Self::Repo(repo) => Ok(Self::Repo(repo.clone(env)?)),
+      Self::Repo(blame) => blame.get_line(line).map(|x| BlameHunk::from(&x)),
+      Self::Hunks(hunks) => hunks
+        .iter()
+        .find(|x| {
+          let start = x.final_start_line_number as usize;
+          start <= line && line < start + x.lines_in_hunk as usize
+        })
+        .cloned(),
     }
   }
+
+  fn blame_line(&self, line_number: u32, line: &[u8]) -> Option<BlameLine> {
+    let hunk = self.get_line(line_number as usize)?;
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    Some(BlameLine {
+      line_number,
+      content: String::from_utf8_lossy(line).to_string(),
+      orig_line_number: hunk.orig_start_line_number + line_number - hunk.final_start_line_number,
+      orig_path: hunk.path.clone(),
+      hunk,
+    })
+  }
 }
 
 impl<'a> From<&git2::BlameHunk<'a>> for BlameHunk {
@@ -287,7 +388,7 @@ impl Blame {
   ///
   /// @returns True if the blame result contains no hunks
   pub fn is_empty(&self) -> bool {
-    self.inner.is_empty()
+    self.inner.len() == 0
   }
 
   #[napi]
@@ -310,7 +411,7 @@ impl Blame {
       .get_index(index as usize)
       .ok_or_else(|| Error::new(Status::InvalidArg, format!("No blame hunk found at index {index}")))?;
 
-    Ok(BlameHunk::from(&hunk))
+    Ok(hunk)
   }
 
   #[napi]
@@ -333,7 +434,7 @@ impl Blame {
       .get_line(line as usize)
       .ok_or_else(|| Error::new(Status::InvalidArg, format!("No blame hunk found for line {line}")))?;
 
-    Ok(BlameHunk::from(&hunk))
+    Ok(hunk)
   }
 
   #[napi(iterator)]
@@ -360,7 +461,10 @@ impl Blame {
   /// ```
   pub fn iter(&self, env: Env) -> crate::Result<BlameHunks> {
     let inner = Blame {
+      repo: self.repo.clone(env)?,
       inner: self.inner.clone_with_env(env)?,
+      content: self.content.clone(),
+      use_mailmap: self.use_mailmap,
     };
 
     Ok(BlameHunks {
@@ -394,7 +498,10 @@ impl Blame {
   /// ```
   pub fn iter_by_line(&self, env: Env) -> crate::Result<BlameHunksByLine> {
     let inner = Blame {
+      repo: self.repo.clone(env)?,
       inner: self.inner.clone_with_env(env)?,
+      content: self.content.clone(),
+      use_mailmap: self.use_mailmap,
     };
 
     Ok(BlameHunksByLine {
@@ -424,6 +531,7 @@ impl Blame {
   ///
   /// @param {Buffer} buffer - Buffer containing file content to blame
   /// @returns A new Blame object for the buffer content
+  /// @throws If the blame ignores revisions or is created by `blameFileAsync`
   pub fn buffer(&self, buffer: Buffer, env: Env) -> crate::Result<Blame> {
     let blame = match &self.inner {
       BlameInner::Repo(shared_ref) => {
@@ -435,10 +543,545 @@ impl Blame {
             .map_err(|e| crate::Error::from(e).into())
         })?
       }
+      BlameInner::Hunks(_) => {
+        return Err(
+          Error::new(
+            Status::InvalidArg,
+            "Blame of a buffer is not supported for blames which ignore revisions or are computed asynchronously",
+          )
+          .into(),
+        );
+      }
     };
 
     Ok(Blame {
+      repo: self.repo.clone(env)?,
       inner: BlameInner::Repo(blame),
+      content: Some(Rc::new(buffer.to_vec())),
+      use_mailmap: self.use_mailmap,
+    })
+  }
+
+  #[napi(iterator)]
+  /// Gets the lines of the blamed file with their hunks as an iterator
+  ///
+  /// Lines which are not blamed, e.g. outside of `minLine` and `maxLine`, are
+  /// skipped.
+  ///
+  /// @category Blame/Methods
+  /// @signature
+  /// ```ts
+  /// class Blame {
+  ///   iterLines(): Generator<BlameLine>;
+  /// }
+  /// ```
+  ///
+  /// @returns Iterator of the lines of the blamed file
+  /// @example
+  /// ```ts
+  /// for (const { lineNumber, content, hunk } of blame.iterLines()) {
+  ///   console.log(`${hunk.finalCommitId.slice(0, 8)} ${lineNumber}) ${content}`);
+  /// }
+  /// ```
+  pub fn iter_lines(&self, env: Env) -> crate::Result<BlameLines> {
+    let inner = Blame {
+      repo: self.repo.clone(env)?,
+      inner: self.inner.clone_with_env(env)?,
+      content: self.content.clone(),
+      use_mailmap: self.use_mailmap,
+    };
+
+    Ok(BlameLines { inner, line: 1, pos: 0 })
+  }
+
+  #[napi]
+  /// Gets the lines of the blamed file with their hunks
+  ///
+  /// Each line has the line number and the path of the file where it was
+  /// originally written, after following renames and copies. Lines which are
+  /// not blamed are skipped.
+  ///
+  /// @category Blame/Methods
+  /// @signature
+  /// ```ts
+  /// class Blame {
+  ///   lines(): BlameLine[];
+  /// }
+  /// ```
+  ///
+  /// @returns Lines of the blamed file
+  /// @example
+  /// ```ts
+  /// const blame = repo.blameFile('renamed.txt', { trackCopiesSameCommitMoves: true });
+  /// for (const line of blame.lines()) {
+  ///   console.log(`${line.origPath}:${line.origLineNumber} ${line.content}`);
+  /// }
+  /// ```
+  pub fn lines(&self) -> Vec<BlameLine> {
+    let content = self.content.as_deref().map(|x| x.as_slice()).unwrap_or_default();
+    let mut lines = Vec::new();
+    let mut pos = 0;
+    let mut line_number = 1;
+    while let Some(line) = next_line(content, &mut pos) {
+      lines.extend(self.inner.blame_line(line_number, line));
+      line_number += 1;
+    }
+    lines
+  }
+
+  #[napi]
+  /// Formats the blame like `git blame --porcelain`
+  ///
+  /// Information of each commit is written once at the first line blamed to
+  /// the commit, unless `lineFormat` is set to write it for every line like
+  /// `git blame --line-porcelain`.
+  ///
+  /// @category Blame/Methods
+  /// @signature
+  /// ```ts
+  /// class Blame {
+  ///   toPorcelain(options?: BlamePorcelainOptions): string;
+  /// }
+  /// ```
+  ///
+  /// @param {BlamePorcelainOptions} [options] - Options for the output format
+  /// @returns Blame in the porcelain format
+  /// @throws If commits of the blame can't be found
+  /// @example
+  /// ```ts
+  /// const blame = repo.blameFile('path/to/file.js');
+  /// const output = blame.toPorcelain({ lineFormat: true });
+  /// ```
+  pub fn to_porcelain(&self, options: Option<BlamePorcelainOptions>) -> crate::Result<String> {
+    let line_format = options.and_then(|x| x.line_format).unwrap_or_default();
+    let mailmap = match self.use_mailmap {
+      true => Some(self.repo.inner.mailmap()?),
+      false => None,
+    };
+    let content = self.content.as_deref().map(|x| x.as_slice()).unwrap_or_default();
+    let mut commits = HashSet::new();
+    let mut paths = HashSet::new();
+    let mut group = None;
+    let mut out = String::new();
+    let mut pos = 0;
+    let mut line_number = 1;
+    while let Some(raw) = next_line(content, &mut pos) {
+      let Some(line) = self.inner.blame_line(line_number, raw) else {
+        line_number += 1;
+        continue;
+      };
+      let hunk = &line.hunk;
+      out.push_str(&format!(
+        "{} {} {}",
+        hunk.final_commit_id, line.orig_line_number, line_number
+      ));
+      if group != Some(hunk.final_start_line_number) {
+        group = Some(hunk.final_start_line_number);
+        let remaining = hunk.final_start_line_number + hunk.lines_in_hunk - line_number;
+        out.push_str(&format!(" {remaining}"));
+      }
+      out.push('\n');
+      let path = line.orig_path.as_deref().unwrap_or_default();
+      let new_commit = commits.insert(hunk.final_commit_id.clone());
+      let new_path = paths.insert((hunk.final_commit_id.clone(), path.to_string()));
+      if line_format || new_commit {
+        push_porcelain_details(&mut out, &self.repo.inner, mailmap.as_ref(), hunk, path)?;
+      }
+      if line_format || new_commit || new_path {
+        out.push_str(&format!("filename {path}\n"));
+      }
+      out.push('\t');
+      out.push_str(&String::from_utf8_lossy(raw));
+      out.push('\n');
+      line_number += 1;
+    }
+    Ok(out)
+  }
+}
+
+#[napi]
+impl Generator for BlameLines {
+  type Yield = BlameLine;
+  type Next = ();
+  type Return = ();
+
+  fn next(&mut self, _value: Option<Self::Next>) -> Option<Self::Yield> {
+    let content = self.inner.content.clone()?;
+    while let Some(line) = next_line(&content, &mut self.pos) {
+      let line_number = self.line;
+      self.line += 1;
+      if let Some(line) = self.inner.inner.blame_line(line_number, line) {
+        return Some(line);
+      }
+    }
+    None
+  }
+}
+
+/// Split the next line from the position of the content, without the newline.
+fn next_line<'a>(content: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+  let rest = content.get(*pos..).filter(|x| !x.is_empty())?;
+  let end = rest
+    .iter()
+    .position(|x| *x == b'\n')
+    .map(|x| x + 1)
+    .unwrap_or(rest.len());
+  *pos += end;
+  let line = &rest[..end];
+  Some(line.strip_suffix(b"\n").unwrap_or(line))
+}
+
+fn push_signature(out: &mut String, role: &str, name: &str, email: &str, when: git2::Time) {
+  let offset = when.offset_minutes().abs();
+  out.push_str(&format!(
+    "{role} {name}\n{role}-mail <{email}>\n{role}-time {}\n{role}-tz {}{:02}{:02}\n",
+    when.seconds(),
+    when.sign(),
+    offset / 60,
+    offset % 60,
+  ));
+}
+
+/// Write the information of the commit of the hunk, in the porcelain format.
+///
+/// Signatures are mapped by the mailmap, when it's given.
+fn push_porcelain_details(
+  out: &mut String,
+  repo: &git2::Repository,
+  mailmap: Option<&git2::Mailmap>,
+  hunk: &BlameHunk,
+  path: &str,
+) -> crate::Result<()> {
+  let oid = git2::Oid::from_str(&hunk.final_commit_id)?;
+  if oid.is_zero() {
+    let now = git2::Time::new(chrono::Utc::now().timestamp(), 0);
+    push_signature(out, "author", "Not Committed Yet", "not.committed.yet", now);
+    push_signature(out, "committer", "Not Committed Yet", "not.committed.yet", now);
+    out.push_str(&format!("summary Version of {path} from {path}\n"));
+    return Ok(());
+  }
+  let commit = repo.find_commit(oid)?;
+  let (author, committer) = match mailmap {
+    Some(mailmap) => (
+      commit.author_with_mailmap(mailmap)?,
+      commit.committer_with_mailmap(mailmap)?,
+    ),
+    None => (commit.author(), commit.committer()),
+  };
+  for (role, signature) in [("author", &author), ("committer", &committer)] {
+    push_signature(
+      out,
+      role,
+      &String::from_utf8_lossy(signature.name_bytes()),
+      &String::from_utf8_lossy(signature.email_bytes()),
+      signature.when(),
+    );
+  }
+  out.push_str(&format!("summary {}\n", commit.summary().unwrap_or_default()));
+  if hunk.is_boundary {
+    out.push_str("boundary\n");
+  } else if let Ok(parent) = commit.parent(0) {
+    if let Some(previous) = parent_path(repo, &commit, &parent, path)? {
+      out.push_str(&format!("previous {} {previous}\n", parent.id()));
+    }
+  }
+  Ok(())
+}
+
+/// Find the path of the file in the parent, following a rename or a copy by
+/// the commit.
+fn parent_path(
+  repo: &git2::Repository,
+  commit: &git2::Commit,
+  parent: &git2::Commit,
+  path: &str,
+) -> crate::Result<Option<String>> {
+  let parent_tree = parent.tree()?;
+  if parent_tree.get_path(Path::new(path)).is_ok() {
+    return Ok(Some(path.to_string()));
+  }
+  let mut diff = repo.diff_tree_to_tree(Some(&parent_tree), Some(&commit.tree()?), None)?;
+  diff.find_similar(Some(git2::DiffFindOptions::new().renames(true).copies(true)))?;
+  let previous = diff
+    .deltas()
+    .filter(|x| matches!(x.status(), git2::Delta::Renamed | git2::Delta::Copied))
+    .find(|x| x.new_file().path() == Some(Path::new(path)))
+    .and_then(|x| x.old_file().path().map(|x| x.to_string_lossy().to_string()));
+  Ok(previous)
+}
+
+/// Read the content of the file at the newest commit to blame.
+fn file_content(repo: &git2::Repository, path: &Path, options: Option<&BlameOptions>) -> Option<Vec<u8>> {
+  let commit = match options.and_then(|x| x.newest_commit.as_deref()) {
+    Some(oid) => repo.find_commit(git2::Oid::from_str(oid).ok()?).ok()?,
+    None => repo.head().ok()?.peel_to_commit().ok()?,
+  };
+  let entry = commit.tree().ok()?.get_path(path).ok()?;
+  let blob = repo.find_blob(entry.id()).ok()?;
+  Some(blob.content().to_vec())
+}
+
+/// Collect revisions to ignore from the options and the `blame.ignoreRevsFile`
+/// configuration.
+///
+/// A missing file set by the configuration is skipped, so that it doesn't
+/// break blaming in repositories which don't have the file.
+fn ignored_revisions(repo: &git2::Repository, options: Option<&BlameOptions>) -> crate::Result<HashSet<git2::Oid>> {
+  let mut revs = options.and_then(|x| x.ignore_revs.clone()).unwrap_or_default();
+  let base = repo.workdir().unwrap_or(repo.path());
+  let mut contents = Vec::new();
+  if let Ok(file) = repo.config()?.get_path("blame.ignoreRevsFile") {
+    match std::fs::read_to_string(base.join(file)) {
+      Ok(content) => contents.push(content),
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+      Err(e) => return Err(e.into()),
+    }
+  }
+  if let Some(file) = options.and_then(|x| x.ignore_revs_file.as_deref()) {
+    contents.push(std::fs::read_to_string(base.join(file))?);
+  }
+  for content in contents {
+    for line in content.lines() {
+      let rev = line.split('#').next().unwrap_or_default().trim();
+      if !rev.is_empty() {
+        revs.push(rev.to_string());
+      }
+    }
+  }
+  let mut oids = HashSet::new();
+  for rev in revs {
+    oids.insert(repo.revparse_single(&rev)?.peel_to_commit()?.id());
+  }
+  Ok(oids)
+}
+
+/// Push the hunk, merging it into the last hunk if it continues the last hunk.
+fn push_hunk(hunks: &mut Vec<BlameHunk>, hunk: BlameHunk) {
+  if let Some(last) = hunks.last_mut() {
+    if last.final_commit_id == hunk.final_commit_id
+      && last.orig_commit_id == hunk.orig_commit_id
+      && last.path == hunk.path
+      && last.final_start_line_number + last.lines_in_hunk == hunk.final_start_line_number
+      && last.orig_start_line_number + last.lines_in_hunk == hunk.orig_start_line_number
+    {
+      last.lines_in_hunk += hunk.lines_in_hunk;
+      return;
+    }
+  }
+  hunks.push(hunk);
+}
+
+/// Attributes lines changed by ignored revisions to the previous commits which
+/// changed them, like `git blame --ignore-rev`.
+///
+/// A line is followed into the first parent of the ignored commit at the same
+/// position in the changed region. Lines which were added by the ignored commit
+/// stay attributed to it.
+struct IgnoreRevisions<'a> {
+  repo: &'a git2::Repository,
+  revs: &'a HashSet<git2::Oid>,
+  options: Option<&'a BlameOptions>,
+  blames: HashMap<(git2::Oid, PathBuf), git2::Blame<'a>>,
+}
+
+impl<'a> IgnoreRevisions<'a> {
+  fn apply(&mut self, hunks: Vec<BlameHunk>) -> crate::Result<Vec<BlameHunk>> {
+    let mut result = Vec::with_capacity(hunks.len());
+    for hunk in hunks {
+      let commit = git2::Oid::from_str(&hunk.final_commit_id)?;
+      if !self.revs.contains(&commit) {
+        push_hunk(&mut result, hunk);
+        continue;
+      }
+      let path = PathBuf::from(hunk.path.clone().unwrap_or_default());
+      for offset in 0..hunk.lines_in_hunk {
+        let orig_line = hunk.orig_start_line_number + offset;
+        let line = match self.blame_line(commit, &path, orig_line)? {
+          Some(line) => line,
+          None => BlameHunk {
+            orig_start_line_number: orig_line,
+            ..hunk.clone()
+          },
+        };
+        push_hunk(
+          &mut result,
+          BlameHunk {
+            final_start_line_number: hunk.final_start_line_number + offset,
+            lines_in_hunk: 1,
+            ..line
+          },
+        );
+      }
+    }
+    Ok(result)
+  }
+
+  /// Blame the line of the file at the commit from the parent of the commit,
+  /// skipping ignored revisions.
+  fn blame_line(&mut self, commit: git2::Oid, path: &Path, line: u32) -> crate::Result<Option<BlameHunk>> {
+    let (mut commit, mut path, mut line) = (commit, path.to_path_buf(), line);
+    loop {
+      let Some((parent, parent_path, parent_line)) = self.parent_line(commit, &path, line)? else {
+        return Ok(None);
+      };
+      let key = (parent, parent_path);
+      if !self.blames.contains_key(&key) {
+        let mut opts = self.options.map(|x| x.history_options()).unwrap_or_default();
+        opts.newest_commit(parent);
+        let blame = self.repo.blame_file(&key.1, Some(&mut opts))?;
+        self.blames.insert(key.clone(), blame);
+      }
+      let Some(hunk) = self.blames[&key].get_line(parent_line as usize) else {
+        return Ok(None);
+      };
+      let offset = parent_line - hunk.final_start_line() as u32;
+      let hunk = BlameHunk::from(&hunk);
+      let orig_line = hunk.orig_start_line_number + offset;
+      let oid = git2::Oid::from_str(&hunk.final_commit_id)?;
+      if !self.revs.contains(&oid) {
+        return Ok(Some(BlameHunk {
+          orig_start_line_number: orig_line,
+          ..hunk
+        }));
+      }
+      commit = oid;
+      path = PathBuf::from(hunk.path.unwrap_or_default());
+      line = orig_line;
+    }
+  }
+
+  /// Find the line in the first parent of the commit which corresponds to the
+  /// line of the file at the commit.
+  fn parent_line(&self, commit: git2::Oid, path: &Path, line: u32) -> crate::Result<Option<(git2::Oid, PathBuf, u32)>> {
+    let commit = self.repo.find_commit(commit)?;
+    let Ok(parent) = commit.parent(0) else {
+      return Ok(None);
+    };
+    let mut opts = git2::DiffOptions::new();
+    opts.context_lines(0).pathspec(path);
+    let diff = self
+      .repo
+      .diff_tree_to_tree(Some(&parent.tree()?), Some(&commit.tree()?), Some(&mut opts))?;
+    let Some(delta) = diff.deltas().next() else {
+      return Ok(Some((parent.id(), path.to_path_buf(), line)));
+    };
+    if delta.status() == git2::Delta::Added {
+      return Ok(None);
+    }
+    let parent_path = delta.old_file().path().unwrap_or(path).to_path_buf();
+    let Some(patch) = git2::Patch::from_diff(&diff, 0)? else {
+      return Ok(None);
+    };
+    let line = line as i64;
+    let mut shift = 0;
+    for index in 0..patch.num_hunks() {
+      let (hunk, _) = patch.hunk(index)?;
+      let (old_start, old_lines) = (hunk.old_start() as i64, hunk.old_lines() as i64);
+      let (new_start, new_lines) = (hunk.new_start() as i64, hunk.new_lines() as i64);
+      // Empty ranges start after the line of the start position.
+      let new_end = new_start + new_lines.max(1);
+      if line < new_start || (new_lines == 0 && line == new_start) {
+        break;
+      }
+      if line < new_end {
+        if old_lines == 0 {
+          return Ok(None);
+        }
+        let parent_line = old_start + (line - new_start).min(old_lines - 1);
+        return Ok(Some((parent.id(), parent_path, parent_line as u32)));
+      }
+      shift = old_start + old_lines.max(1) - new_end;
+    }
+    Ok(Some((parent.id(), parent_path, (line + shift) as u32)))
+  }
+}
+
+/// Attribute lines changed by the ignored revisions to the previous commits.
+fn ignore_revisions(
+  repo: &git2::Repository,
+  revs: &HashSet<git2::Oid>,
+  hunks: Vec<BlameHunk>,
+  options: Option<&BlameOptions>,
+) -> crate::Result<Vec<BlameHunk>> {
+  if revs.is_empty() {
+    return Ok(hunks);
+  }
+  let mut ignore = IgnoreRevisions {
+    repo,
+    revs,
+    options,
+    blames: HashMap::new(),
+  };
+  ignore.apply(hunks)
+}
+
+/// Number of lines blamed by `blameFileAsync` between which the progress is
+/// reported.
+const BLAME_PROGRESS_LINES: u32 = 1000;
+
+pub struct BlameFileTask {
+  repo: Reference<Repository>,
+  path: String,
+  options: Option<BlameOptions>,
+}
+
+unsafe impl Send for BlameFileTask {}
+
+impl BlameFileTask {
+  fn blame(&self) -> crate::Result<(Vec<BlameHunk>, Option<Vec<u8>>)> {
+    let repo = &self.repo.inner;
+    let path = Path::new(&self.path);
+    let options = self.options.as_ref();
+    let content = file_content(repo, path, options);
+    let mut opts = options.map(git2::BlameOptions::from).unwrap_or_default();
+    let blame = repo.blame_file(path, Some(&mut opts))?;
+    let revs = ignored_revisions(repo, options)?;
+    let mut ignore = IgnoreRevisions {
+      repo,
+      revs: &revs,
+      options,
+      blames: HashMap::new(),
+    };
+    let progress = options.and_then(|x| x.progress.as_ref());
+    let total = blame.iter().map(|x| x.lines_in_hunk() as u32).sum::<u32>();
+    let (mut blamed, mut reported) = (0, 0);
+    let mut hunks = Vec::new();
+    for hunk in blame.iter() {
+      let hunk = BlameHunk::from(&hunk);
+      blamed += hunk.lines_in_hunk;
+      // Lines changed by ignored revisions are blamed again here, which is
+      // where most of the time goes when revisions are ignored.
+      for hunk in ignore.apply(vec![hunk])? {
+        push_hunk(&mut hunks, hunk);
+      }
+      if let Some(progress) = progress {
+        if blamed - reported >= BLAME_PROGRESS_LINES || blamed == total {
+          progress.invoke((blamed, total).into())?;
+          reported = blamed;
+        }
+      }
+    }
+    Ok((hunks, content))
+  }
+}
+
+#[napi]
+impl Task for BlameFileTask {
+  type Output = (Vec<BlameHunk>, Option<Vec<u8>>);
+  type JsValue = Blame;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let output = self.blame()?;
+    Ok(output)
+  }
+
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    let (hunks, content) = output;
+    Ok(Blame {
+      repo: self.repo.clone(env)?,
+      inner: BlameInner::Hunks(Rc::new(hunks)),
+      content: content.map(Rc::new),
+      use_mailmap: self.options.as_ref().and_then(|x| x.use_mailmap).unwrap_or_default(),
     })
   }
 }
@@ -480,6 +1123,9 @@ impl Repository {
     env: Env,
   ) -> crate::Result<Blame> {
     let file_path = Path::new(&path);
+    let content = file_content(&self.inner, file_path, options.as_ref());
+    let revs = ignored_revisions(&self.inner, options.as_ref())?;
+    let owner = this.clone(env)?;
 
     let blame = this.share_with(env, |repo| {
       let result = match &options {
@@ -493,8 +1139,70 @@ impl Repository {
       result.map_err(|e| crate::Error::from(e).into())
     })?;
 
+    let inner = if revs.is_empty() {
+      BlameInner::Repo(blame)
+    } else {
+      let hunks = blame.iter().map(|x| BlameHunk::from(&x)).collect::<Vec<_>>();
+      let hunks = ignore_revisions(&self.inner, &revs, hunks, options.as_ref())?;
+      BlameInner::Hunks(Rc::new(hunks))
+    };
+
     Ok(Blame {
-      inner: BlameInner::Repo(blame),
+      repo: owner,
+      inner,
+      content: content.map(Rc::new),
+      use_mailmap: options.as_ref().and_then(|x| x.use_mailmap).unwrap_or_default(),
     })
   }
+
+  #[napi]
+  /// Asynchronously creates a blame object for the file at the given path.
+  ///
+  /// The `progress` callback of options is called every thousand lines with
+  /// the number of blamed lines and the total number of lines to blame, while
+  /// the lines are attributed to commits. Lines changed by ignored revisions
+  /// are blamed again in this step, which takes most of the time then.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   blameFileAsync(
+  ///     path: string,
+  ///     options?: BlameOptions,
+  ///     signal?: AbortSignal,
+  ///   ): Promise<Blame>;
+  /// }
+  /// ```
+  ///
+  /// @param {string} path - Path to the file to blame
+  /// @param {BlameOptions} [options] - Options to control blame behavior
+  /// @param {AbortSignal} [signal] - Optional AbortSignal to cancel the operation.
+  /// @returns Blame object for the specified file
+  /// @throws If the file doesn't exist or can't be opened
+  ///
+  /// @example
+  /// ```ts
+  /// const blame = await repo.blameFileAsync('path/to/file.js', {
+  ///   progress: (linesBlamed, totalLines) => {
+  ///     console.log(`${linesBlamed}/${totalLines}`);
+  ///   },
+  /// });
+  /// ```
+  pub fn blame_file_async(
+    &self,
+    this: Reference<Repository>,
+    path: String,
+    options: Option<BlameOptions>,
+    signal: Option<AbortSignal>,
+  ) -> AsyncTask<BlameFileTask> {
+    AsyncTask::with_optional_signal(
+      BlameFileTask {
+        repo: this,
+        path,
+        options,
+      },
+      signal,
+    )
+  }
 }
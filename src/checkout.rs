@@ -1,11 +1,109 @@
+use crate::diff::FileMode;
+use crate::hooks::{head_oid, Hooks};
 use crate::index::Index;
 use crate::object::GitObject;
 use crate::repository::Repository;
+use crate::sparse_checkout::apply_if_enabled as apply_sparse_checkout;
+use crate::util::path_to_string;
+use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::cell::RefCell;
 use std::path::Path;
+use std::rc::Rc;
+
+#[napi(string_enum)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+/// Types of notifications emitted during checkout.
+///
+/// - `Conflict` : A file conflicts with the checkout and prevents it.
+/// - `Dirty` : A file is modified in the working directory but is not
+///   changed by the checkout.
+/// - `Updated` : A file is changed by the checkout.
+/// - `Untracked` : An untracked file in the working directory.
+/// - `Ignored` : An ignored file in the working directory.
+pub enum CheckoutNotifyType {
+  Conflict,
+  Dirty,
+  Updated,
+  Untracked,
+  Ignored,
+}
+
+impl CheckoutNotifyType {
+  fn from_git2(value: git2::CheckoutNotificationType) -> Option<Self> {
+    if value.contains(git2::CheckoutNotificationType::CONFLICT) {
+      Some(Self::Conflict)
+    } else if value.contains(git2::CheckoutNotificationType::DIRTY) {
+      Some(Self::Dirty)
+    } else if value.contains(git2::CheckoutNotificationType::UPDATED) {
+      Some(Self::Updated)
+    } else if value.contains(git2::CheckoutNotificationType::UNTRACKED) {
+      Some(Self::Untracked)
+    } else if value.contains(git2::CheckoutNotificationType::IGNORED) {
+      Some(Self::Ignored)
+    } else {
+      None
+    }
+  }
+}
+
+impl From<CheckoutNotifyType> for git2::CheckoutNotificationType {
+  fn from(value: CheckoutNotifyType) -> Self {
+    match value {
+      CheckoutNotifyType::Conflict => git2::CheckoutNotificationType::CONFLICT,
+      CheckoutNotifyType::Dirty => git2::CheckoutNotificationType::DIRTY,
+      CheckoutNotifyType::Updated => git2::CheckoutNotificationType::UPDATED,
+      CheckoutNotifyType::Untracked => git2::CheckoutNotificationType::UNTRACKED,
+      CheckoutNotifyType::Ignored => git2::CheckoutNotificationType::IGNORED,
+    }
+  }
+}
 
 #[napi(object)]
 #[derive(Clone)]
+/// A side of a file reported by a checkout notification.
+pub struct CheckoutNotifyFile {
+  /// Object ID(SHA1) of the file.
+  pub id: String,
+  /// Path of the file relative to the working directory.
+  pub path: Option<String>,
+  /// Size of the file, in bytes.
+  pub size: i64,
+  /// Mode of the file.
+  pub mode: FileMode,
+}
+
+impl From<git2::DiffFile<'_>> for CheckoutNotifyFile {
+  fn from(value: git2::DiffFile<'_>) -> Self {
+    Self {
+      id: value.id().to_string(),
+      path: value.path().map(path_to_string),
+      size: value.size() as i64,
+      mode: value.mode().into(),
+    }
+  }
+}
+
+#[napi(object)]
+#[derive(Clone)]
+/// A notification emitted for a file during checkout.
+pub struct CheckoutNotify {
+  #[napi(js_name = "type")]
+  /// Type of the notification.
+  pub kind: CheckoutNotifyType,
+  /// Path of the file relative to the working directory.
+  pub path: String,
+  /// The file in the baseline tree. (usually `HEAD`)
+  pub baseline: Option<CheckoutNotifyFile>,
+  /// The file in the target tree.
+  pub target: Option<CheckoutNotifyFile>,
+  /// The file in the working directory.
+  pub workdir: Option<CheckoutNotifyFile>,
+}
+
+pub type CheckoutProgressCallback = Function<'static, FnArgs<(Option<String>, u32, u32)>, ()>;
+
+#[napi(object)]
 pub struct CheckoutOptions {
   /// Indicate that this checkout should perform a dry run by checking for
   /// conflicts but not make any actual changes.
@@ -107,10 +205,42 @@ pub struct CheckoutOptions {
   pub our_label: Option<String>,
   /// The name of the common their side of conflicts
   pub their_label: Option<String>,
+  /// Types of notifications for which `notify` is called.
+  ///
+  /// If not provided, `notify` is called for every type of notification.
+  pub notify_on: Option<Vec<CheckoutNotifyType>>,
+  #[napi(ts_type = "(notify: CheckoutNotify) => boolean | void")]
+  /// Callback which is called for each file with a notification during
+  /// checkout.
+  ///
+  /// Returning `false` cancels the checkout. An error thrown by the callback
+  /// also cancels the checkout, and is thrown by the checkout.
+  ///
+  /// Only used by `checkoutHead`, `checkoutIndex`, `checkoutTree` and
+  /// `checkoutDryRun`.
+  pub notify: Option<Function<'static, CheckoutNotify, Option<bool>>>,
+  #[napi(ts_type = "(path: string | null, completed: number, total: number) => void")]
+  /// Callback which is called to report the progress of checkout.
+  ///
+  /// Only used by `checkoutHead`, `checkoutIndex`, `checkoutTree` and
+  /// `checkoutDryRun`.
+  pub progress: Option<CheckoutProgressCallback>,
+  /// Run the `post-checkout` hook after checkout.
+  ///
+  /// Only used by `checkoutHead`, `checkoutIndex` and `checkoutTree`.
+  ///
+  /// `checkoutTree` doesn't move `HEAD`, so the hook is run as a file checkout.
+  pub run_hooks: Option<bool>,
 }
 
 impl From<CheckoutOptions> for git2::build::CheckoutBuilder<'static> {
   fn from(value: CheckoutOptions) -> Self {
+    Self::from(&value)
+  }
+}
+
+impl From<&CheckoutOptions> for git2::build::CheckoutBuilder<'static> {
+  fn from(value: &CheckoutOptions) -> Self {
     let mut builder = git2::build::CheckoutBuilder::new();
     if let Some(true) = value.dry_run {
       builder.dry_run();
@@ -172,25 +302,175 @@ impl From<CheckoutOptions> for git2::build::CheckoutBuilder<'static> {
     if let Some(perm) = value.file_perm {
       builder.file_perm(perm);
     }
-    if let Some(path) = value.path {
+    if let Some(path) = &value.path {
       builder.path(path);
     }
-    if let Some(dst) = value.target_dir {
-      builder.target_dir(Path::new(&dst));
+    if let Some(dst) = &value.target_dir {
+      builder.target_dir(Path::new(dst));
     }
-    if let Some(label) = value.ancestor_label {
-      builder.ancestor_label(&label);
+    if let Some(label) = &value.ancestor_label {
+      builder.ancestor_label(label);
     }
-    if let Some(label) = value.our_label {
-      builder.our_label(&label);
+    if let Some(label) = &value.our_label {
+      builder.our_label(label);
     }
-    if let Some(label) = value.their_label {
-      builder.their_label(&label);
+    if let Some(label) = &value.their_label {
+      builder.their_label(label);
     }
     builder
   }
 }
 
+/// Notifications recorded during checkout, and the first error thrown by the
+/// JavaScript callbacks.
+#[derive(Clone, Default)]
+pub(crate) struct CheckoutRecords {
+  notifications: Rc<RefCell<Vec<CheckoutNotify>>>,
+  error: Rc<RefCell<Option<Error>>>,
+}
+
+impl CheckoutRecords {
+  /// Keep the error of the JavaScript callback to throw it after checkout.
+  pub(crate) fn catch<T>(&self, result: Result<T>) -> Option<T> {
+    match result {
+      Ok(value) => Some(value),
+      Err(e) => {
+        self.error.borrow_mut().get_or_insert(e);
+        None
+      }
+    }
+  }
+
+  /// Throw the error of the JavaScript callbacks if any, or list paths of the
+  /// recorded conflicts in the error message if the checkout is prevented by
+  /// conflicts.
+  pub(crate) fn finish(&self, result: std::result::Result<(), git2::Error>) -> crate::Result<()> {
+    if let Some(e) = self.error.borrow_mut().take() {
+      return Err(e.into());
+    }
+    let e = match result {
+      Ok(()) => return Ok(()),
+      Err(e) => e,
+    };
+    let notifications = self.notifications.borrow();
+    if e.code() != git2::ErrorCode::Conflict || notifications.is_empty() {
+      return Err(e.into());
+    }
+    let paths = notifications.iter().map(|x| x.path.clone()).collect::<Vec<_>>();
+    let message = format!("{}: {}", e.message(), paths.join(", "));
+    Err(git2::Error::new(e.code(), e.class(), message).into())
+  }
+}
+
+/// Create a checkout builder which calls the JavaScript callbacks of the
+/// options, and records notifications of the given types.
+pub(crate) fn checkout_builder(
+  env: Env,
+  mut options: Option<CheckoutOptions>,
+  record_on: git2::CheckoutNotificationType,
+  records: CheckoutRecords,
+) -> crate::Result<git2::build::CheckoutBuilder<'static>> {
+  let (notify, notify_on, progress) = match options.as_mut() {
+    Some(opts) => (opts.notify.take(), opts.notify_on.take(), opts.progress.take()),
+    None => (None, None, None),
+  };
+  let notify_on = match (&notify, notify_on) {
+    (Some(_), Some(types)) => types
+      .into_iter()
+      .fold(git2::CheckoutNotificationType::empty(), |acc, x| acc | x.into()),
+    (Some(_), None) => git2::CheckoutNotificationType::all(),
+    (None, _) => git2::CheckoutNotificationType::empty(),
+  };
+  let notify = match notify {
+    Some(callback) => Some(callback.create_ref()?),
+    None => None,
+  };
+  let progress = match progress {
+    Some(callback) => Some(callback.create_ref()?),
+    None => None,
+  };
+
+  let records_for_progress = records.clone();
+  let mut builder = options.map(git2::build::CheckoutBuilder::from).unwrap_or_default();
+  builder.notify_on(notify_on | record_on);
+  builder.notify(move |kind, path, baseline, target, workdir| {
+    let Some(notify_type) = CheckoutNotifyType::from_git2(kind) else {
+      return true;
+    };
+    let event = CheckoutNotify {
+      kind: notify_type,
+      path: path.map(path_to_string).unwrap_or_default(),
+      baseline: baseline.map(CheckoutNotifyFile::from),
+      target: target.map(CheckoutNotifyFile::from),
+      workdir: workdir.map(CheckoutNotifyFile::from),
+    };
+    if record_on.intersects(kind) {
+      records.notifications.borrow_mut().push(event.clone());
+    }
+    match &notify {
+      Some(js_ref) if notify_on.intersects(kind) => {
+        let result = js_ref.borrow_back(&env).and_then(|callback| callback.call(event));
+        // An error thrown by the callback cancels the checkout, and is thrown
+        // after checkout.
+        records.catch(result).map(|x| x.unwrap_or(true)).unwrap_or(false)
+      }
+      _ => true,
+    }
+  });
+  if let Some(js_ref) = progress {
+    let records = records_for_progress;
+    builder.progress(move |path, completed, total| {
+      let path = path.map(path_to_string);
+      let result = js_ref
+        .borrow_back(&env)
+        .and_then(|callback| callback.call((path, completed as u32, total as u32).into()));
+      records.catch(result);
+    });
+  }
+  Ok(builder)
+}
+
+/// Run checkout with the builder, listing paths of the conflicts in the
+/// error message if the checkout is prevented by conflicts.
+///
+/// Files outside of the sparse checkout patterns are removed from the working
+/// tree afterwards, if sparse checkout is enabled and it is not a dry run.
+///
+/// `target` is the commit checked out for the `post-checkout` hook, which is
+/// `None` if only files are checked out.
+fn run_checkout(
+  repo: &git2::Repository,
+  env: Env,
+  options: Option<CheckoutOptions>,
+  target: Option<git2::Oid>,
+  checkout: impl FnOnce(&mut git2::build::CheckoutBuilder<'static>) -> std::result::Result<(), git2::Error>,
+) -> crate::Result<()> {
+  let dry_run = options.as_ref().and_then(|x| x.dry_run).unwrap_or(false);
+  let run_hooks = !dry_run && options.as_ref().and_then(|x| x.run_hooks).unwrap_or(false);
+  let target = match options.as_ref().and_then(|x| x.path.as_ref()) {
+    Some(_) => None,
+    None => target,
+  };
+  let prev_head = head_oid(repo);
+  let records: CheckoutRecords = Default::default();
+  let mut builder = checkout_builder(env, options, git2::CheckoutNotificationType::CONFLICT, records.clone())?;
+  let result = checkout(&mut builder);
+  drop(builder);
+  records.finish(result)?;
+  if !dry_run {
+    apply_sparse_checkout(repo)?;
+  }
+  if run_hooks {
+    let args = [
+      prev_head.to_string(),
+      target.unwrap_or(prev_head).to_string(),
+      if target.is_some() { "1" } else { "0" }.to_string(),
+    ];
+    Hooks::new(repo)?.run_ignored("post-checkout", &args, None);
+  }
+  Ok(())
+}
+
 #[napi]
 impl Repository {
   #[napi]
@@ -206,10 +486,11 @@ impl Repository {
   /// ```
   ///
   /// @param {CheckoutOptions} [options] - Options for checkout.
-  pub fn checkout_head(&self, options: Option<CheckoutOptions>) -> crate::Result<()> {
-    let mut builder = options.map(git2::build::CheckoutBuilder::from);
-    self.inner.checkout_head(builder.as_mut())?;
-    Ok(())
+  pub fn checkout_head(&self, env: Env, options: Option<CheckoutOptions>) -> crate::Result<()> {
+    let target = Some(head_oid(&self.inner));
+    run_checkout(&self.inner, env, options, target, |builder| {
+      self.inner.checkout_head(Some(builder))
+    })
   }
 
   #[napi]
@@ -228,11 +509,16 @@ impl Repository {
   ///
   /// @param {Index} [index] - Index to checkout. If not given, the repository's index will be used.
   /// @param {CheckoutOptions} [options] - Options for checkout.
-  pub fn checkout_index(&self, index: Option<&mut Index>, options: Option<CheckoutOptions>) -> crate::Result<()> {
-    let mut builder = options.map(git2::build::CheckoutBuilder::from);
+  pub fn checkout_index(
+    &self,
+    env: Env,
+    index: Option<&mut Index>,
+    options: Option<CheckoutOptions>,
+  ) -> crate::Result<()> {
     let git_index = index.map(|x| &mut x.inner);
-    self.inner.checkout_index(git_index, builder.as_mut())?;
-    Ok(())
+    run_checkout(&self.inner, env, options, None, |builder| {
+      self.inner.checkout_index(git_index, Some(builder))
+    })
   }
 
   #[napi]
@@ -249,9 +535,71 @@ impl Repository {
   ///
   /// @param {GitObject} treeish - Git object which tree pointed.
   /// @param {CheckoutOptions} [options] - Options for checkout.
-  pub fn checkout_tree(&self, treeish: &GitObject, options: Option<CheckoutOptions>) -> crate::Result<()> {
-    let mut builder = options.map(git2::build::CheckoutBuilder::from);
-    self.inner.checkout_tree(&treeish.inner, builder.as_mut())?;
-    Ok(())
+  pub fn checkout_tree(&self, env: Env, treeish: &GitObject, options: Option<CheckoutOptions>) -> crate::Result<()> {
+    // `HEAD` doesn't move, so the hook is run as a file checkout.
+    run_checkout(&self.inner, env, options, None, |builder| {
+      self.inner.checkout_tree(&treeish.inner, Some(builder))
+    })
+  }
+
+  #[napi]
+  /// Check which files would be touched by checking out the tree pointed at
+  /// by the treeish, without making any actual changes.
+  ///
+  /// Only files with the `Conflict`, `Dirty` and `Updated` types are listed.
+  /// Conflicts do not throw an error, but are listed with the `Conflict`
+  /// type instead.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   checkoutDryRun(
+  ///     treeish?: GitObject | undefined | null,
+  ///     options?: CheckoutOptions | undefined | null,
+  ///   ): CheckoutNotify[];
+  /// }
+  /// ```
+  ///
+  /// @param {GitObject} [treeish] - Git object which tree pointed. If not given, `HEAD` will be used.
+  /// @param {CheckoutOptions} [options] - Options for checkout. `dryRun` is always set.
+  /// @returns Notifications for each file which would be touched by the checkout.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// const branch = repo.getBranch('main', 'Local');
+  /// const result = repo.checkoutDryRun(branch.reference().peelToCommit().asObject());
+  /// for (const conflict of result.filter(x => x.type === 'Conflict')) {
+  ///   console.log(`${conflict.path} prevents switching branches`);
+  /// }
+  /// ```
+  pub fn checkout_dry_run(
+    &self,
+    env: Env,
+    treeish: Option<&GitObject>,
+    options: Option<CheckoutOptions>,
+  ) -> crate::Result<Vec<CheckoutNotify>> {
+    let records: CheckoutRecords = Default::default();
+    let record_on = git2::CheckoutNotificationType::CONFLICT
+      | git2::CheckoutNotificationType::DIRTY
+      | git2::CheckoutNotificationType::UPDATED;
+    let mut builder = checkout_builder(env, options, record_on, records.clone())?;
+    builder.dry_run();
+    let result = match treeish {
+      Some(treeish) => self.inner.checkout_tree(&treeish.inner, Some(&mut builder)),
+      None => self.inner.checkout_head(Some(&mut builder)),
+    };
+    drop(builder);
+    // Conflicts are listed instead of being thrown.
+    let result = match result {
+      Err(e) if e.code() == git2::ErrorCode::Conflict => Ok(()),
+      result => result,
+    };
+    records.finish(result)?;
+    let notifications = records.notifications.take();
+    Ok(notifications)
   }
 }
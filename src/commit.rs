@@ -1,6 +1,9 @@
+use crate::diff::{Diff, DiffOptions};
+use crate::hooks::{run_commit_msg_hooks, run_post_commit_hooks, run_pre_commit_hook};
 use crate::object::{GitObject, ObjectInner};
 use crate::repository::Repository;
 use crate::signature::{Signature, SignaturePayload};
+use crate::signing::{sign_payload, SignerOptions};
 use crate::tree::{Tree, TreeInner};
 use chrono::{DateTime, Utc};
 use napi::bindgen_prelude::*;
@@ -29,6 +32,19 @@ pub struct CommitOptions {
   ///
   /// If not provided, the default signature field (gpgsig) will be used.
   pub signature_field: Option<String>,
+  /// Signer to create a signed commit.
+  ///
+  /// Ignored if `signature` is provided.
+  pub signer: Option<SignerOptions>,
+  /// Run the `pre-commit`, `prepare-commit-msg`, `commit-msg` and `post-commit` hooks.
+  ///
+  /// The commit is aborted if `pre-commit`, `prepare-commit-msg` or `commit-msg`
+  /// exits with non-zero status. The message may be edited by the hooks, and
+  /// an edited message is cleaned up by `commit.cleanup` config like `git commit -m`.
+  ///
+  /// If the tree is written from the index, the index is written before
+  /// `pre-commit` runs and changes of the hook to the index are committed.
+  pub run_hooks: Option<bool>,
 }
 
 #[napi(object)]
@@ -51,6 +67,41 @@ pub struct AmendOptions {
   /// E.g. "UTF-8".
   /// If NULL, no encoding header is written and UTF-8 is assumed.
   pub message_encoding: Option<String>,
+  /// Signer to create a signed commit.
+  ///
+  /// Only available for commits looked up from a repository.
+  pub signer: Option<SignerOptions>,
+  /// Run the `pre-commit`, `prepare-commit-msg`, `commit-msg`, `post-commit`
+  /// and `post-rewrite` hooks.
+  ///
+  /// Amending is aborted if `pre-commit`, `prepare-commit-msg` or `commit-msg`
+  /// exits with non-zero status. Only available for commits looked up from a repository.
+  ///
+  /// If the tree to amend with is the tree of the index, the index is written before
+  /// `pre-commit` runs and changes of the hook to the index are committed.
+  pub run_hooks: Option<bool>,
+}
+
+#[napi(object)]
+pub struct CommitHeaderField {
+  /// Name of the header field. (e.g. "change-id")
+  ///
+  /// It can't be empty or contain a space or a newline.
+  pub name: String,
+  /// Value of the header field.
+  ///
+  /// Multi-line values will be written as continuation lines.
+  pub value: String,
+}
+
+#[napi(object)]
+pub struct CreateCommitBufferOptions {
+  /// The encoding for the message in the commit, represented with a standard encoding name.
+  /// E.g. "UTF-8".
+  /// If not provided, no encoding header is written and UTF-8 is assumed.
+  pub message_encoding: Option<String>,
+  /// Extra header fields to write after the standard headers of the commit.
+  pub headers: Option<Vec<CommitHeaderField>>,
 }
 
 pub(crate) enum CommitInner {
@@ -69,6 +120,102 @@ impl Deref for CommitInner {
   }
 }
 
+impl Commit {
+  /// Wrap a commit related to this commit, keeping it bound to the
+  /// repository if this commit is looked up from a repository.
+  fn related(&self, env: Env, commit: git2::Commit<'static>) -> crate::Result<Commit> {
+    let inner = match &self.inner {
+      CommitInner::Repo(inner) => {
+        let id = commit.id();
+        let shared = inner.clone_owner(env)?.share_with(env, |repo| {
+          repo
+            .inner
+            .find_commit(id)
+            .map_err(crate::Error::from)
+            .map_err(|e| e.into())
+        })?;
+        CommitInner::Repo(shared)
+      }
+      CommitInner::Owned(_) => CommitInner::Owned(commit),
+    };
+    Ok(Commit { inner })
+  }
+}
+
+/// Insert a header field into the raw commit content, right before the message.
+///
+/// The name can't be empty or contain a space or a newline, which would
+/// corrupt the header.
+fn insert_commit_header(content: &mut String, field: &str, value: &str) -> crate::Result<()> {
+  if field.is_empty() || field.contains([' ', '\n']) {
+    return Err(
+      git2::Error::new(
+        git2::ErrorCode::Invalid,
+        git2::ErrorClass::Object,
+        format!("invalid commit header name '{field}'"),
+      )
+      .into(),
+    );
+  }
+  let pos = content.find("\n\n").map(|x| x + 1).unwrap_or(content.len());
+  let value = value.trim_end_matches('\n').replace('\n', "\n ");
+  content.insert_str(pos, &format!("{field} {value}\n"));
+  Ok(())
+}
+
+/// Update the reference (following symbolic references) to point the commit.
+///
+/// Like libgit2 does when creating commits, an existing reference is only
+/// updated if it still points to `expected` (the first parent, or the amended
+/// commit).
+pub(crate) fn update_ref_to_commit(
+  repo: &git2::Repository,
+  refname: &str,
+  oid: git2::Oid,
+  expected: Option<git2::Oid>,
+  log_message: &str,
+) -> crate::Result<()> {
+  let mut name = refname.to_string();
+  let mut current = None;
+  while let Ok(reference) = repo.find_reference(&name) {
+    match reference.symbolic_target() {
+      Some(target) => name = target.to_string(),
+      None => {
+        current = reference.target();
+        break;
+      }
+    }
+  }
+  match (current, expected) {
+    (None, _) => repo.reference(&name, oid, false, log_message)?,
+    (Some(current), Some(expected)) if current == expected => {
+      repo.reference_matching(&name, oid, true, expected, log_message)?
+    }
+    (Some(_), _) => {
+      return Err(
+        git2::Error::new(
+          git2::ErrorCode::Modified,
+          git2::ErrorClass::Object,
+          format!("failed to update '{name}': current tip is not the expected commit"),
+        )
+        .into(),
+      )
+    }
+  };
+  Ok(())
+}
+
+/// Create a signed commit from the unsigned commit content.
+pub(crate) fn create_signed_commit(
+  repo: &git2::Repository,
+  content: &str,
+  signer: &SignerOptions,
+) -> crate::Result<git2::Oid> {
+  let signature = sign_payload(repo, signer, content)?;
+  let oid = repo.commit_signed(content, &signature, None)?;
+  Ok(oid)
+}
+
 #[napi]
 /// A class to represent a git commit.
 pub struct Commit {
@@ -154,6 +301,45 @@ impl Commit {
     Ok(message)
   }
 
+  #[napi]
+  /// Get the full raw message of a commit, exactly as stored in the commit
+  /// object.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   messageRaw(): string;
+  /// }
+  /// ```
+  ///
+  /// @returns Full raw message of this commit.
+  /// @throws If the message is not valid utf-8.
+  pub fn message_raw(&self) -> crate::Result<String> {
+    let message = std::str::from_utf8(self.inner.message_raw_bytes())?.to_string();
+    Ok(message)
+  }
+
+  #[napi]
+  /// Get the encoding for the message of a commit, as a string representing
+  /// a standard encoding name.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   messageEncoding(): string | null;
+  /// }
+  /// ```
+  ///
+  /// @returns Encoding of the message. Returns `null` if the encoding is not
+  /// specified, in which case UTF-8 is assumed.
+  pub fn message_encoding(&self) -> Option<String> {
+    self.inner.message_encoding().map(|x| x.to_string())
+  }
+
   #[napi]
   /// Get the short "summary" of the git commit message.
   ///
@@ -227,6 +413,24 @@ impl Commit {
     Ok(time)
   }
 
+  #[napi]
+  /// Get the timezone offset of the commit time (i.e. committer time) of a
+  /// commit.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   timeOffset(): number;
+  /// }
+  /// ```
+  ///
+  /// @returns Timezone offset in minutes from UTC.
+  pub fn time_offset(&self) -> i32 {
+    self.inner.time().offset_minutes()
+  }
+
   #[napi]
   /// Get the id of the tree pointed to by this commit.
   ///
@@ -268,6 +472,159 @@ impl Commit {
     })
   }
 
+  #[napi]
+  /// Get the number of parents of a commit.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   parentCount(): number;
+  /// }
+  /// ```
+  ///
+  /// @returns Number of parents of this commit.
+  pub fn parent_count(&self) -> u32 {
+    self.inner.parent_count() as u32
+  }
+
+  #[napi]
+  /// Get the ids of the parents of a commit.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   parentIds(): string[];
+  /// }
+  /// ```
+  ///
+  /// @returns IDs(SHA1) of the parents of this commit.
+  pub fn parent_ids(&self) -> Vec<String> {
+    self.inner.parent_ids().map(|x| x.to_string()).collect()
+  }
+
+  #[napi]
+  /// Get the parents of a commit.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   parents(): Commit[];
+  /// }
+  /// ```
+  ///
+  /// @returns Parent commits of this commit.
+  pub fn parents(&self, env: Env) -> crate::Result<Vec<Commit>> {
+    self.inner.parents().map(|x| self.related(env, x)).collect()
+  }
+
+  #[napi]
+  /// Get the specified parent of a commit.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   parent(n: number): Commit;
+  /// }
+  /// ```
+  ///
+  /// @param {number} n - Position of the parent. (0-based)
+  /// @returns Parent commit at the given position.
+  /// @throws Throws error if the parent does not exist.
+  pub fn parent(&self, env: Env, n: u32) -> crate::Result<Commit> {
+    let parent = self.inner.parent(n as usize)?;
+    self.related(env, parent)
+  }
+
+  #[napi]
+  /// Get the commit object that is the n-th generation ancestor of a commit,
+  /// following only the first parents.
+  ///
+  /// Passing `0` as the generation number returns another instance of the
+  /// same commit.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   nthGenAncestor(n: number): Commit;
+  /// }
+  /// ```
+  ///
+  /// @param {number} n - Requested generation.
+  /// @returns Ancestor commit of the given generation.
+  /// @throws Throws error if the ancestor does not exist.
+  pub fn nth_gen_ancestor(&self, env: Env, n: u32) -> crate::Result<Commit> {
+    let mut commit = self.inner.deref().clone();
+    for _ in 0..n {
+      commit = commit.parent(0)?;
+    }
+    self.related(env, commit)
+  }
+
+  #[napi]
+  /// Create a diff between the tree of the specified parent and the tree of
+  /// a commit.
+  ///
+  /// For a root commit, the diff is created against an empty tree.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   diffToParent(n?: number | null | undefined, options?: DiffOptions | null | undefined): Diff;
+  /// }
+  /// ```
+  ///
+  /// @param {number} [n] - Position of the parent. (0-based) Defaults to `0`.
+  /// @param {DiffOptions} [options] - Options for creating the diff.
+  /// @returns Diff from the parent to this commit.
+  /// @throws Throws error if the parent does not exist, or if the commit is
+  /// not looked up from a repository.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// const commit = repo.getCommit(repo.head().target()!);
+  /// for (const delta of commit.diffToParent().deltas()) {
+  ///   console.log(delta.status(), delta.newFile().path());
+  /// }
+  /// ```
+  pub fn diff_to_parent(&self, env: Env, n: Option<u32>, options: Option<DiffOptions>) -> crate::Result<Diff> {
+    let repo = match &self.inner {
+      CommitInner::Repo(inner) => inner.clone_owner(env)?,
+      CommitInner::Owned(_) => {
+        return Err(git2::Error::from_str("cannot diff a commit which is not looked up from a repository").into())
+      }
+    };
+    let n = n.unwrap_or_default() as usize;
+    let old_tree = match self.inner.parent_count() {
+      0 if n == 0 => None,
+      _ => Some(self.inner.parent(n)?.tree()?),
+    };
+    let new_tree = self.inner.tree()?;
+    let mut opts: git2::DiffOptions = options.map(|x| x.into()).unwrap_or_default();
+    let inner = repo.share_with(env, |repo| {
+      repo
+        .inner
+        .diff_tree_to_tree(old_tree.as_ref(), Some(&new_tree), Some(&mut opts))
+        .map_err(crate::Error::from)
+        .map_err(|e| e.into())
+    })?;
+    Ok(Diff { inner })
+  }
+
   #[napi]
   /// Casts this Commit to be usable as an `GitObject`.
   ///
@@ -288,6 +645,50 @@ impl Commit {
     }
   }
 
+  #[napi]
+  /// Get an arbitrary header field of a commit.
+  ///
+  /// Continuation lines of multi-line fields are joined with newlines.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   headerField(name: string): string | null;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - Name of the header field. (e.g. "encoding", "gpgsig")
+  /// @returns Value of the header field. Returns `null` if the field does not exist.
+  /// @throws If the value is not valid utf-8.
+  pub fn header_field(&self, name: String) -> crate::Result<Option<String>> {
+    match self.inner.header_field_bytes(name) {
+      Ok(buf) => Ok(Some(std::str::from_utf8(&buf)?.to_string())),
+      Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+      Err(e) => Err(e.into()),
+    }
+  }
+
+  #[napi]
+  /// Get the full raw text of the commit header.
+  ///
+  /// @category Commit/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Commit {
+  ///   rawHeader(): string;
+  /// }
+  /// ```
+  ///
+  /// @returns Raw header of this commit, without the message.
+  /// @throws If the header is not valid utf-8.
+  pub fn raw_header(&self) -> crate::Result<String> {
+    let header = std::str::from_utf8(self.inner.raw_header_bytes())?.to_string();
+    Ok(header)
+  }
+
   #[napi]
   /// Amend this existing commit with all non-nullable values
   ///
@@ -307,7 +708,7 @@ impl Commit {
   /// @param {AmendOptions} [options] - Options for amending commit.
   /// @param {Tree} [tree] - Tree to use for amending commit.
   /// @returns ID(SHA1) of amended commit.
-  pub fn amend(&self, options: Option<AmendOptions>, tree: Option<&Tree>) -> crate::Result<String> {
+  pub fn amend(&self, env: Env, options: Option<AmendOptions>, tree: Option<&Tree>) -> crate::Result<String> {
     let opts = options.unwrap_or_default();
     let update_ref = opts.update_ref;
     let author = opts
@@ -318,17 +719,92 @@ impl Commit {
       .committer
       .and_then(|x| Signature::try_from(x).ok())
       .and_then(|x| git2::Signature::try_from(x).ok());
-    let message = opts.message;
+    let mut message = opts.message;
     let message_encoding = opts.message_encoding;
 
-    let oid = self.inner.amend(
-      update_ref.as_deref(),
-      author.as_ref(),
-      committer.as_ref(),
-      message_encoding.as_deref(),
-      message.as_deref(),
-      tree.map(|x| x.inner.deref()),
-    )?;
+    let hooks_repo = match opts.run_hooks.unwrap_or(false) {
+      true => match &self.inner {
+        CommitInner::Repo(inner) => Some(inner.clone_owner(env)?),
+        CommitInner::Owned(_) => {
+          return Err(crate::Error::Hook(
+            "cannot run hooks for a commit which is not looked up from a repository".to_string(),
+          ))
+        }
+      },
+      false => None,
+    };
+    let mut hook_tree = None;
+    if let Some(repo) = &hooks_repo {
+      let tree_id = tree.map(|x| x.inner.id()).unwrap_or_else(|| self.inner.tree_id());
+      let updated = run_pre_commit_hook(&repo.inner, tree_id)?;
+      if updated != tree_id {
+        hook_tree = Some(repo.inner.find_tree(updated)?);
+      }
+      let current = match message {
+        Some(message) => message,
+        None => std::str::from_utf8(self.inner.message_raw_bytes())?.to_string(),
+      };
+      let source = ["commit".to_string(), self.inner.id().to_string()];
+      message = Some(run_commit_msg_hooks(&repo.inner, &current, &source)?);
+    }
+
+    let oid = if let Some(signer) = opts.signer {
+      let repo = match &self.inner {
+        CommitInner::Repo(inner) => inner.clone_owner(env)?,
+        CommitInner::Owned(_) => {
+          return Err(crate::Error::Signing(
+            "cannot sign a commit which is not looked up from a repository".to_string(),
+          ))
+        }
+      };
+      let author = author.unwrap_or_else(|| self.inner.author().to_owned());
+      let committer = committer.unwrap_or_else(|| self.inner.committer().to_owned());
+      let message = match message {
+        Some(message) => message,
+        None => std::str::from_utf8(self.inner.message_raw_bytes())?.to_string(),
+      };
+      let tree = match (&hook_tree, tree) {
+        (Some(tree), _) => tree.clone(),
+        (None, Some(tree)) => tree.inner.deref().clone(),
+        (None, None) => self.inner.tree()?,
+      };
+      let parents = self.inner.parents().collect::<Vec<_>>();
+      let buf = repo.inner.commit_create_buffer(
+        &author,
+        &committer,
+        &message,
+        &tree,
+        &parents.iter().collect::<Vec<_>>(),
+      )?;
+      let mut content = std::str::from_utf8(&buf)?.to_string();
+      if let Some(encoding) = message_encoding.as_deref().or(self.inner.message_encoding()) {
+        insert_commit_header(&mut content, "encoding", encoding)?;
+      }
+      let oid = create_signed_commit(&repo.inner, &content, &signer)?;
+      if let Some(update_ref) = update_ref {
+        let summary = message.lines().next().unwrap_or_default();
+        update_ref_to_commit(
+          &repo.inner,
+          &update_ref,
+          oid,
+          Some(self.inner.id()),
+          &format!("commit (amend): {summary}"),
+        )?;
+      }
+      oid
+    } else {
+      self.inner.amend(
+        update_ref.as_deref(),
+        author.as_ref(),
+        committer.as_ref(),
+        message_encoding.as_deref(),
+        message.as_deref(),
+        hook_tree.as_ref().or(tree.map(|x| x.inner.deref())),
+      )?
+    };
+    if let Some(repo) = &hooks_repo {
+      run_post_commit_hooks(&repo.inner, Some((self.inner.id(), oid)));
+    }
     Ok(oid.to_string())
   }
 }
@@ -403,7 +879,7 @@ impl Repository {
   ///
   /// @returns ID(SHA1) of created commit.
   pub fn commit(&self, tree: &Tree, message: String, options: Option<CommitOptions>) -> crate::Result<String> {
-    let (update_ref, author, committer, parents, signature, signature_field) = match options {
+    let (update_ref, author, committer, parents, signature, signature_field, signer, run_hooks) = match options {
       Some(opts) => {
         let update_ref = opts.update_ref;
         let author = opts.author.and_then(|x| Signature::try_from(x).ok());
@@ -420,9 +896,35 @@ impl Repository {
         };
         let signature = opts.signature;
         let signature_field = opts.signature_field;
-        (update_ref, author, committer, parents, signature, signature_field)
+        let signer = opts.signer;
+        let run_hooks = opts.run_hooks.unwrap_or(false);
+        (
+          update_ref,
+          author,
+          committer,
+          parents,
+          signature,
+          signature_field,
+          signer,
+          run_hooks,
+        )
       }
-      None => (None, None, None, None, None, None),
+      None => (None, None, None, None, None, None, None, false),
+    };
+    let mut hook_tree = None;
+    let message = match run_hooks {
+      true => {
+        let updated = run_pre_commit_hook(&self.inner, tree.inner.id())?;
+        if updated != tree.inner.id() {
+          hook_tree = Some(self.inner.find_tree(updated)?);
+        }
+        run_commit_msg_hooks(&self.inner, &message, &["message".to_string()])?
+      }
+      false => message,
+    };
+    let commit_tree: &git2::Tree = match &hook_tree {
+      Some(tree) => tree,
+      None => &tree.inner,
     };
     let author = author
       .and_then(|x| git2::Signature::try_from(x).ok())
@@ -433,31 +935,163 @@ impl Repository {
       .or_else(|| self.inner.signature().ok())
       .ok_or(crate::Error::SignatureNotFound)?;
 
-    let oid = if let Some(signature_str) = signature {
+    let parents = parents.unwrap_or_default();
+    let create_commit_content = || -> crate::Result<String> {
       let commit_content = self.inner.commit_create_buffer(
         &author,
         &committer,
         &message,
-        &tree.inner,
-        &parents.unwrap_or_default().iter().collect::<Vec<_>>(),
+        commit_tree,
+        &parents.iter().collect::<Vec<_>>(),
       )?;
+      Ok(std::str::from_utf8(&commit_content)?.to_string())
+    };
+    let signed = match (signature, &signer) {
+      (Some(signature_str), _) => Some((create_commit_content()?, signature_str)),
+      (None, Some(signer)) => {
+        let commit_content_str = create_commit_content()?;
+        let signature_str = sign_payload(&self.inner, signer, &commit_content_str)?;
+        Some((commit_content_str, signature_str))
+      }
+      (None, None) => None,
+    };
 
-      let commit_content_str = std::str::from_utf8(&commit_content)?.to_string();
-
-      self
+    let oid = if let Some((commit_content_str, signature_str)) = signed {
+      let oid = self
         .inner
-        .commit_signed(&commit_content_str, &signature_str, signature_field.as_deref())?
+        .commit_signed(&commit_content_str, &signature_str, signature_field.as_deref())?;
+      if let Some(update_ref) = update_ref {
+        let summary = message.lines().next().unwrap_or_default();
+        let log_message = match parents.is_empty() {
+          true => format!("commit (initial): {summary}"),
+          false => format!("commit: {summary}"),
+        };
+        let expected = parents.first().map(|x| x.id());
+        update_ref_to_commit(&self.inner, &update_ref, oid, expected, &log_message)?;
+      }
+      oid
     } else {
       self.inner.commit(
         update_ref.as_deref(),
         &author,
         &committer,
         &message,
-        &tree.inner,
-        &parents.unwrap_or_default().iter().collect::<Vec<_>>(),
+        commit_tree,
+        &parents.iter().collect::<Vec<_>>(),
       )?
     };
+    if run_hooks {
+      run_post_commit_hooks(&self.inner, None);
+    }
+
+    Ok(oid.to_string())
+  }
+
+  #[napi]
+  /// Create a commit object and return its raw content, without writing it
+  /// to the object database.
+  ///
+  /// The returned buffer can be signed out-of-process and written with
+  /// `commitSigned`.
+  ///
+  /// @category Repository/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   createCommitBuffer(
+  ///     tree: Tree,
+  ///     parents: string[],
+  ///     author: SignaturePayload,
+  ///     committer: SignaturePayload,
+  ///     message: string,
+  ///     options?: CreateCommitBufferOptions | null | undefined,
+  ///   ): string;
+  /// }
+  /// ```
+  ///
+  /// @param {Tree} tree - Tree of the commit.
+  /// @param {string[]} parents - IDs of the parent commits.
+  /// @param {SignaturePayload} author - Signature for author.
+  /// @param {SignaturePayload} committer - Signature for committer.
+  /// @param {string} message - Full message for the commit.
+  /// @param {CreateCommitBufferOptions} [options] - Options for creating the commit buffer.
+  /// @returns Raw content of the commit object.
+  /// @throws If a header name is empty or contains a space or a newline.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// const signature = { name: 'Seokju Na', email: 'seokju.me@toss.im' };
+  /// const buffer = repo.createCommitBuffer(
+  ///   repo.head().peelToTree(),
+  ///   [repo.head().target()!],
+  ///   signature,
+  ///   signature,
+  ///   'commit message',
+  ///   { headers: [{ name: 'change-id', value: 'I1234' }] },
+  /// );
+  /// const oid = repo.commitSigned(buffer, await signExternally(buffer));
+  /// ```
+  pub fn create_commit_buffer(
+    &self,
+    tree: &Tree,
+    parents: Vec<String>,
+    author: SignaturePayload,
+    committer: SignaturePayload,
+    message: String,
+    options: Option<CreateCommitBufferOptions>,
+  ) -> crate::Result<String> {
+    let author = git2::Signature::try_from(Signature::try_from(author)?)?;
+    let committer = git2::Signature::try_from(Signature::try_from(committer)?)?;
+    let parents = parents
+      .iter()
+      .map(|x| self.inner.find_commit_by_prefix(x))
+      .collect::<std::result::Result<Vec<_>, _>>()?;
+    let buf = self.inner.commit_create_buffer(
+      &author,
+      &committer,
+      &message,
+      &tree.inner,
+      &parents.iter().collect::<Vec<_>>(),
+    )?;
+    let mut content = std::str::from_utf8(&buf)?.to_string();
+    if let Some(opts) = options {
+      if let Some(encoding) = opts.message_encoding {
+        insert_commit_header(&mut content, "encoding", &encoding)?;
+      }
+      for header in opts.headers.unwrap_or_default() {
+        insert_commit_header(&mut content, &header.name, &header.value)?;
+      }
+    }
+    Ok(content)
+  }
 
+  #[napi]
+  /// Create a commit object from the given buffer and signature.
+  ///
+  /// Given the unsigned commit object's contents, its signature and the
+  /// header field in which to store the signature, attach the signature to
+  /// the commit and write it into the object database. No reference is
+  /// updated.
+  ///
+  /// @category Repository/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   commitSigned(buffer: string, signature: string, field?: string | null | undefined): string;
+  /// }
+  /// ```
+  ///
+  /// @param {string} buffer - Raw content of the unsigned commit.
+  /// @param {string} signature - Signature to attach to the commit.
+  /// @param {string} [field] - Header field for the signature. Defaults to `gpgsig`.
+  /// @returns ID(SHA1) of created commit.
+  pub fn commit_signed(&self, buffer: String, signature: String, field: Option<String>) -> crate::Result<String> {
+    let oid = self.inner.commit_signed(&buffer, &signature, field.as_deref())?;
     Ok(oid.to_string())
   }
 }
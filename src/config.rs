@@ -1,8 +1,11 @@
 use crate::repository::Repository;
-use crate::util::path_to_string;
+use crate::util::{path_to_string, wildmatch};
 use napi::bindgen_prelude::*;
+use napi::Either;
 use napi_derive::napi;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+pub type ConfigValue = Either<String, Either<i64, bool>>;
 
 #[napi(string_enum)]
 /// - `ProgramData` : System-wide on Windows, for compatibility with portable git.
@@ -39,6 +42,21 @@ impl From<git2::ConfigLevel> for ConfigLevel {
   }
 }
 
+impl From<ConfigLevel> for git2::ConfigLevel {
+  fn from(value: ConfigLevel) -> Self {
+    match value {
+      ConfigLevel::ProgramData => Self::ProgramData,
+      ConfigLevel::System => Self::System,
+      ConfigLevel::XDG => Self::XDG,
+      ConfigLevel::Global => Self::Global,
+      ConfigLevel::Local => Self::Local,
+      ConfigLevel::Worktree => Self::Worktree,
+      ConfigLevel::App => Self::App,
+      ConfigLevel::Highest => Self::Highest,
+    }
+  }
+}
+
 #[napi(object)]
 pub struct ConfigEntry {
   /// The name of this entry.
@@ -77,6 +95,149 @@ impl TryFrom<&git2::ConfigEntry<'_>> for ConfigEntry {
   }
 }
 
+#[napi(string_enum)]
+#[derive(Copy, Clone)]
+/// Type to interpret values of config variables.
+///
+/// - `String` : Value as is.
+/// - `Bool` : Boolean value. (e.g. `true`, `yes`, `on`, `1`)
+/// - `Int` : Integer value, with suffixes like k, M, or G.
+/// - `Path` : Path value, with a leading `~/` expanded to the home directory.
+pub enum ConfigValueType {
+  String,
+  Bool,
+  Int,
+  Path,
+}
+
+#[napi(object)]
+/// A color value of a config variable. (e.g. `color.diff.new`)
+pub struct ConfigColor {
+  /// Foreground color. (e.g. `red`, `brightblue`, `#ff0000`, `208`)
+  pub foreground: Option<String>,
+  /// Background color.
+  pub background: Option<String>,
+  /// Attributes of the color. (e.g. `bold`, `ul`, `nobold`)
+  pub attributes: Vec<String>,
+}
+
+const COLOR_NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+const COLOR_ATTRIBUTES: [&str; 7] = ["bold", "dim", "ul", "blink", "reverse", "italic", "strike"];
+
+fn is_color(word: &str) -> bool {
+  let name = word.strip_prefix("bright").unwrap_or(word);
+  if matches!(word, "normal" | "default") || COLOR_NAMES.contains(&name) {
+    return true;
+  }
+  if let Some(hex) = word.strip_prefix('#') {
+    return matches!(hex.len(), 3 | 6) && hex.chars().all(|x| x.is_ascii_hexdigit());
+  }
+  word.parse::<u8>().is_ok() || word == "-1"
+}
+
+fn is_color_attribute(word: &str) -> bool {
+  let attribute = word
+    .strip_prefix("no-")
+    .or_else(|| word.strip_prefix("no"))
+    .unwrap_or(word);
+  COLOR_ATTRIBUTES.contains(&attribute) || word == "reset"
+}
+
+/// Parse a color value in the same syntax as git (e.g. `bold red blue`).
+fn parse_color(value: &str) -> Option<ConfigColor> {
+  let mut color = ConfigColor {
+    foreground: None,
+    background: None,
+    attributes: vec![],
+  };
+  for word in value.split_whitespace() {
+    let word = word.to_lowercase();
+    if is_color(&word) {
+      match (&color.foreground, &color.background) {
+        (None, _) => color.foreground = Some(word),
+        (Some(_), None) => color.background = Some(word),
+        (Some(_), Some(_)) => return None,
+      }
+    } else if is_color_attribute(&word) {
+      color.attributes.push(word);
+    } else {
+      return None;
+    }
+  }
+  Some(color)
+}
+
+fn duration_unit_seconds(unit: &str) -> Option<i64> {
+  let unit = unit.strip_suffix('s').filter(|x| !x.is_empty()).unwrap_or(unit);
+  match unit {
+    "second" | "sec" => Some(1),
+    "minute" | "min" => Some(60),
+    "hour" => Some(60 * 60),
+    "day" => Some(24 * 60 * 60),
+    "week" => Some(7 * 24 * 60 * 60),
+    "month" => Some(30 * 24 * 60 * 60),
+    "year" => Some(365 * 24 * 60 * 60),
+    _ => None,
+  }
+}
+
+/// Parse a relative duration (e.g. `2.weeks.ago`, `90 days`) into seconds.
+///
+/// Returns `Some(None)` for `never`, which means no expiration.
+fn parse_duration(value: &str) -> Option<Option<i64>> {
+  let value = value.trim().to_lowercase();
+  match value.as_str() {
+    "never" | "false" => return Some(None),
+    "now" | "all" => return Some(Some(0)),
+    _ => {}
+  }
+  if let Ok(seconds) = value.parse::<i64>() {
+    return Some(Some(seconds));
+  }
+  let words = value
+    .split(|x: char| x == '.' || x.is_whitespace())
+    .filter(|x| !x.is_empty())
+    .collect::<Vec<_>>();
+  let words = match words.split_last() {
+    Some((&"ago", rest)) => rest,
+    _ => &words[..],
+  };
+  if words.is_empty() || words.len() % 2 != 0 {
+    return None;
+  }
+  let mut seconds = 0i64;
+  for pair in words.chunks(2) {
+    let amount = pair[0].parse::<i64>().ok()?;
+    let unit = duration_unit_seconds(pair[1])?;
+    seconds = seconds.checked_add(amount.checked_mul(unit)?)?;
+  }
+  Some(Some(seconds))
+}
+
+fn home_dir() -> Option<PathBuf> {
+  std::env::var_os("HOME")
+    .or_else(|| std::env::var_os("USERPROFILE"))
+    .map(PathBuf::from)
+}
+
+fn expand_home(value: &str) -> String {
+  match (value.strip_prefix("~/"), home_dir()) {
+    (Some(rest), Some(home)) => path_to_string(&home.join(rest)),
+    _ => value.to_string(),
+  }
+}
+
+fn parse_config_value(value: Option<&str>, value_type: ConfigValueType) -> crate::Result<ConfigValue> {
+  let value = match (value, value_type) {
+    (None, ConfigValueType::Bool) => Either::B(Either::B(true)),
+    (value, ConfigValueType::String) => Either::A(value.unwrap_or_default().to_string()),
+    (value, ConfigValueType::Path) => Either::A(expand_home(value.unwrap_or_default())),
+    (value, ConfigValueType::Bool) => Either::B(Either::B(git2::Config::parse_bool(value.unwrap_or_default())?)),
+    (value, ConfigValueType::Int) => Either::B(Either::A(git2::Config::parse_i64(value.unwrap_or_default())?)),
+  };
+  Ok(value)
+}
+
 #[napi(iterator)]
 /// An iterator over the `ConfigEntry` values of a config.
 pub struct ConfigEntries {
@@ -381,6 +542,93 @@ impl Config {
     Ok(path_to_string(&value))
   }
 
+  #[napi]
+  /// Get the value of a path config variable.
+  ///
+  /// A leading '~' will be expanded to the global search path (which
+  /// defaults to the user's home directory).
+  ///
+  /// @category Config/Methods
+  /// @signature
+  /// ```ts
+  /// class Config {
+  ///   findPath(name: string): string | null;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of config entry.
+  /// @returns The value of a path config variable. Returns `null` if the
+  /// variable does not exist.
+  pub fn find_path(&self, name: String) -> Option<String> {
+    self.get_path(name).ok()
+  }
+
+  #[napi]
+  /// Get the value of a color config variable.
+  ///
+  /// The value is parsed in the same syntax as git. (e.g. `bold red blue`)
+  ///
+  /// @category Config/Methods
+  /// @signature
+  /// ```ts
+  /// class Config {
+  ///   getColor(name: string): ConfigColor;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of config entry.
+  /// @returns The value of a color config variable.
+  /// @throws Throws error if the variable does not exist or is not a valid color.
+  pub fn get_color(&self, name: String) -> crate::Result<ConfigColor> {
+    let value = self.inner.get_string(&name)?;
+    let color =
+      parse_color(&value).ok_or_else(|| git2::Error::from_str(&format!("failed to parse '{value}' as a color")))?;
+    Ok(color)
+  }
+
+  #[napi]
+  /// Find the value of a color config variable.
+  ///
+  /// @category Config/Methods
+  /// @signature
+  /// ```ts
+  /// class Config {
+  ///   findColor(name: string): ConfigColor | null;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of config entry.
+  /// @returns The value of a color config variable. Returns `null` if the
+  /// variable does not exist or is not a valid color.
+  pub fn find_color(&self, name: String) -> Option<ConfigColor> {
+    self.get_color(name).ok()
+  }
+
+  #[napi]
+  /// Get the value of a duration config variable, such as `gc.pruneExpire`.
+  ///
+  /// Relative durations like `2.weeks.ago` or `90 days` and plain numbers of
+  /// seconds are supported. Months are counted as 30 days and years as 365
+  /// days.
+  ///
+  /// @category Config/Methods
+  /// @signature
+  /// ```ts
+  /// class Config {
+  ///   getDuration(name: string): number | null;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of config entry.
+  /// @returns The duration in seconds. Returns `null` if the value is `never`.
+  /// @throws Throws error if the variable does not exist or is not a valid duration.
+  pub fn get_duration(&self, name: String) -> crate::Result<Option<i64>> {
+    let value = self.inner.get_string(&name)?;
+    let duration = parse_duration(&value)
+      .ok_or_else(|| git2::Error::from_str(&format!("failed to parse '{value}' as a duration")))?;
+    Ok(duration)
+  }
+
   #[napi]
   /// Get the entry for a config variable.
   ///
@@ -492,6 +740,57 @@ impl Config {
     Ok(ConfigEntries { inner })
   }
 
+  #[napi(ts_return_type = "Array<string | number | boolean>")]
+  /// Get the values of a multivar, interpreted as the given type.
+  ///
+  /// @category Config/Methods
+  /// @signature
+  /// ```ts
+  /// class Config {
+  ///   multivarValues(
+  ///     name: string,
+  ///     regexp?: string | null | undefined,
+  ///     valueType?: ConfigValueType | null | undefined,
+  ///   ): Array<string | number | boolean>;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of config entry.
+  /// @param {string} [regexp] - If `regexp` is provided, only values which match the pattern
+  /// will be returned.
+  /// @param {ConfigValueType} [valueType] - Type to interpret the values. Defaults to `String`.
+  /// @returns Values of the multivar, in the order of their defined level.
+  /// @throws Throws error if a value cannot be interpreted as the given type.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// const config = repo.config();
+  /// const refspecs = config.multivarValues('remote.origin.fetch');
+  /// const pruneTags = config.multivarValues('remote.origin.prunetags', null, 'Bool');
+  /// ```
+  pub fn multivar_values(
+    &self,
+    name: String,
+    regexp: Option<String>,
+    value_type: Option<ConfigValueType>,
+  ) -> crate::Result<Vec<ConfigValue>> {
+    let value_type = value_type.unwrap_or(ConfigValueType::String);
+    let mut values = vec![];
+    let mut entries = self.inner.multivar(&name, regexp.as_deref())?;
+    while let Some(entry) = entries.next() {
+      let entry = entry?;
+      let value = match entry.has_value() {
+        true => Some(std::str::from_utf8(entry.value_bytes())?),
+        false => None,
+      };
+      values.push(parse_config_value(value, value_type)?);
+    }
+    Ok(values)
+  }
+
   #[napi]
   /// Set the value of a boolean config variable in the config file with the
   /// highest level (usually the local one).
@@ -587,6 +886,112 @@ impl Config {
     self.inner.set_str(&name, &value)?;
     Ok(())
   }
+
+  #[napi]
+  /// Add an on-disk config file instance to an existing config.
+  ///
+  /// The on-disk file pointed at by `path` will be opened and parsed; it's
+  /// expected to be a native Git config file following the default Git
+  /// config syntax (see man git-config).
+  ///
+  /// Further queries on this config object will access each of the config
+  /// file instances in order (instances with a higher priority level will
+  /// be accessed first).
+  ///
+  /// @category Config/Methods
+  /// @signature
+  /// ```ts
+  /// class Config {
+  ///   addFile(path: string, level: ConfigLevel, force?: boolean | null | undefined): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string} path - Path to config file.
+  /// @param {ConfigLevel} level - The priority level of the config file.
+  /// @param {boolean} [force] - Replace the config file which already exists at the same level.
+  /// Defaults to `false`.
+  pub fn add_file(&mut self, path: String, level: ConfigLevel, force: Option<bool>) -> crate::Result<()> {
+    self
+      .inner
+      .add_file(Path::new(&path), level.into(), force.unwrap_or_default())?;
+    Ok(())
+  }
+
+  #[napi]
+  /// Build a single-level focused config object from a multi-level one.
+  ///
+  /// The returned config object can be used to perform get/set/delete
+  /// operations on a single specific level. This is useful to write values
+  /// to a chosen file, e.g. repository local config without touching the
+  /// global config.
+  ///
+  /// @category Config/Methods
+  /// @signature
+  /// ```ts
+  /// class Config {
+  ///   openLevel(level: ConfigLevel): Config;
+  /// }
+  /// ```
+  ///
+  /// @param {ConfigLevel} level - The level of config file to open.
+  /// @returns Config instance which only contains the given level.
+  /// @throws Throws error if there is no config file at the given level.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// const local = repo.config().openLevel('Local');
+  /// local.setString('user.email', 'seokju.me@toss.im');
+  /// ```
+  pub fn open_level(&self, level: ConfigLevel) -> crate::Result<Config> {
+    let inner = self.inner.open_level(level.into())?;
+    Ok(Config { inner })
+  }
+
+  #[napi]
+  /// Open the global/XDG configuration file according to git's rules.
+  ///
+  /// Git allows you to store your global configuration at `$HOME/.gitconfig`
+  /// or `$XDG_CONFIG_HOME/git/config`. For backwards compatibility, the XDG
+  /// file shouldn't be used unless the user has created it explicitly. With
+  /// this function you'll open the correct one to write to.
+  ///
+  /// @category Config/Methods
+  /// @signature
+  /// ```ts
+  /// class Config {
+  ///   openGlobal(): Config;
+  /// }
+  /// ```
+  ///
+  /// @returns Config instance which only contains the global configuration file.
+  pub fn open_global(&mut self) -> crate::Result<Config> {
+    let inner = self.inner.open_global()?;
+    Ok(Config { inner })
+  }
+
+  #[napi]
+  /// Create a snapshot of the current state of a configuration, which allows
+  /// you to look into a consistent view of the configuration for looking up
+  /// complex values (e.g. a remote, submodule).
+  ///
+  /// The snapshot is read-only.
+  ///
+  /// @category Config/Methods
+  /// @signature
+  /// ```ts
+  /// class Config {
+  ///   snapshot(): Config;
+  /// }
+  /// ```
+  ///
+  /// @returns Read-only snapshot of this config.
+  pub fn snapshot(&mut self) -> crate::Result<Config> {
+    let inner = self.inner.snapshot()?;
+    Ok(Config { inner })
+  }
 }
 
 #[napi]
@@ -605,6 +1010,51 @@ pub fn open_config(path: String) -> crate::Result<Config> {
   Ok(Config { inner })
 }
 
+#[napi(object)]
+/// A config file to add to a config stack.
+pub struct ConfigFile {
+  /// Path to config file.
+  pub path: String,
+  /// The priority level of the config file.
+  pub level: ConfigLevel,
+  /// Replace the config file which already exists at the same level.
+  ///
+  /// Defaults to `false`.
+  pub force: Option<bool>,
+}
+
+#[napi]
+/// Create a new config instance composed of several on-disk files.
+///
+/// Files with a higher priority level will be accessed first.
+///
+/// @category Config
+/// @signature
+/// ```ts
+/// function openConfigStack(files: ConfigFile[]): Config;
+/// ```
+///
+/// @param {ConfigFile[]} files - Config files to compose.
+/// @returns Config instance representing a git configuration key/value store.
+///
+/// @example
+/// ```ts
+/// import { openConfigStack } from 'es-git';
+///
+/// const config = openConfigStack([
+///   { path: '/home/user/.gitconfig', level: 'Global' },
+///   { path: '/path/to/repo/.git/config', level: 'Local' },
+/// ]);
+/// console.log(config.getString('user.email'));
+/// ```
+pub fn open_config_stack(files: Vec<ConfigFile>) -> crate::Result<Config> {
+  let mut inner = git2::Config::new()?;
+  for file in files {
+    inner.add_file(Path::new(&file.path), file.level.into(), file.force.unwrap_or_default())?;
+  }
+  Ok(Config { inner })
+}
+
 #[napi]
 /// Open the global, XDG and system configuration files
 ///
@@ -752,4 +1202,95 @@ impl Repository {
     let inner = self.inner.config()?;
     Ok(Config { inner })
   }
+
+  #[napi]
+  /// Evaluate the condition of an `includeIf` section for this repository.
+  ///
+  /// Supported conditions are `gitdir:`, `gitdir/i:`, `onbranch:` and
+  /// `hasconfig:remote.*.url:`. Conditions of other kinds never match.
+  ///
+  /// Included files with `gitdir:` and `onbranch:` conditions are already
+  /// loaded by `config()`, and their values can be told apart with
+  /// `ConfigEntry.includeDepth`. Files with `hasconfig:remote.*.url:`
+  /// conditions are not loaded, but can be checked with this method.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   matchesIncludeIf(condition: string): boolean;
+  /// }
+  /// ```
+  ///
+  /// @param {string} condition - Condition of the section. (e.g. `gitdir:~/work/`)
+  /// @returns Returns `true` if the condition matches.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// repo.matchesIncludeIf('onbranch:feature/');
+  /// repo.matchesIncludeIf('hasconfig:remote.*.url:https://github.com/toss/**');
+  /// ```
+  pub fn matches_include_if(&self, condition: String) -> crate::Result<bool> {
+    let Some((kind, pattern)) = condition.split_once(':') else {
+      return Ok(false);
+    };
+    let matched = match kind {
+      "gitdir" | "gitdir/i" => {
+        let mut pattern = match pattern.strip_prefix("~/") {
+          Some(rest) => match home_dir() {
+            Some(home) => path_to_string(&home.join(rest)),
+            None => return Ok(false),
+          },
+          None => pattern.to_string(),
+        };
+        if cfg!(windows) {
+          pattern = pattern.replace('\\', "/");
+        }
+        if !pattern.starts_with('/') && !Path::new(&pattern).is_absolute() {
+          pattern = format!("**/{pattern}");
+        }
+        if pattern.ends_with('/') {
+          pattern.push_str("**");
+        }
+        let gitdir = self.inner.path();
+        let candidates = [Some(gitdir.to_path_buf()), gitdir.canonicalize().ok()];
+        candidates.iter().flatten().any(|x| {
+          let path = match cfg!(windows) {
+            true => path_to_string(x).replace('\\', "/"),
+            false => path_to_string(x),
+          };
+          wildmatch(&pattern, path.trim_end_matches('/'), kind == "gitdir/i")
+        })
+      }
+      "onbranch" => {
+        let head = self.inner.find_reference("HEAD")?;
+        let branch = head.symbolic_target().and_then(|x| x.strip_prefix("refs/heads/"));
+        let pattern = match pattern.ends_with('/') {
+          true => format!("{pattern}**"),
+          false => pattern.to_string(),
+        };
+        branch.is_some_and(|x| wildmatch(&pattern, x, false))
+      }
+      "hasconfig" => match pattern.strip_prefix("remote.*.url:") {
+        Some(pattern) => {
+          let config = self.inner.config()?;
+          let mut entries = config.entries(Some("remote\\..*\\.url"))?;
+          let mut matched = false;
+          while let Some(entry) = entries.next() {
+            if entry?.value().is_some_and(|x| wildmatch(pattern, x, false)) {
+              matched = true;
+              break;
+            }
+          }
+          matched
+        }
+        None => false,
+      },
+      _ => false,
+    };
+    Ok(matched)
+  }
 }
@@ -1,23 +1,26 @@
-use crate::object::GitObject;
+use crate::object::{GitObject, ObjectInner};
 use crate::repository::Repository;
+use crate::util::wildmatch;
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::collections::HashMap;
 use std::ops::Deref;
 
+/// The second field of the `Repo` and `Object` variants is the id of the
+/// described commit.
 pub(crate) enum DescribeInner {
-  Repo(SharedReference<Repository, git2::Describe<'static>>),
-  Object(SharedReference<GitObject, git2::Describe<'static>>),
+  Repo(SharedReference<Repository, git2::Describe<'static>>, git2::Oid),
+  Object(SharedReference<GitObject, git2::Describe<'static>>, git2::Oid),
+  /// Name of the commit relative to the tag which contains it.
+  Contains(DescribeContains),
 }
 
-impl Deref for DescribeInner {
-  type Target = git2::Describe<'static>;
-
-  fn deref(&self) -> &Self::Target {
-    match self {
-      Self::Repo(repo) => repo.deref(),
-      Self::Object(obj) => obj.deref(),
-    }
-  }
+pub(crate) struct DescribeContains {
+  name: String,
+  tag: Option<String>,
+  distance: Option<u32>,
+  oid: git2::Oid,
+  abbreviated_oid: String,
 }
 
 #[napi]
@@ -42,6 +45,40 @@ pub struct DescribeFormatOptions {
   pub dirty_suffix: Option<String>,
 }
 
+/// Structured result of a `describe` operation.
+#[napi(object)]
+pub struct DescribeDetails {
+  /// Name of the tag or the reference which describes the commit.
+  ///
+  /// `null` if no tag or reference is found, and the abbreviated commit id is
+  /// shown as a fallback.
+  pub tag: Option<String>,
+  /// Number of commits between the commit and the tag.
+  ///
+  /// With the `contains` option, this is the number of commits from the tag
+  /// down to the commit.
+  pub distance: Option<u32>,
+  /// Abbreviated id of the described commit.
+  pub abbreviated_oid: String,
+  /// Whether the working directory is dirty. This is always `false` for
+  /// descriptions of objects or with the `contains` option.
+  pub dirty: bool,
+}
+
+/// Options for the structured result of a `describe` operation.
+#[napi(object)]
+pub struct DescribeDetailsOptions {
+  /// Sets the size of the abbreviated commit id to use.
+  ///
+  /// The value is the lower bound for the length of the abbreviated string,
+  /// and the default is 7.
+  pub abbreviated_size: Option<u32>,
+}
+
+/// Suffix which marks the dirty working directory while parsing the long
+/// format of describe.
+const DIRTY_SUFFIX: &str = "-dirty";
+
 impl From<DescribeFormatOptions> for git2::DescribeFormatOptions {
   fn from(value: DescribeFormatOptions) -> Self {
     let mut opts = git2::DescribeFormatOptions::new();
@@ -74,10 +111,101 @@ impl Describe {
   /// @param {DescribeFormatOptions} [options] - Options for formatting describe.
   /// @returns Formatted string for this describe.
   pub fn format(&self, options: Option<DescribeFormatOptions>) -> crate::Result<String> {
+    let describe = match &self.inner {
+      DescribeInner::Repo(repo, _) => repo.deref(),
+      DescribeInner::Object(obj, _) => obj.deref(),
+      DescribeInner::Contains(contains) => return Ok(contains.name.clone()),
+    };
     let opts = options.map(git2::DescribeFormatOptions::from);
-    let str = self.inner.format(opts.as_ref())?;
+    let str = describe.format(opts.as_ref())?;
     Ok(str)
   }
+
+  #[napi]
+  /// Gets the structured result of this describe.
+  ///
+  /// @category Describe/Methods
+  /// @signature
+  /// ```ts
+  /// class Describe {
+  ///   details(options?: DescribeDetailsOptions | null | undefined): DescribeDetails;
+  /// }
+  /// ```
+  ///
+  /// @param {DescribeDetailsOptions} [options] - Options for the abbreviated commit id.
+  /// @returns Tag name, distance, abbreviated commit id and dirty flag of this describe.
+  ///
+  /// @example
+  /// ```ts
+  /// const { tag, distance, abbreviatedOid, dirty } = repo.describe({ describeTags: true }).details();
+  /// ```
+  pub fn details(&self, options: Option<DescribeDetailsOptions>) -> crate::Result<DescribeDetails> {
+    let abbreviated_size = options.and_then(|x| x.abbreviated_size);
+    let (describe, oid) = match &self.inner {
+      DescribeInner::Contains(contains) => {
+        let oid = contains.oid.to_string();
+        let size = (abbreviated_size.unwrap_or_default() as usize)
+          .max(contains.abbreviated_oid.len())
+          .min(oid.len());
+        return Ok(DescribeDetails {
+          tag: contains.tag.clone(),
+          distance: contains.distance,
+          abbreviated_oid: oid[..size].to_string(),
+          dirty: false,
+        });
+      }
+      DescribeInner::Repo(repo, oid) => (repo.deref(), oid),
+      DescribeInner::Object(obj, oid) => (obj.deref(), oid),
+    };
+    let mut opts = git2::DescribeFormatOptions::new();
+    opts.always_use_long_format(true).dirty_suffix(DIRTY_SUFFIX);
+    if let Some(size) = abbreviated_size {
+      opts.abbreviated_size(size);
+    }
+    let formatted = describe.format(Some(&opts))?;
+    let (formatted, dirty) = match formatted.strip_suffix(DIRTY_SUFFIX) {
+      Some(formatted) => (formatted, true),
+      None => (formatted.as_str(), false),
+    };
+    let details = match parse_long_format(formatted, &oid.to_string()) {
+      Some((tag, distance, abbreviated_oid)) => DescribeDetails {
+        tag: Some(tag.to_string()),
+        distance: Some(distance),
+        abbreviated_oid: abbreviated_oid.to_string(),
+        dirty,
+      },
+      None => fallback_details(formatted, dirty),
+    };
+    Ok(details)
+  }
+}
+
+/// Parse the long format `<tag>-<distance>-g<abbreviated oid>` of describe
+/// into the tag, the distance and the abbreviated oid.
+///
+/// The abbreviated oid must be a prefix of the id of the described commit, so
+/// tags which contain dashes or look like the long format are kept as a
+/// whole. `None` is returned if only the abbreviated oid is shown as a
+/// fallback.
+fn parse_long_format<'a>(formatted: &'a str, oid: &str) -> Option<(&'a str, u32, &'a str)> {
+  let (rest, abbreviated_oid) = formatted.rsplit_once("-g")?;
+  if abbreviated_oid.is_empty() || !oid.starts_with(abbreviated_oid) {
+    return None;
+  }
+  let (tag, distance) = rest.rsplit_once('-')?;
+  if tag.is_empty() || distance.is_empty() || !distance.bytes().all(|x| x.is_ascii_digit()) {
+    return None;
+  }
+  Some((tag, distance.parse().ok()?, abbreviated_oid))
+}
+
+fn fallback_details(abbreviated_oid: &str, dirty: bool) -> DescribeDetails {
+  DescribeDetails {
+    tag: None,
+    distance: None,
+    abbreviated_oid: abbreviated_oid.to_string(),
+    dirty,
+  }
 }
 
 #[napi(object)]
@@ -99,10 +227,232 @@ pub struct DescribeOptions {
   /// back to showing the full id of the commit.
   pub show_commit_oid_as_fallback: Option<bool>,
   pub pattern: Option<String>,
+  /// Describe the commit by the tag which contains it, like the
+  /// `--contains` option to git-describe.
+  ///
+  /// The commit is named relative to the tag like `v1.0~2` or `v1.0~1^2`, and
+  /// lightweight tags are also considered. With `describeAll`, any reference
+  /// is considered. The working directory is not considered to be dirty.
+  pub contains: Option<bool>,
+}
+
+/// Options for naming a commit with `nameRev`.
+#[napi(object)]
+#[derive(Default)]
+pub struct NameRevOptions {
+  /// Only use references matching any of the glob patterns, like the
+  /// `--refs` option to git-name-rev. A pattern matches the full name of the
+  /// reference or its trailing path components (e.g. `v1.*` matches
+  /// `refs/tags/v1.0`).
+  pub refs: Option<Vec<String>>,
+  /// Only use tags to name the commit, like the `--tags` option to
+  /// git-name-rev.
+  pub tags_only: Option<bool>,
+}
+
+/// Weight of the distance to traverse to a parent other than the first
+/// parent, which makes names through merges less preferred.
+const MERGE_TRAVERSAL_WEIGHT: u32 = 65535;
+
+/// Name of a commit relative to a reference, like git-name-rev.
+#[derive(Clone)]
+struct RevName {
+  /// Name of the reference which the name starts from.
+  tip: String,
+  /// Path from the reference to the last merge, like `v1.0~2^2`.
+  path: String,
+  /// Number of first parents from the path.
+  generation: u32,
+  /// Weighted distance from the reference, to compare names.
+  distance: u32,
+  /// Number of commits from the reference.
+  commits: u32,
+  from_tag: bool,
+  /// Whether the reference is an annotated tag, which is named with `^0`.
+  deref: bool,
+  tagger_date: i64,
 }
 
-impl From<DescribeOptions> for git2::DescribeOptions {
-  fn from(value: DescribeOptions) -> Self {
+impl RevName {
+  fn name(&self) -> String {
+    match (self.generation, self.deref) {
+      (0, true) => format!("{}^0", self.path),
+      (0, false) => self.path.clone(),
+      (generation, _) => format!("{}~{generation}", self.path),
+    }
+  }
+
+  /// Prefer tags, older tags and then shorter distances, like git-name-rev.
+  fn is_better_than(&self, other: &RevName) -> bool {
+    if self.from_tag && other.from_tag && self.tagger_date != other.tagger_date {
+      return self.tagger_date < other.tagger_date;
+    }
+    if self.from_tag != other.from_tag {
+      return self.from_tag;
+    }
+    self.distance < other.distance
+  }
+
+  fn parent(&self, index: usize) -> RevName {
+    if index == 0 {
+      return RevName {
+        generation: self.generation + 1,
+        distance: self.distance.saturating_add(1),
+        commits: self.commits + 1,
+        deref: false,
+        ..self.clone()
+      };
+    }
+    let path = match self.generation {
+      0 => format!("{}^{}", self.path, index + 1),
+      generation => format!("{}~{generation}^{}", self.path, index + 1),
+    };
+    RevName {
+      path,
+      generation: 0,
+      distance: self.distance.saturating_add(MERGE_TRAVERSAL_WEIGHT),
+      commits: self.commits + 1,
+      deref: false,
+      ..self.clone()
+    }
+  }
+}
+
+/// Match the reference name or its trailing path components with the pattern.
+fn subpath_matches(refname: &str, pattern: &str) -> bool {
+  let mut path = refname;
+  loop {
+    if wildmatch(pattern, path, false) {
+      return true;
+    }
+    match path.split_once('/') {
+      Some((_, rest)) => path = rest,
+      None => return false,
+    }
+  }
+}
+
+fn short_ref_name(refname: &str, tags_only: bool) -> String {
+  let short = match tags_only {
+    true => refname.strip_prefix("refs/tags/"),
+    false => refname
+      .strip_prefix("refs/heads/")
+      .or_else(|| refname.strip_prefix("refs/")),
+  };
+  short.unwrap_or(refname).to_string()
+}
+
+/// Name the commit relative to the best reference which contains it.
+fn name_commit(
+  repo: &git2::Repository,
+  oid: git2::Oid,
+  refs: Option<&[String]>,
+  tags_only: bool,
+  abbreviate: bool,
+) -> crate::Result<Option<RevName>> {
+  let mut tips = Vec::new();
+  for reference in repo.references()? {
+    let reference = reference?;
+    let (Some(refname), Some(target)) = (reference.name(), reference.target()) else {
+      continue;
+    };
+    let from_tag = refname.starts_with("refs/tags/");
+    if tags_only && !from_tag {
+      continue;
+    }
+    if refs.is_some_and(|x| !x.iter().any(|pattern| subpath_matches(refname, pattern))) {
+      continue;
+    }
+    let Ok(commit) = reference.peel_to_commit() else {
+      continue;
+    };
+    let tag = repo.find_tag(target).ok();
+    let tagger_date = tag
+      .as_ref()
+      .and_then(|x| x.tagger())
+      .map(|x| x.when().seconds())
+      .unwrap_or(commit.time().seconds());
+    let name = short_ref_name(refname, abbreviate && from_tag);
+    let tip = RevName {
+      tip: name.clone(),
+      path: name,
+      generation: 0,
+      distance: 0,
+      commits: 0,
+      from_tag,
+      deref: tag.is_some(),
+      tagger_date,
+    };
+    tips.push((commit.id(), tip));
+  }
+
+  // Commits older than the commit by more than a day can't reach it, allowing
+  // some clock skew like git-name-rev.
+  let cutoff = repo.find_commit(oid)?.time().seconds() - 86400;
+  let mut names: HashMap<git2::Oid, RevName> = HashMap::new();
+  for tip in tips {
+    let mut stack = vec![tip];
+    while let Some((commit_id, name)) = stack.pop() {
+      if names.get(&commit_id).is_some_and(|x| !name.is_better_than(x)) {
+        continue;
+      }
+      let commit = repo.find_commit(commit_id)?;
+      if commit.time().seconds() < cutoff {
+        continue;
+      }
+      let parents = commit.parent_ids().collect::<Vec<_>>();
+      // Push parents in reverse, to name through the first parent first.
+      for (index, parent) in parents.into_iter().enumerate().rev() {
+        stack.push((parent, name.parent(index)));
+      }
+      names.insert(commit_id, name);
+    }
+  }
+  Ok(names.remove(&oid))
+}
+
+/// Describe the commit by the tag which contains it, like
+/// `git describe --contains`.
+fn describe_contains(
+  repo: &git2::Repository,
+  commit: &git2::Commit,
+  options: &DescribeOptions,
+) -> crate::Result<DescribeContains> {
+  let all = options.describe_all.unwrap_or_default();
+  let refs = options.pattern.as_ref().map(|pattern| match all {
+    true => vec![pattern.clone()],
+    false => vec![format!("refs/tags/{pattern}")],
+  });
+  let name = name_commit(repo, commit.id(), refs.as_deref(), !all, !all)?;
+  let abbreviated_oid = commit.as_object().short_id()?.as_str().unwrap_or_default().to_string();
+  match name {
+    Some(name) => Ok(DescribeContains {
+      name: name.name(),
+      tag: Some(name.tip),
+      distance: Some(name.commits),
+      oid: commit.id(),
+      abbreviated_oid,
+    }),
+    None if options.show_commit_oid_as_fallback.unwrap_or_default() => Ok(DescribeContains {
+      name: abbreviated_oid.clone(),
+      tag: None,
+      distance: None,
+      oid: commit.id(),
+      abbreviated_oid,
+    }),
+    None => Err(
+      git2::Error::new(
+        git2::ErrorCode::NotFound,
+        git2::ErrorClass::Describe,
+        format!("cannot describe '{}'", commit.id()),
+      )
+      .into(),
+    ),
+  }
+}
+
+impl From<&DescribeOptions> for git2::DescribeOptions {
+  fn from(value: &DescribeOptions) -> Self {
     let mut opts = git2::DescribeOptions::new();
     if let Some(max) = value.max_candidates_tags {
       opts.max_candidates_tags(max);
@@ -151,7 +501,16 @@ impl Repository {
     env: Env,
     options: Option<DescribeOptions>,
   ) -> crate::Result<Describe> {
-    let opts = options.map_or_else(git2::DescribeOptions::new, git2::DescribeOptions::from);
+    if let Some(options) = options.as_ref().filter(|x| x.contains.unwrap_or_default()) {
+      let commit = self.inner.head()?.peel_to_commit()?;
+      let contains = describe_contains(&self.inner, &commit, options)?;
+      return Ok(Describe {
+        inner: DescribeInner::Contains(contains),
+      });
+    }
+    let opts = options
+      .as_ref()
+      .map_or_else(git2::DescribeOptions::new, git2::DescribeOptions::from);
     let inner = this.share_with(env, |repo| {
       repo
         .inner
@@ -159,10 +518,51 @@ impl Repository {
         .map_err(crate::Error::from)
         .map_err(|e| e.into())
     })?;
+    let oid = self.inner.head()?.peel_to_commit()?.id();
     Ok(Describe {
-      inner: DescribeInner::Repo(inner),
+      inner: DescribeInner::Repo(inner, oid),
     })
   }
+
+  #[napi]
+  /// Names a commit relative to the references which contain it
+  ///
+  /// This behaves like git-name-rev. The name is like `tags/v1.0~2` or
+  /// `main~1^2`, starting from the best reference which contains the commit.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   nameRev(oid: string, options?: NameRevOptions | null | undefined): string | null;
+  /// }
+  /// ```
+  ///
+  /// @param {string} oid - Id of the commit to name.
+  /// @param {NameRevOptions} [options] - Options for naming the commit.
+  /// @returns Name of the commit, or `null` if no reference contains the commit.
+  ///
+  /// @example
+  /// ```ts
+  /// repo.nameRev(oid); // 'main~2'
+  /// repo.nameRev(oid, { tagsOnly: true }); // 'tags/v1.0~1'
+  /// repo.nameRev(oid, { refs: ['v1.*'] }); // 'tags/v1.0~1'
+  /// ```
+  pub fn name_rev(&self, oid: String, options: Option<NameRevOptions>) -> crate::Result<Option<String>> {
+    let options = options.unwrap_or_default();
+    let commit = self
+      .inner
+      .find_object(git2::Oid::from_str(&oid)?, None)?
+      .peel_to_commit()?;
+    let name = name_commit(
+      &self.inner,
+      commit.id(),
+      options.refs.as_deref(),
+      options.tags_only.unwrap_or_default(),
+      false,
+    )?;
+    Ok(name.map(|x| x.name()))
+  }
 }
 
 #[napi]
@@ -188,7 +588,20 @@ impl GitObject {
     env: Env,
     options: Option<DescribeOptions>,
   ) -> crate::Result<Describe> {
-    let opts = options.map_or_else(git2::DescribeOptions::new, git2::DescribeOptions::from);
+    if let Some(options) = options.as_ref().filter(|x| x.contains.unwrap_or_default()) {
+      let ObjectInner::Repo(inner) = &self.inner else {
+        return Err(Error::new(Status::GenericFailure, "Object is not owned by a repository").into());
+      };
+      let commit = self.inner.peel_to_commit()?;
+      let repo = inner.clone_owner(env)?;
+      let contains = describe_contains(&repo.inner, &commit, options)?;
+      return Ok(Describe {
+        inner: DescribeInner::Contains(contains),
+      });
+    }
+    let opts = options
+      .as_ref()
+      .map_or_else(git2::DescribeOptions::new, git2::DescribeOptions::from);
     let inner = this.share_with(env, |obj| {
       obj
         .inner
@@ -196,8 +609,9 @@ impl GitObject {
         .map_err(crate::Error::from)
         .map_err(|e| e.into())
     })?;
+    let oid = self.inner.peel_to_commit()?.id();
     Ok(Describe {
-      inner: DescribeInner::Object(inner),
+      inner: DescribeInner::Object(inner, oid),
     })
   }
 }
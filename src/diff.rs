@@ -595,6 +595,7 @@ impl DiffDelta {
 }
 
 #[napi(string_enum)]
+#[derive(Copy, Clone)]
 /// Valid modes for index and tree entries.
 pub enum FileMode {
   Unreadable,
@@ -6,10 +6,18 @@ pub enum Error {
   Napi(#[from] napi::Error),
   #[error(transparent)]
   Utf8Error(#[from] std::str::Utf8Error),
+  #[error(transparent)]
+  IoError(#[from] std::io::Error),
   #[error("invalid time")]
   InvalidTime,
   #[error("signature not found")]
   SignatureNotFound,
+  #[error("signing error: {0}")]
+  Signing(String),
+  #[error("hook error: {0}")]
+  Hook(String),
+  #[error("protocol error: {0}")]
+  Protocol(String),
 }
 
 impl From<Error> for napi::Error {
@@ -18,8 +26,12 @@ impl From<Error> for napi::Error {
       Error::Git2(e) => napi::Error::new(napi::Status::GenericFailure, format!("libgit2 error: {e}")),
       Error::Napi(e) => e,
       Error::Utf8Error(e) => napi::Error::new(napi::Status::GenericFailure, format!("utf8 error: {e}")),
+      Error::IoError(e) => napi::Error::new(napi::Status::GenericFailure, format!("io error: {e}")),
       Error::InvalidTime => napi::Error::new(napi::Status::GenericFailure, format!("{value}")),
       Error::SignatureNotFound => napi::Error::new(napi::Status::GenericFailure, format!("{value}")),
+      Error::Signing(_) => napi::Error::new(napi::Status::GenericFailure, format!("{value}")),
+      Error::Hook(_) => napi::Error::new(napi::Status::GenericFailure, format!("{value}")),
+      Error::Protocol(_) => napi::Error::new(napi::Status::GenericFailure, format!("{value}")),
     }
   }
 }
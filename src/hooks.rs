@@ -0,0 +1,290 @@
+use crate::repository::Repository;
+use crate::util::path_to_string;
+use napi_derive::napi;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[napi(object)]
+/// Options for running a hook.
+pub struct RunHookOptions {
+  /// Data written to the standard input of the hook.
+  pub stdin: Option<String>,
+  /// Additional environment variables for the hook.
+  pub env: Option<HashMap<String, String>>,
+}
+
+#[napi(object)]
+/// Result of running a hook.
+pub struct HookResult {
+  /// Name of the hook.
+  pub name: String,
+  /// Path of the executed hook.
+  pub path: String,
+  /// Exit code of the hook. `null` if the hook is terminated by a signal.
+  pub exit_code: Option<i32>,
+  /// Standard output of the hook.
+  pub stdout: String,
+  /// Standard error of the hook.
+  pub stderr: String,
+}
+
+/// Hooks of a repository, which are looked up in `core.hooksPath` or the
+/// `hooks` directory of the git directory.
+#[derive(Clone)]
+pub(crate) struct Hooks {
+  dir: PathBuf,
+  cwd: PathBuf,
+  git_dir: PathBuf,
+  index_file: Option<PathBuf>,
+}
+
+impl Hooks {
+  pub(crate) fn new(repo: &git2::Repository) -> crate::Result<Self> {
+    // Like git, hooks run in the root of the working tree, or in the git
+    // directory for bare repositories.
+    let cwd = repo.workdir().unwrap_or_else(|| repo.path()).to_path_buf();
+    let git_dir = repo.path().to_path_buf();
+    let index_file = match repo.is_bare() {
+      true => None,
+      false => Some(git_dir.join("index")),
+    };
+    let dir = match repo.config()?.get_path("core.hooksPath") {
+      Ok(path) => cwd.join(path),
+      Err(e) if e.code() == git2::ErrorCode::NotFound => repo.commondir().join("hooks"),
+      Err(e) => return Err(e.into()),
+    };
+    Ok(Self {
+      dir,
+      cwd,
+      git_dir,
+      index_file,
+    })
+  }
+
+  /// Run the hook, returning `None` if the hook does not exist or is not executable.
+  pub(crate) fn run(
+    &self,
+    name: &str,
+    args: &[String],
+    stdin: Option<&str>,
+    env: Option<&HashMap<String, String>>,
+  ) -> crate::Result<Option<HookResult>> {
+    let path = self.dir.join(name);
+    if !is_executable(&path) {
+      return Ok(None);
+    }
+    let mut command = match cfg!(windows) {
+      // Hooks are usually shell scripts which can't be executed directly on Windows.
+      true => {
+        let mut command = Command::new("sh");
+        command.arg(&path);
+        command
+      }
+      false => Command::new(&path),
+    };
+    command
+      .args(args)
+      .current_dir(&self.cwd)
+      .stdin(match stdin {
+        Some(_) => Stdio::piped(),
+        None => Stdio::null(),
+      })
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .env("GIT_DIR", &self.git_dir);
+    if let Some(index_file) = &self.index_file {
+      command.env("GIT_INDEX_FILE", index_file);
+    }
+    if let Some(env) = env {
+      command.envs(env);
+    }
+    let mut child = command.spawn()?;
+    if let (Some(input), Some(mut child_stdin)) = (stdin, child.stdin.take()) {
+      let input = input.to_string();
+      // Write in another thread, so the hook is not blocked on a full output pipe.
+      std::thread::spawn(move || {
+        let _ = child_stdin.write_all(input.as_bytes());
+      });
+    }
+    let output = child.wait_with_output()?;
+    Ok(Some(HookResult {
+      name: name.to_string(),
+      path: path_to_string(&path),
+      exit_code: output.status.code(),
+      stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+      stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    }))
+  }
+
+  /// Run the hook, failing if it exits with non-zero status.
+  pub(crate) fn run_checked(&self, name: &str, args: &[String], stdin: Option<&str>) -> crate::Result<()> {
+    match self.run(name, args, stdin, None)? {
+      Some(result) if result.exit_code != Some(0) => {
+        let output = format!("{}{}", result.stdout, result.stderr);
+        let status = match result.exit_code {
+          Some(code) => format!("exit code {code}"),
+          None => "signal".to_string(),
+        };
+        Err(crate::Error::Hook(format!(
+          "{name} hook declined with {status}: {}",
+          output.trim_end()
+        )))
+      }
+      _ => Ok(()),
+    }
+  }
+
+  /// Run the hook whose result can't affect the outcome of the operation.
+  pub(crate) fn run_ignored(&self, name: &str, args: &[String], stdin: Option<&str>) {
+    let _ = self.run(name, args, stdin, None);
+  }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+  path
+    .metadata()
+    .map(|x| x.is_file() && x.permissions().mode() & 0o111 != 0)
+    .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+  path.is_file()
+}
+
+/// Run the `pre-commit` hook for a commit of the tree, returning the tree to
+/// commit.
+///
+/// `pre-commit` may update the index (e.g. formatters adding fixed files).
+/// If the tree is written from the index, the index is written for the hook
+/// like `git commit` does, and the tree is written again afterwards.
+pub(crate) fn run_pre_commit_hook(repo: &git2::Repository, tree: git2::Oid) -> crate::Result<git2::Oid> {
+  let mut index = repo.index()?;
+  let from_index = index.write_tree()? == tree;
+  if from_index {
+    index.write()?;
+  }
+  Hooks::new(repo)?.run_checked("pre-commit", &[], None)?;
+  if !from_index {
+    return Ok(tree);
+  }
+  index.read(true)?;
+  Ok(index.write_tree()?)
+}
+
+/// Run the `prepare-commit-msg` and `commit-msg` hooks, returning the message
+/// which may be edited by the hooks.
+///
+/// The message is returned as is if the hooks don't edit it. Otherwise it's
+/// cleaned up like `git commit -m` by `commit.cleanup` config: comment lines
+/// are stripped only for `strip`, nothing is done for `verbatim`, and only
+/// whitespace is cleaned up for the other modes.
+pub(crate) fn run_commit_msg_hooks(repo: &git2::Repository, message: &str, source: &[String]) -> crate::Result<String> {
+  let hooks = Hooks::new(repo)?;
+  let message_file = repo.path().join("COMMIT_EDITMSG");
+  std::fs::write(&message_file, message)?;
+  let message_file = path_to_string(&message_file);
+  let mut args = vec![message_file.clone()];
+  args.extend_from_slice(source);
+  hooks.run_checked("prepare-commit-msg", &args, None)?;
+  hooks.run_checked("commit-msg", std::slice::from_ref(&message_file), None)?;
+  let edited = std::fs::read_to_string(message_file)?;
+  if edited == message {
+    return Ok(edited);
+  }
+  let config = repo.config()?;
+  let message = match config.get_string("commit.cleanup").ok().as_deref() {
+    Some("verbatim") => edited,
+    Some("strip") => {
+      let comment_char = config
+        .get_string("core.commentChar")
+        .ok()
+        .filter(|x| x != "auto")
+        .and_then(|x| x.bytes().next())
+        .unwrap_or(b'#');
+      git2::message_prettify(edited, Some(comment_char))?
+    }
+    _ => git2::message_prettify(edited, None)?,
+  };
+  Ok(message)
+}
+
+/// Run the `post-commit` hook, and the `post-rewrite` hook if the commit
+/// replaces another one.
+pub(crate) fn run_post_commit_hooks(repo: &git2::Repository, rewritten: Option<(git2::Oid, git2::Oid)>) {
+  let Ok(hooks) = Hooks::new(repo) else {
+    return;
+  };
+  hooks.run_ignored("post-commit", &[], None);
+  if let Some((old, new)) = rewritten {
+    hooks.run_ignored("post-rewrite", &["amend".to_string()], Some(&format!("{old} {new}\n")));
+  }
+}
+
+/// Get the commit `HEAD` points to, or the zero object id if `HEAD` is unborn.
+pub(crate) fn head_oid(repo: &git2::Repository) -> git2::Oid {
+  repo
+    .head()
+    .and_then(|x| x.peel_to_commit())
+    .map(|x| x.id())
+    .unwrap_or_else(|_| git2::Oid::zero())
+}
+
+#[napi]
+impl Repository {
+  #[napi]
+  /// Run a [hook][1] of the repository.
+  ///
+  /// Hooks are looked up in the directory of `core.hooksPath` config, or the
+  /// `hooks` directory of the git directory. The hook runs in the root of the
+  /// working tree.
+  ///
+  /// [1]: https://git-scm.com/docs/githooks
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   runHook(
+  ///     name: string,
+  ///     args?: string[] | null | undefined,
+  ///     options?: RunHookOptions | null | undefined,
+  ///   ): HookResult | null;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - Name of the hook (e.g. `pre-commit`).
+  /// @param {string[]} [args] - Arguments passed to the hook.
+  /// @param {RunHookOptions} [options] - Options for running the hook.
+  /// @returns Result of the hook. Returns `null` if the hook does not exist or is not executable.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// const result = repo.runHook('pre-push', ['origin', 'https://github.com/toss/es-git'], {
+  ///   stdin: 'refs/heads/main 67e2f2b refs/heads/main 0000000\n',
+  /// });
+  /// if (result != null && result.exitCode !== 0) {
+  ///   console.error(result.stderr);
+  /// }
+  /// ```
+  pub fn run_hook(
+    &self,
+    name: String,
+    args: Option<Vec<String>>,
+    options: Option<RunHookOptions>,
+  ) -> crate::Result<Option<HookResult>> {
+    let hooks = Hooks::new(&self.inner)?;
+    let (stdin, env) = match options {
+      Some(options) => (options.stdin, options.env),
+      None => (None, None),
+    };
+    hooks.run(&name, &args.unwrap_or_default(), stdin.as_deref(), env.as_ref())
+  }
+}
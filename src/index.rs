@@ -1,3 +1,4 @@
+use crate::diff::DiffOptions;
 use crate::repository::Repository;
 use crate::util;
 use chrono::{DateTime, Timelike, Utc};
@@ -178,6 +179,205 @@ pub struct IndexUpdateAllOptions {
   pub on_match: Option<Function<'static, IndexOnMatchCallbackArgs, i32>>,
 }
 
+#[napi(object)]
+pub struct HunkSelection {
+  /// Index of the hunk in the diff of the file.
+  pub hunk: u32,
+  /// Indices of lines in the hunk to select.
+  ///
+  /// Indices count every line of the hunk (context lines included), in the
+  /// same order as they are printed by `Diff.print()`. Only added and
+  /// deleted lines are affected by the selection.
+  ///
+  /// If not provided, every line in the hunk is selected.
+  pub lines: Option<Vec<u32>>,
+}
+
+fn is_line_selected(selection: &[HunkSelection], hunk_idx: usize, line_idx: usize) -> bool {
+  selection.iter().any(|x| {
+    x.hunk as usize == hunk_idx
+      && x
+        .lines
+        .as_ref()
+        .map(|lines| lines.iter().any(|l| *l as usize == line_idx))
+        .unwrap_or(true)
+  })
+}
+
+/// Build new content by applying only the selected lines of the diff between
+/// `old` and `new` on top of `old`.
+fn apply_selected_lines(
+  old: &[u8],
+  new: &[u8],
+  path: &Path,
+  options: Option<DiffOptions>,
+  selected: impl Fn(usize, usize) -> bool,
+) -> crate::Result<Vec<u8>> {
+  let mut opts = options.map(git2::DiffOptions::from);
+  let patch = git2::Patch::from_buffers(old, Some(path), new, Some(path), opts.as_mut())?;
+  let old_lines = old.split_inclusive(|x| *x == b'\n').collect::<Vec<_>>();
+  let mut content = Vec::with_capacity(new.len().max(old.len()));
+  let mut old_pos = 0;
+  for hunk_idx in 0..patch.num_hunks() {
+    let (hunk, lines_count) = patch.hunk(hunk_idx)?;
+    // Hunks without old lines start *after* `oldStart`.
+    let hunk_start = match hunk.old_lines() {
+      0 => hunk.old_start() as usize,
+      _ => hunk.old_start() as usize - 1,
+    };
+    for line in old_lines.iter().take(hunk_start).skip(old_pos) {
+      push_line(&mut content, line);
+    }
+    old_pos = hunk_start;
+    for line_idx in 0..lines_count {
+      let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+      match line.origin() {
+        ' ' => {
+          push_line(&mut content, line.content());
+          old_pos += 1;
+        }
+        '-' => {
+          if !selected(hunk_idx, line_idx) {
+            push_line(&mut content, line.content());
+          }
+          old_pos += 1;
+        }
+        '+' if selected(hunk_idx, line_idx) => push_line(&mut content, line.content()),
+        // `=`, `>` and `<` mark the line before them as having no newline at
+        // the end of the file, which `push_line` takes care of if another line
+        // follows it.
+        _ => {}
+      }
+    }
+  }
+  for line in old_lines.iter().skip(old_pos) {
+    push_line(&mut content, line);
+  }
+  Ok(content)
+}
+
+/// Append a line, terminating the previous line first if it is the last
+/// line of a file without a newline at the end.
+fn push_line(content: &mut Vec<u8>, line: &[u8]) {
+  if content.last().is_some_and(|x| *x != b'\n') {
+    content.push(b'\n');
+  }
+  content.extend_from_slice(line);
+}
+
+fn head_content(repo: &git2::Repository, path: &Path) -> crate::Result<Option<Vec<u8>>> {
+  let tree = match repo.head().and_then(|x| x.peel_to_tree()) {
+    Ok(tree) => tree,
+    Err(e) if matches!(e.code(), git2::ErrorCode::UnbornBranch | git2::ErrorCode::NotFound) => return Ok(None),
+    Err(e) => return Err(e.into()),
+  };
+  let entry = match tree.get_path(path) {
+    Ok(entry) => entry,
+    Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+    Err(e) => return Err(e.into()),
+  };
+  let blob = repo.find_blob(entry.id())?;
+  Ok(Some(blob.content().to_vec()))
+}
+
+fn index_content(
+  repo: &git2::Repository,
+  index: &git2::Index,
+  path: &Path,
+) -> crate::Result<Option<(git2::IndexEntry, Vec<u8>)>> {
+  match index.get_path(path, 0) {
+    Some(entry) => {
+      let blob = repo.find_blob(entry.id)?;
+      let content = blob.content().to_vec();
+      Ok(Some((entry, content)))
+    }
+    None => Ok(None),
+  }
+}
+
+fn workdir_path(repo: &git2::Repository, path: &Path) -> crate::Result<std::path::PathBuf> {
+  let workdir = repo
+    .workdir()
+    .ok_or_else(|| git2::Error::from_str("cannot select lines in a bare repository"))?;
+  Ok(workdir.join(path))
+}
+
+/// Clear the stat data of the entry, whose content no longer matches the file
+/// in the working directory.
+fn clear_stat(entry: &mut git2::IndexEntry) {
+  entry.ctime = git2::IndexTime::new(0, 0);
+  entry.mtime = git2::IndexTime::new(0, 0);
+  entry.dev = 0;
+  entry.ino = 0;
+  entry.uid = 0;
+  entry.gid = 0;
+}
+
+/// Read the file in the working directory as it would be staged, with the
+/// clean filters (e.g. `core.autocrlf`) applied.
+fn workdir_content(repo: &git2::Repository, path: &Path) -> crate::Result<Vec<u8>> {
+  let oid = repo.blob_path(&workdir_path(repo, path)?)?;
+  let blob = repo.find_blob(oid)?;
+  Ok(blob.content().to_vec())
+}
+
+/// Write the content into the file in the working directory by checking it
+/// out, so that smudge filters are applied. The index is not updated.
+fn write_workdir_content(repo: &git2::Repository, path: &str, mode: u32, content: &[u8]) -> crate::Result<()> {
+  let mut entry = new_index_entry(path, mode);
+  entry.id = repo.blob(content)?;
+  let mut index = git2::Index::new()?;
+  index.add(&entry)?;
+  let mut checkout = git2::build::CheckoutBuilder::new();
+  checkout
+    .force()
+    .update_index(false)
+    .disable_pathspec_match(true)
+    .path(path);
+  repo.checkout_index(Some(&mut index), Some(&mut checkout))?;
+  Ok(())
+}
+
+/// Get the mode of a file in the working directory to stage it as a new
+/// file, which is executable only if `core.fileMode` is not disabled.
+fn workdir_mode(repo: &git2::Repository, path: &Path) -> crate::Result<u32> {
+  let trust_mode = repo.config()?.get_bool("core.fileMode").unwrap_or(true);
+  let executable = trust_mode && is_executable(&workdir_path(repo, path)?.metadata()?);
+  let mode = match executable {
+    true => git2::FileMode::BlobExecutable,
+    false => git2::FileMode::Blob,
+  };
+  Ok(u32::from(mode))
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &std::fs::Metadata) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+  metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &std::fs::Metadata) -> bool {
+  false
+}
+
+fn new_index_entry(path: &str, mode: u32) -> git2::IndexEntry {
+  git2::IndexEntry {
+    ctime: git2::IndexTime::new(0, 0),
+    mtime: git2::IndexTime::new(0, 0),
+    dev: 0,
+    ino: 0,
+    mode,
+    uid: 0,
+    gid: 0,
+    file_size: 0,
+    id: git2::Oid::zero(),
+    flags: 0,
+    flags_extended: 0,
+    path: path.as_bytes().to_vec(),
+  }
+}
+
 #[napi]
 /// A class to represent a git [index][1].
 ///
@@ -526,6 +726,127 @@ impl Index {
     Ok(())
   }
 
+  #[napi]
+  /// Stage only the selected hunks or lines of a file.
+  ///
+  /// The selection refers to the diff between the index and the working
+  /// directory for the path (as in `diffIndexToWorkdir`). New content is
+  /// built from the index version with selected changes applied and written
+  /// into this index. The working directory file is not touched.
+  ///
+  /// Like `git add`, the working directory file is read with clean filters
+  /// (e.g. `core.autocrlf`) applied, and a new file is staged with its
+  /// executable bit.
+  ///
+  /// @category Index/Methods
+  /// @signature
+  /// ```ts
+  /// class Index {
+  ///   stageHunks(
+  ///     repo: Repository,
+  ///     path: string,
+  ///     selection: HunkSelection[],
+  ///     options?: DiffOptions | null | undefined,
+  ///   ): void;
+  /// }
+  /// ```
+  ///
+  /// @param {Repository} repo - Repository which owns this index.
+  /// @param {string} path - Relative file path to the repository's working directory.
+  /// @param {HunkSelection[]} selection - Hunks or lines to stage.
+  /// @param {DiffOptions} [options] - Options used to generate the diff. Must match the options
+  /// used to display hunks, so that hunk and line indices refer to the same lines.
+  ///
+  /// @throws This method will fail in bare repositories.
+  ///
+  /// @example
+  ///
+  /// Stage the first added line of the second hunk.
+  ///
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// const index = repo.index();
+  /// index.stageHunks(repo, 'README.md', [{ hunk: 1, lines: [3] }]);
+  /// index.write();
+  /// ```
+  pub fn stage_hunks(
+    &mut self,
+    repo: &Repository,
+    path: String,
+    selection: Vec<HunkSelection>,
+    options: Option<DiffOptions>,
+  ) -> crate::Result<()> {
+    let repo_path = Path::new(&path);
+    let new = workdir_content(&repo.inner, repo_path)?;
+    let (mut entry, old) = match index_content(&repo.inner, &self.inner, repo_path)? {
+      Some((entry, content)) => (entry, content),
+      None => (new_index_entry(&path, workdir_mode(&repo.inner, repo_path)?), vec![]),
+    };
+    let content = apply_selected_lines(&old, &new, repo_path, options, |hunk_idx, line_idx| {
+      is_line_selected(&selection, hunk_idx, line_idx)
+    })?;
+    clear_stat(&mut entry);
+    self.inner.add_frombuffer(&entry, &content)?;
+    Ok(())
+  }
+
+  #[napi]
+  /// Unstage only the selected hunks or lines of a file.
+  ///
+  /// The selection refers to the diff between the `HEAD` tree and the index
+  /// for the path. Selected changes are reverted to the `HEAD` version in
+  /// this index. The working directory file is not touched.
+  ///
+  /// If the file doesn't exist in `HEAD` and every line is unstaged, the
+  /// file is removed from this index.
+  ///
+  /// @category Index/Methods
+  /// @signature
+  /// ```ts
+  /// class Index {
+  ///   unstageHunks(
+  ///     repo: Repository,
+  ///     path: string,
+  ///     selection: HunkSelection[],
+  ///     options?: DiffOptions | null | undefined,
+  ///   ): void;
+  /// }
+  /// ```
+  ///
+  /// @param {Repository} repo - Repository which owns this index.
+  /// @param {string} path - Relative file path to the repository's working directory.
+  /// @param {HunkSelection[]} selection - Hunks or lines to unstage.
+  /// @param {DiffOptions} [options] - Options used to generate the diff. Must match the options
+  /// used to display hunks, so that hunk and line indices refer to the same lines.
+  ///
+  /// @throws Throws error if the path does not exist in the index.
+  pub fn unstage_hunks(
+    &mut self,
+    repo: &Repository,
+    path: String,
+    selection: Vec<HunkSelection>,
+    options: Option<DiffOptions>,
+  ) -> crate::Result<()> {
+    let repo_path = Path::new(&path);
+    let (mut entry, new) = index_content(&repo.inner, &self.inner, repo_path)?
+      .ok_or_else(|| git2::Error::from_str(&format!("path '{path}' does not exist in the index")))?;
+    let head = head_content(&repo.inner, repo_path)?;
+    let old = head.as_deref().unwrap_or_default();
+    let content = apply_selected_lines(old, &new, repo_path, options, |hunk_idx, line_idx| {
+      !is_line_selected(&selection, hunk_idx, line_idx)
+    })?;
+    // Unstaging every line of a new file unstages the file itself.
+    if head.is_none() && content.is_empty() && !new.is_empty() {
+      self.inner.remove_path(repo_path)?;
+      return Ok(());
+    }
+    clear_stat(&mut entry);
+    self.inner.add_frombuffer(&entry, &content)?;
+    Ok(())
+  }
+
   #[napi]
   /// Get the count of entries currently in the index.
   ///
@@ -647,4 +968,52 @@ impl Repository {
       inner: self.inner.index()?,
     })
   }
+
+  #[napi]
+  /// Discard only the selected hunks or lines of a file in the working
+  /// directory.
+  ///
+  /// The selection refers to the diff between the index and the working
+  /// directory for the path (as in `diffIndexToWorkdir`). Selected changes
+  /// are reverted to the index version in the working directory file. The
+  /// index is not touched.
+  ///
+  /// The working directory file is read with clean filters and written by a
+  /// checkout with smudge filters, like `git checkout -p`.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   discardHunks(
+  ///     path: string,
+  ///     selection: HunkSelection[],
+  ///     options?: DiffOptions | null | undefined,
+  ///   ): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string} path - Relative file path to the repository's working directory.
+  /// @param {HunkSelection[]} selection - Hunks or lines to discard.
+  /// @param {DiffOptions} [options] - Options used to generate the diff. Must match the options
+  /// used to display hunks, so that hunk and line indices refer to the same lines.
+  ///
+  /// @throws Throws error if the path does not exist in the index or the repository is bare.
+  pub fn discard_hunks(
+    &self,
+    path: String,
+    selection: Vec<HunkSelection>,
+    options: Option<DiffOptions>,
+  ) -> crate::Result<()> {
+    let repo_path = Path::new(&path);
+    let index = self.inner.index()?;
+    let (entry, old) = index_content(&self.inner, &index, repo_path)?
+      .ok_or_else(|| git2::Error::from_str(&format!("path '{path}' does not exist in the index")))?;
+    let new = workdir_content(&self.inner, repo_path)?;
+    let content = apply_selected_lines(&old, &new, repo_path, options, |hunk_idx, line_idx| {
+      !is_line_selected(&selection, hunk_idx, line_idx)
+    })?;
+    write_workdir_content(&self.inner, &path, entry.mode, &content)?;
+    Ok(())
+  }
 }
@@ -19,31 +19,36 @@ where
   Either<Ret, UnknownReturnValue>: FromNapiValue,
 {
   fn invoke(&self, args: Args) -> Result<Ret, Error> {
-    let init_value = Ok(Either::B(UnknownReturnValue));
-    let pair = Arc::new((Mutex::new(init_value), Condvar::new()));
+    let pair = Arc::new((Mutex::new(None), Condvar::new()));
     let pair_clone = Arc::clone(&pair);
 
-    self.call_with_return_value(
+    let status = self.call_with_return_value(
       args,
       napi::threadsafe_function::ThreadsafeFunctionCallMode::NonBlocking,
       move |ret, _env| {
         let (lock, cvar) = &*pair;
-        *lock.lock().unwrap() = ret;
+        *lock.lock().unwrap() = Some(ret);
         cvar.notify_one();
         Ok(())
       },
     );
+    if status != Status::Ok {
+      return Err(Error::new(status, "failed to call the JavaScript callback"));
+    }
 
+    // The callback may run before we start waiting, so wait for the result
+    // rather than for the notification.
     let (lock, cvar) = &*pair_clone;
     let notified = lock.lock().unwrap();
     let mut res = cvar
-      .wait(notified)
+      .wait_while(notified, |res| res.is_none())
       .map_err(|err| Error::new(Status::GenericFailure, format!("PoisonError: {err:?}",)))?;
     let res = res
-      .as_mut()
+      .take()
+      .unwrap_or(Ok(Either::B(UnknownReturnValue)))
       .map_err(|err| Error::new(Status::GenericFailure, format!("{err:?}",)))?;
 
-    match std::mem::replace(res, Either::B(UnknownReturnValue)) {
+    match res {
       Either::A(ret) => Ok(ret),
       Either::B(_unknown) => unknown_return_err::<Ret>(),
     }
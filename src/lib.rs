@@ -13,6 +13,7 @@ pub mod config;
 pub mod describe;
 pub mod diff;
 mod error;
+pub mod hooks;
 pub mod ignore;
 pub mod index;
 pub(crate) mod js;
@@ -29,12 +30,16 @@ pub mod repository;
 pub mod revert;
 pub mod revparse;
 pub mod revwalk;
+pub mod server;
 pub mod signature;
+pub mod signing;
+pub mod sparse_checkout;
 pub mod stash;
 pub mod status;
 pub mod submodule;
 pub mod tag;
 pub mod tracing;
+pub mod transport;
 pub mod tree;
 pub(crate) mod util;
 pub mod worktree;
@@ -1,6 +1,7 @@
 use crate::annotated_commit::AnnotatedCommit;
 use crate::checkout::CheckoutOptions;
 use crate::commit::Commit;
+use crate::hooks::Hooks;
 use crate::index::Index;
 use crate::reference::Reference;
 use crate::repository::Repository;
@@ -83,6 +84,10 @@ pub struct MergeOptions {
   pub patience: Option<bool>,
   /// Take extra time to find minimal diff
   pub minimal: Option<bool>,
+  /// Run the `post-merge` hook after a merge without conflicts.
+  ///
+  /// Only used by `merge`.
+  pub run_hooks: Option<bool>,
 }
 
 impl From<MergeOptions> for git2::MergeOptions {
@@ -392,11 +397,16 @@ impl Repository {
     checkout_options: Option<CheckoutOptions>,
   ) -> crate::Result<()> {
     let commits = annotated_commits.iter().map(|x| x.inner.deref()).collect::<Vec<_>>();
+    let run_hooks = merge_options.as_ref().and_then(|x| x.run_hooks).unwrap_or(false);
     let mut merge_opts = merge_options.map(git2::MergeOptions::from);
     let mut checkout_opts = checkout_options.map(git2::build::CheckoutBuilder::from);
     self
       .inner
       .merge(commits.as_slice(), merge_opts.as_mut(), checkout_opts.as_mut())?;
+    if run_hooks && !self.inner.index()?.has_conflicts() {
+      // The argument tells whether the merge is a squash merge, which is never the case here.
+      Hooks::new(&self.inner)?.run_ignored("post-merge", &["0".to_string()], None);
+    }
     Ok(())
   }
 
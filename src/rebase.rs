@@ -1,9 +1,11 @@
 use crate::annotated_commit::AnnotatedCommit;
 use crate::checkout::CheckoutOptions;
+use crate::hooks::Hooks;
 use crate::index::Index;
 use crate::merge::MergeOptions;
 use crate::repository::Repository;
 use crate::signature::{Signature, SignaturePayload};
+use crate::signing::{sign_payload, SignerOptions};
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use std::ops::Deref;
@@ -17,6 +19,10 @@ pub struct RebaseCommitOptions {
   pub committer: SignaturePayload,
   /// To keep the message from the original commit leave this as empty.
   pub message: Option<String>,
+  /// Signer to create a signed commit.
+  ///
+  /// Not available for in-memory rebases.
+  pub signer: Option<SignerOptions>,
 }
 
 #[napi]
@@ -25,6 +31,8 @@ pub struct RebaseCommitOptions {
 /// (e.g., `for (const op of rebase) { ... }` or calling `next()`).
 pub struct Rebase {
   pub(crate) inner: SharedReference<Repository, git2::Rebase<'static>>,
+  hooks: Option<Hooks>,
+  rewritten: Vec<(git2::Oid, git2::Oid)>,
 }
 
 #[napi]
@@ -127,15 +135,45 @@ impl Rebase {
   ///   rebase.commit({ committer: sig });
   /// }
   /// ```
-  pub fn commit(&mut self, options: RebaseCommitOptions) -> crate::Result<String> {
+  pub fn commit(&mut self, env: Env, options: RebaseCommitOptions) -> crate::Result<String> {
     let author = options
       .author
       .and_then(|x| Signature::try_from(x).ok())
       .and_then(|x| git2::Signature::try_from(x).ok());
     let committer = Signature::try_from(options.committer).and_then(git2::Signature::try_from)?;
+    // The in-memory index is only available for in-memory rebases, where the next
+    // operation is applied on top of the created commit which cannot be replaced.
+    if options.signer.is_some() && self.inner.inmemory_index().is_ok() {
+      return Err(crate::Error::Signing(
+        "cannot sign commits of an in-memory rebase".to_string(),
+      ));
+    }
+    let original = self
+      .inner
+      .operation_current()
+      .and_then(|i| self.inner.nth(i))
+      .map(|op| op.id());
     let oid = self
       .inner
       .commit(author.as_ref(), &committer, options.message.as_deref())?;
+    let oid = match options.signer {
+      Some(signer) => {
+        // Replace the created commit with the signed one, so the next operation is
+        // applied on top of it.
+        let repo = self.inner.clone_owner(env)?;
+        let odb = repo.inner.odb()?;
+        let object = odb.read(oid)?;
+        let content = std::str::from_utf8(object.data())?;
+        let signature = sign_payload(&repo.inner, &signer, content)?;
+        let signed_oid = repo.inner.commit_signed(content, &signature, None)?;
+        replace_rebased_commit(&repo.inner, oid, signed_oid)?;
+        signed_oid
+      }
+      None => oid,
+    };
+    if let (Some(_), Some(original)) = (&self.hooks, original) {
+      self.rewritten.push((original, oid));
+    }
     Ok(oid.to_string())
   }
 
@@ -173,6 +211,16 @@ impl Rebase {
       .and_then(|x| Signature::try_from(x).ok())
       .and_then(|x| git2::Signature::try_from(x).ok());
     self.inner.finish(signature.as_ref())?;
+    if let Some(hooks) = &self.hooks {
+      if !self.rewritten.is_empty() {
+        let stdin = self
+          .rewritten
+          .iter()
+          .map(|(old, new)| format!("{old} {new}\n"))
+          .collect::<String>();
+        hooks.run_ignored("post-rewrite", &["rebase".to_string()], Some(&stdin));
+      }
+    }
     Ok(())
   }
 
@@ -299,6 +347,12 @@ pub struct RebaseOptions {
   /// strategy of `GIT_CHECKOUT_FORCE` is defaulted in `abort` to match git
   /// semantics.
   pub checkout_options: Option<CheckoutOptions>,
+  /// Run the `pre-rebase` hook before the rebase begins, and the
+  /// `post-rewrite` hook with rebased commits in `finish()`.
+  ///
+  /// The rebase is aborted if `pre-rebase` exits with non-zero status.
+  /// `post-rewrite` is not run for in-memory rebases.
+  pub run_hooks: Option<bool>,
 }
 
 impl From<RebaseOptions> for git2::RebaseOptions<'_> {
@@ -382,6 +436,14 @@ impl Repository {
     env: Env,
     this: Reference<Repository>,
   ) -> crate::Result<Rebase> {
+    let hooks = rebase_hooks(&self.inner, options.as_ref())?;
+    if let (Some(hooks), Some(upstream)) = (&hooks, upstream) {
+      let mut args = vec![annotated_commit_name(upstream)];
+      if let Some(branch) = branch {
+        args.push(annotated_commit_name(branch));
+      }
+      hooks.run_checked("pre-rebase", &args, None)?;
+    }
     let inner = this.share_with(env, move |repo| {
       repo
         .inner
@@ -394,7 +456,11 @@ impl Repository {
         .map_err(crate::Error::from)
         .map_err(|e| e.into())
     })?;
-    Ok(Rebase { inner })
+    Ok(Rebase {
+      inner,
+      hooks,
+      rewritten: Vec::new(),
+    })
   }
 
   #[napi]
@@ -419,6 +485,7 @@ impl Repository {
     env: Env,
     this: Reference<Repository>,
   ) -> crate::Result<Rebase> {
+    let hooks = rebase_hooks(&self.inner, options.as_ref())?;
     let inner = this.share_with(env, move |repo| {
       repo
         .inner
@@ -426,6 +493,45 @@ impl Repository {
         .map_err(crate::Error::from)
         .map_err(|e| e.into())
     })?;
-    Ok(Rebase { inner })
+    Ok(Rebase {
+      inner,
+      hooks,
+      rewritten: Vec::new(),
+    })
+  }
+}
+
+/// Replace the commit which libgit2 created for the current operation with
+/// the signed one, in `HEAD` and in the list of rewritten commits which is
+/// used to rewrite notes when the rebase finishes.
+fn replace_rebased_commit(repo: &git2::Repository, unsigned: git2::Oid, signed: git2::Oid) -> crate::Result<()> {
+  let commit = repo.find_commit(signed)?;
+  let summary = commit.summary().unwrap_or_default();
+  repo.reference_matching("HEAD", signed, true, unsigned, &format!("rebase (sign): {summary}"))?;
+  let rewritten = repo.path().join("rebase-merge").join("rewritten");
+  if let Ok(content) = std::fs::read_to_string(&rewritten) {
+    if let Some(rest) = content.strip_suffix(&format!(" {unsigned}\n")) {
+      std::fs::write(&rewritten, format!("{rest} {signed}\n"))?;
+    }
+  }
+  Ok(())
+}
+
+/// Resolve hooks for the rebase, if the options tell to run hooks.
+fn rebase_hooks(repo: &git2::Repository, options: Option<&RebaseOptions>) -> crate::Result<Option<Hooks>> {
+  match options {
+    Some(options) if options.run_hooks.unwrap_or(false) && !options.inmemory.unwrap_or(false) => {
+      Ok(Some(Hooks::new(repo)?))
+    }
+    _ => Ok(None),
+  }
+}
+
+/// Name of the annotated commit passed to hooks, which is the reference name
+/// if looked up from a reference, or the commit id.
+fn annotated_commit_name(commit: &AnnotatedCommit) -> String {
+  match commit.refname() {
+    Ok(Some(refname)) => refname,
+    _ => commit.id(),
   }
 }
@@ -1,10 +1,15 @@
-use crate::repository::Repository;
+use crate::hooks::Hooks;
+use crate::repository::{shallow_roots, Repository};
+use chrono::{DateTime, Utc};
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
-use std::path::Path;
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 
 #[napi(string_enum)]
+#[derive(Copy, Clone)]
 /// - `Fetch` : Fetch direction.
 /// - `Push` : Push direction.
 pub enum Direction {
@@ -21,6 +26,15 @@ impl From<git2::Direction> for Direction {
   }
 }
 
+impl From<Direction> for git2::Direction {
+  fn from(value: Direction) -> Self {
+    match value {
+      Direction::Fetch => git2::Direction::Fetch,
+      Direction::Push => git2::Direction::Push,
+    }
+  }
+}
+
 #[napi(object)]
 /// A data object to represent a git [refspec][1].
 ///
@@ -206,6 +220,30 @@ pub struct FetchOptions {
   /// Set fetch depth, a value less or equal to 0 is interpreted as pull
   /// everything (effectively the same as not declaring a limit depth).
   pub depth: Option<i32>,
+  /// Convert a shallow repository to a complete one by fetching all of the
+  /// missing history.
+  ///
+  /// This takes precedence over `depth`.
+  pub unshallow: Option<bool>,
+  /// Deepen the history of a shallow repository by the given number of
+  /// commits from its current shallow boundary (`git fetch --deepen`).
+  ///
+  /// This takes precedence over `depth`.
+  pub deepen: Option<i32>,
+  /// Deepen the history of a shallow repository until it reaches commits
+  /// older than the given date (`git fetch --shallow-since`).
+  ///
+  /// libgit2 can only request a number of commits from the remote, so the
+  /// history is deepened step by step and a few commits older than the date
+  /// may be fetched as well.
+  pub shallow_since: Option<DateTime<Utc>>,
+  /// Deepen the history of a shallow repository until it reaches commits
+  /// reachable from the given revisions (`git fetch --shallow-exclude`).
+  ///
+  /// The revisions are resolved in the local repository, so they are usually
+  /// remote-tracking branches or tags. As with `shallowSince`, a few commits
+  /// reachable from the revisions may be fetched as well.
+  pub shallow_exclude: Option<Vec<String>>,
   /// Set how to behave regarding tags on the remote, such as auto-downloading
   /// tags for objects we're downloading or downloading all of them.
   ///
@@ -219,12 +257,42 @@ pub struct FetchOptions {
   pub follow_redirects: Option<RemoteRedirect>,
   /// Set extra headers for this fetch operation.
   pub custom_headers: Option<Vec<String>>,
+  /// Object filter of a partial clone, like `git clone --filter` (e.g.
+  /// `blob:none`, `blob:limit=1m` or `tree:0`).
+  ///
+  /// Partial clones are not supported, because libgit2 can't negotiate
+  /// filters with the remote nor fetch missing objects on demand. An error is
+  /// thrown if this is set.
+  pub filter: Option<String>,
 }
 
 impl<'a> FetchOptions {
-  pub(crate) fn to_git2_fetch_options(&'a self) -> git2::FetchOptions<'a> {
+  /// Whether the history has to be deepened relatively to the current
+  /// shallow boundary with [`fetch_deepen`].
+  pub(crate) fn deepens(&self) -> bool {
+    self.unshallow != Some(true)
+      && (self.deepen.is_some() || self.shallow_since.is_some() || self.shallow_exclude.is_some())
+  }
+
+  pub(crate) fn to_git2_fetch_options(&'a self) -> crate::Result<git2::FetchOptions<'a>> {
+    self.to_git2_fetch_options_with(git2::RemoteCallbacks::new())
+  }
+
+  pub(crate) fn to_git2_fetch_options_with(
+    &'a self,
+    mut callbacks: git2::RemoteCallbacks<'a>,
+  ) -> crate::Result<git2::FetchOptions<'a>> {
+    if let Some(filter) = &self.filter {
+      return Err(
+        git2::Error::new(
+          git2::ErrorCode::Invalid,
+          git2::ErrorClass::Net,
+          format!("partial clone filter '{filter}' is not supported"),
+        )
+        .into(),
+      );
+    }
     let mut fetch = git2::FetchOptions::new();
-    let mut callbacks = git2::RemoteCallbacks::new();
     if let Some(cred) = &self.credential {
       callbacks.credentials(move |_url, _username, _cred| cred.to_git2_cred());
     }
@@ -238,6 +306,10 @@ impl<'a> FetchOptions {
     if let Some(depth) = self.depth {
       fetch.depth(depth);
     }
+    if let Some(true) = self.unshallow {
+      // Same as `GIT_FETCH_DEPTH_UNSHALLOW` of libgit2.
+      fetch.depth(i32::MAX);
+    }
     if let Some(download_tags) = self.download_tags {
       fetch.download_tags(download_tags.into());
     }
@@ -247,8 +319,151 @@ impl<'a> FetchOptions {
     if let Some(custom_headers) = &self.custom_headers {
       fetch.custom_headers(&custom_headers.iter().map(|x| x.as_str()).collect::<Vec<_>>());
     }
-    fetch
+    Ok(fetch)
+  }
+}
+
+/// Fetch with increasing depths until the `deepen`, `shallowSince` and
+/// `shallowExclude` options are satisfied.
+///
+/// libgit2 only sends `deepen <depth>` to the remote, which is counted from the
+/// fetched tips rather than from the current shallow boundary. So the depth
+/// starts at the current depth of the repository (fetching with a smaller
+/// depth would cut off history) and grows until the shallow boundary is where
+/// it is requested to be, the repository is not shallow anymore or the remote
+/// has no more history to send.
+pub(crate) fn fetch_deepen(
+  repo_path: &Path,
+  options: &FetchOptions,
+  mut fetch: impl FnMut(i32) -> crate::Result<()>,
+) -> crate::Result<()> {
+  let deepen = options.deepen.map(|x| x.max(0) as usize);
+  let (mut depth, roots) = {
+    let repo = git2::Repository::open(repo_path)?;
+    let roots = shallow_roots(&repo)?;
+    if roots.is_empty() {
+      // The history is complete already.
+      return fetch(0);
+    }
+    (current_depth(&repo)?, roots)
+  };
+  depth = depth.saturating_add(deepen.unwrap_or(0));
+  let mut previous = None;
+  loop {
+    fetch(depth.min(i32::MAX as usize) as i32)?;
+    // Open the repository again, as commits parsed before the fetch keep the
+    // parents of the previous shallow boundary.
+    let repo = git2::Repository::open(repo_path)?;
+    let shallow = shallow_roots(&repo)?.into_iter().collect::<HashSet<_>>();
+    if shallow.is_empty() || depth >= i32::MAX as usize || previous.as_ref() == Some(&shallow) {
+      return Ok(());
+    }
+    let mut next = depth;
+    if let Some(deepen) = deepen {
+      for root in &roots {
+        if let Some(distance) = distance_to_boundary(&repo, *root, &shallow, deepen)? {
+          next = next.max(depth.saturating_add(deepen - distance));
+        }
+      }
+    }
+    if !reaches_cutoff(&repo, options, &shallow)? {
+      next = next.max(depth.saturating_mul(2));
+    }
+    if next == depth {
+      return Ok(());
+    }
+    depth = next;
+    previous = Some(shallow);
+  }
+}
+
+/// Depth of the history reachable from the references of the repository,
+/// counted like the `depth` fetch option.
+fn current_depth(repo: &git2::Repository) -> crate::Result<usize> {
+  let mut queue = VecDeque::new();
+  let mut seen = HashSet::new();
+  for reference in repo.references()? {
+    if let Ok(commit) = reference?.peel_to_commit() {
+      if seen.insert(commit.id()) {
+        queue.push_back((commit.id(), 1));
+      }
+    }
+  }
+  let mut depth = 1;
+  while let Some((oid, distance)) = queue.pop_front() {
+    depth = depth.max(distance);
+    let Ok(commit) = repo.find_commit(oid) else {
+      continue;
+    };
+    for parent in commit.parent_ids() {
+      if seen.insert(parent) {
+        queue.push_back((parent, distance + 1));
+      }
+    }
+  }
+  Ok(depth)
+}
+
+/// Number of commits between `from` and the closest shallow commit, if it is
+/// less than `limit`.
+fn distance_to_boundary(
+  repo: &git2::Repository,
+  from: git2::Oid,
+  shallow: &HashSet<git2::Oid>,
+  limit: usize,
+) -> crate::Result<Option<usize>> {
+  let mut queue = VecDeque::from([(from, 0)]);
+  let mut seen = HashSet::from([from]);
+  while let Some((oid, distance)) = queue.pop_front() {
+    if distance >= limit {
+      break;
+    }
+    if shallow.contains(&oid) {
+      return Ok(Some(distance));
+    }
+    let Ok(commit) = repo.find_commit(oid) else {
+      continue;
+    };
+    for parent in commit.parent_ids() {
+      if seen.insert(parent) {
+        queue.push_back((parent, distance + 1));
+      }
+    }
+  }
+  Ok(None)
+}
+
+/// Whether the history of the fetched tips reaches the `shallowSince` and
+/// `shallowExclude` cutoffs, i.e. whether no shallow commit newer than the
+/// date or not reachable from the excluded revisions is left.
+fn reaches_cutoff(
+  repo: &git2::Repository,
+  options: &FetchOptions,
+  shallow: &HashSet<git2::Oid>,
+) -> crate::Result<bool> {
+  if options.shallow_since.is_none() && options.shallow_exclude.is_none() {
+    return Ok(true);
+  }
+  let mut revwalk = repo.revwalk()?;
+  repo.fetchhead_foreach(|_, _, oid, _| {
+    let _ = revwalk.push(*oid);
+    true
+  })?;
+  for spec in options.shallow_exclude.iter().flatten() {
+    revwalk.hide(repo.revparse_single(spec)?.peel_to_commit()?.id())?;
+  }
+  let since = options.shallow_since.map(|x| x.timestamp());
+  for oid in revwalk {
+    let oid = oid?;
+    if !shallow.contains(&oid) {
+      continue;
+    }
+    match since {
+      Some(since) if repo.find_commit(oid)?.time().seconds() < since => {}
+      _ => return Ok(false),
+    }
   }
+  Ok(true)
 }
 
 #[napi(object)]
@@ -274,12 +489,65 @@ pub struct PushOptions {
   pub custom_headers: Option<Vec<String>>,
   /// Set "push options" to deliver to the remote.
   pub remote_options: Option<Vec<String>>,
+  /// Expected states of the remote references, like `git push --force-with-lease`.
+  ///
+  /// The push is rejected if any of the remote references does not point to
+  /// the expected object, or if a lease does not match any pushed reference.
+  pub leases: Option<Vec<PushLease>>,
+  /// Throw an error if the remote rejects any of the reference updates.
+  ///
+  /// If `false`, rejected references are returned with the `Rejected` status
+  /// instead.
+  ///
+  /// Default: `true`.
+  pub throw_on_rejected: Option<bool>,
+  /// Run the `pre-push` hook before pushing.
+  ///
+  /// The push is aborted if the hook exits with non-zero status.
+  pub run_hooks: Option<bool>,
+  /// Update all references atomically, like `git push --atomic`.
+  ///
+  /// Atomic pushes are not supported, because libgit2 can't request the
+  /// `atomic` capability of the remote. An error is thrown if this is `true`.
+  pub atomic: Option<bool>,
+}
+
+#[napi(object)]
+/// Expected state of a remote reference for a push with lease.
+pub struct PushLease {
+  /// Name of the remote reference (e.g. `refs/heads/main`).
+  pub refname: String,
+  /// Object id which the remote reference is expected to point to.
+  ///
+  /// Use the zero object id to expect that the reference does not exist.
+  pub expected: String,
+}
+
+#[napi(string_enum)]
+#[derive(Copy, Clone)]
+/// - `Ok` : The remote reference was updated.
+/// - `Rejected` : The remote reference update was rejected by the remote.
+/// - `UpToDate` : The remote reference already pointed to the pushed object.
+pub enum PushStatus {
+  Ok,
+  Rejected,
+  UpToDate,
+}
+
+#[napi(object)]
+/// Result of pushing a reference.
+pub struct PushRefResult {
+  /// Name of the remote reference.
+  pub refname: String,
+  /// Status of the reference update.
+  pub status: PushStatus,
+  /// Message from the remote, if the update was rejected.
+  pub message: Option<String>,
 }
 
 impl<'a> PushOptions {
-  pub(crate) fn to_git2_push_options(&'a self) -> git2::PushOptions<'a> {
+  pub(crate) fn to_git2_push_options(&'a self, mut callbacks: git2::RemoteCallbacks<'a>) -> git2::PushOptions<'a> {
     let mut push = git2::PushOptions::new();
-    let mut callbacks = git2::RemoteCallbacks::new();
     if let Some(cred) = &self.credential {
       callbacks.credentials(move |_url, _username, _cred| cred.to_git2_cred());
     }
@@ -315,13 +583,114 @@ pub struct FetchRemoteOptions {
   pub reflog_msg: Option<String>,
 }
 
+#[napi(string_enum)]
+#[derive(Copy, Clone)]
+/// - `New` : The reference was created.
+/// - `FastForward` : The reference was updated to a descendant of the old object.
+/// - `Forced` : The reference was updated to an object which is not a descendant of the old object.
+/// - `Deleted` : The reference was deleted.
+pub enum UpdatedTipStatus {
+  New,
+  FastForward,
+  Forced,
+  Deleted,
+}
+
+#[napi(object)]
+/// A reference updated by a fetch.
+pub struct UpdatedTip {
+  /// Name of the updated reference.
+  pub refname: String,
+  /// Object id the reference pointed to before the fetch. `null` if the reference is created.
+  pub old_oid: Option<String>,
+  /// Object id the reference points to after the fetch. `null` if the reference is deleted.
+  pub new_oid: Option<String>,
+  /// How the reference is updated.
+  pub status: UpdatedTipStatus,
+}
+
+impl UpdatedTip {
+  fn new(repo: &git2::Repository, refname: String, old: git2::Oid, new: git2::Oid) -> Self {
+    let status = if old.is_zero() {
+      UpdatedTipStatus::New
+    } else if new.is_zero() {
+      UpdatedTipStatus::Deleted
+    } else if repo.graph_descendant_of(new, old).unwrap_or(false) {
+      UpdatedTipStatus::FastForward
+    } else {
+      UpdatedTipStatus::Forced
+    };
+    Self {
+      refname,
+      old_oid: (!old.is_zero()).then(|| old.to_string()),
+      new_oid: (!new.is_zero()).then(|| new.to_string()),
+      status,
+    }
+  }
+}
+
+#[napi(object)]
+/// Statistics of the data transferred from a remote.
+pub struct TransferStats {
+  /// Number of objects in the packfile being downloaded.
+  pub total_objects: u32,
+  /// Received objects that have been hashed.
+  pub indexed_objects: u32,
+  /// Objects which have been downloaded.
+  pub received_objects: u32,
+  /// Locally-available objects that have been injected in order to fix a thin pack.
+  pub local_objects: u32,
+  /// Number of deltas in the packfile being downloaded.
+  pub total_deltas: u32,
+  /// Received deltas that have been hashed.
+  pub indexed_deltas: u32,
+  /// Size of the packfile received up to now.
+  pub received_bytes: i64,
+}
+
+impl From<git2::Progress<'_>> for TransferStats {
+  fn from(value: git2::Progress<'_>) -> Self {
+    Self {
+      total_objects: value.total_objects() as u32,
+      indexed_objects: value.indexed_objects() as u32,
+      received_objects: value.received_objects() as u32,
+      local_objects: value.local_objects() as u32,
+      total_deltas: value.total_deltas() as u32,
+      indexed_deltas: value.indexed_deltas() as u32,
+      received_bytes: value.received_bytes() as i64,
+    }
+  }
+}
+
 #[napi(object)]
 pub struct PruneOptions {
   pub credential: Option<Credential>,
 }
 
+#[napi(object)]
+/// Options to control how a connection to a remote is established.
+pub struct ConnectRemoteOptions {
+  pub credential: Option<Credential>,
+  /// Set the proxy options to use for the connection.
+  pub proxy: Option<ProxyOptions>,
+}
+
+#[napi(object)]
+/// A reference advertised by a remote.
+pub struct RemoteHead {
+  /// The name of the reference.
+  pub name: String,
+  /// The object id the reference points to.
+  pub oid: String,
+  /// The target of the reference, if it is a symbolic reference (e.g. `HEAD`).
+  pub symref_target: Option<String>,
+  /// The object id an annotated tag peels to, if the remote advertised it.
+  pub peeled: Option<String>,
+}
+
 pub struct FetchRemoteTask {
   remote: RwLock<Reference<Remote>>,
+  repo_path: PathBuf,
   refspecs: Vec<String>,
   options: Option<FetchRemoteOptions>,
 }
@@ -330,34 +699,62 @@ unsafe impl Send for FetchRemoteTask {}
 
 #[napi]
 impl Task for FetchRemoteTask {
-  type Output = ();
-  type JsValue = ();
+  type Output = Vec<(String, git2::Oid, git2::Oid)>;
+  type JsValue = Vec<UpdatedTip>;
 
   fn compute(&mut self) -> Result<Self::Output> {
     let mut remote = self
       .remote
       .write()
       .map_err(|e| Error::new(Status::GenericFailure, format!("{e}")))?;
-    let mut fetch_options = match &self.options {
-      Some(FetchRemoteOptions { fetch: Some(fetch), .. }) => Some(fetch.to_git2_fetch_options()),
-      _ => None,
-    };
-    let reflog_msg = match &self.options {
-      Some(FetchRemoteOptions {
-        reflog_msg: Some(reflog_msg),
-        ..
-      }) => Some(reflog_msg.as_str()),
-      _ => None,
+    let tips = RefCell::new(Vec::<(String, git2::Oid, git2::Oid)>::new());
+    let fetch = self.options.as_ref().and_then(|x| x.fetch.as_ref());
+    let reflog_msg = self.options.as_ref().and_then(|x| x.reflog_msg.as_deref());
+    let mut fetch_with_depth = |depth: Option<i32>| -> crate::Result<()> {
+      let mut callbacks = git2::RemoteCallbacks::new();
+      callbacks.update_tips(|refname, old, new| {
+        let mut tips = tips.borrow_mut();
+        // Deepening fetches the same references again, so keep the first old id.
+        match tips.iter_mut().find(|(name, ..)| name == refname) {
+          Some(tip) => tip.2 = new,
+          None => tips.push((refname.to_string(), old, new)),
+        }
+        true
+      });
+      let mut fetch_options = match fetch {
+        Some(fetch) => fetch.to_git2_fetch_options_with(callbacks)?,
+        None => {
+          let mut fetch_options = git2::FetchOptions::new();
+          fetch_options.remote_callbacks(callbacks);
+          fetch_options
+        }
+      };
+      if let Some(depth) = depth {
+        fetch_options.depth(depth);
+      }
+      remote
+        .inner
+        .fetch(&self.refspecs, Some(&mut fetch_options), reflog_msg)?;
+      Ok(())
     };
-    remote
-      .inner
-      .fetch(&self.refspecs, fetch_options.as_mut(), reflog_msg)
-      .map_err(crate::Error::from)?;
-    Ok(())
+    match fetch {
+      Some(fetch) if fetch.deepens() => fetch_deepen(&self.repo_path, fetch, |depth| fetch_with_depth(Some(depth)))?,
+      _ => fetch_with_depth(None)?,
+    }
+    Ok(tips.into_inner())
   }
 
-  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
-    Ok(())
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    let remote = self
+      .remote
+      .read()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("{e}")))?;
+    let repo = remote.inner.clone_owner(env)?;
+    let tips = output
+      .into_iter()
+      .map(|(refname, old, new)| UpdatedTip::new(&repo.inner, refname, old, new))
+      .collect::<Vec<_>>();
+    Ok(tips)
   }
 }
 
@@ -365,30 +762,154 @@ pub struct PushRemoteTask {
   remote: RwLock<Reference<Remote>>,
   refspecs: Vec<String>,
   options: Option<PushOptions>,
+  hooks: Option<Hooks>,
 }
 
 unsafe impl Send for PushRemoteTask {}
 
 #[napi]
 impl Task for PushRemoteTask {
-  type Output = ();
-  type JsValue = ();
+  type Output = Vec<PushRefResult>;
+  type JsValue = Vec<PushRefResult>;
 
   fn compute(&mut self) -> Result<Self::Output> {
     let mut remote = self
       .remote
       .write()
       .map_err(|e| Error::new(Status::GenericFailure, format!("{e}")))?;
-    let mut push_options = self.options.as_ref().map(|x| x.to_git2_push_options());
-    remote
+    let leases = self.options.as_ref().and_then(|x| x.leases.as_ref());
+    let hooks = self.hooks.as_ref();
+    let url = remote
       .inner
-      .push(&self.refspecs, push_options.as_mut())
-      .map_err(crate::Error::from)?;
-    Ok(())
+      .pushurl()
+      .or(remote.inner.url())
+      .unwrap_or_default()
+      .to_string();
+    let hook_args = [remote.inner.name().unwrap_or(&url).to_string(), url.clone()];
+    let up_to_date = RefCell::new(Vec::<String>::new());
+    let stale = RefCell::new(Vec::<String>::new());
+    let negotiated = RefCell::new(false);
+    let unmatched_lease = RefCell::new(None::<String>);
+    let declined = RefCell::new(None::<crate::Error>);
+    let results = RefCell::new(Vec::<PushRefResult>::new());
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.push_negotiation(|updates| {
+      *negotiated.borrow_mut() = true;
+      if let Some(lease) = leases.and_then(|x| {
+        x.iter()
+          .find(|lease| !updates.iter().any(|u| u.dst_refname() == Some(lease.refname.as_str())))
+      }) {
+        *unmatched_lease.borrow_mut() = Some(lease.refname.clone());
+        return Err(git2::Error::from_str("lease does not match any pushed reference"));
+      }
+      for update in updates {
+        let Some(refname) = update.dst_refname() else {
+          continue;
+        };
+        if update.src() == update.dst() {
+          up_to_date.borrow_mut().push(refname.to_string());
+        }
+        if let Some(lease) = leases.and_then(|x| x.iter().find(|x| x.refname == refname)) {
+          if git2::Oid::from_str(&lease.expected)? != update.dst() {
+            stale.borrow_mut().push(refname.to_string());
+          }
+        }
+      }
+      if !stale.borrow().is_empty() {
+        return Err(git2::Error::from_str("stale info"));
+      }
+      if let Some(hooks) = hooks {
+        let stdin = updates
+          .iter()
+          .filter(|x| x.src() != x.dst())
+          .map(|x| {
+            let local_ref = match x.src().is_zero() {
+              true => "(delete)",
+              false => x.src_refname().unwrap_or_default(),
+            };
+            let remote_ref = x.dst_refname().unwrap_or_default();
+            format!("{local_ref} {} {remote_ref} {}\n", x.src(), x.dst())
+          })
+          .collect::<String>();
+        if let Err(e) = hooks.run_checked("pre-push", &hook_args, Some(&stdin)) {
+          *declined.borrow_mut() = Some(e);
+          return Err(git2::Error::from_str("pre-push hook declined"));
+        }
+      }
+      Ok(())
+    });
+    callbacks.push_update_reference(|refname, status| {
+      results.borrow_mut().push(PushRefResult {
+        refname: refname.to_string(),
+        status: match status {
+          Some(_) => PushStatus::Rejected,
+          None => PushStatus::Ok,
+        },
+        message: status.map(|x| x.to_string()),
+      });
+      Ok(())
+    });
+    let mut push_options = match &self.options {
+      Some(options) => options.to_git2_push_options(callbacks),
+      None => {
+        let mut push_options = git2::PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+        push_options
+      }
+    };
+    let pushed = remote.inner.push(&self.refspecs, Some(&mut push_options));
+    drop(push_options);
+    if let Err(e) = pushed {
+      if let Some(declined) = declined.into_inner() {
+        return Err(declined.into());
+      }
+      if let Some(refname) = unmatched_lease.into_inner() {
+        let message = format!("lease for '{refname}' does not match any pushed reference");
+        return Err(crate::Error::from(git2::Error::new(e.code(), e.class(), message)).into());
+      }
+      let stale = stale.into_inner();
+      if !stale.is_empty() {
+        let message = format!("push rejected because of stale info: {}", stale.join(", "));
+        return Err(crate::Error::from(git2::Error::new(e.code(), e.class(), message)).into());
+      }
+      return Err(crate::Error::from(e).into());
+    }
+    // Nothing is pushed without negotiation, so no lease can match.
+    if let (false, Some(lease)) = (negotiated.into_inner(), leases.and_then(|x| x.first())) {
+      let message = format!("lease for '{}' does not match any pushed reference", lease.refname);
+      return Err(crate::Error::from(git2::Error::from_str(&message)).into());
+    }
+    let up_to_date = up_to_date.into_inner();
+    let mut results = results.into_inner();
+    let throw_on_rejected = self.options.as_ref().and_then(|x| x.throw_on_rejected).unwrap_or(true);
+    let rejected = results
+      .iter()
+      .filter(|x| matches!(x.status, PushStatus::Rejected))
+      .map(|x| format!("{} ({})", x.refname, x.message.as_deref().unwrap_or("rejected")))
+      .collect::<Vec<_>>();
+    if throw_on_rejected && !rejected.is_empty() {
+      let message = format!("failed to push some refs: {}", rejected.join(", "));
+      return Err(crate::Error::from(git2::Error::from_str(&message)).into());
+    }
+    for result in results.iter_mut() {
+      if matches!(result.status, PushStatus::Ok) && up_to_date.contains(&result.refname) {
+        result.status = PushStatus::UpToDate;
+      }
+    }
+    for refname in up_to_date {
+      if !results.iter().any(|x| x.refname == refname) {
+        results.push(PushRefResult {
+          refname,
+          status: PushStatus::UpToDate,
+          message: None,
+        });
+      }
+    }
+    Ok(results)
   }
 
-  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
-    Ok(())
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
   }
 }
 
@@ -459,6 +980,50 @@ impl Task for GetRemoteDefaultBranchTask {
   }
 }
 
+pub struct ConnectRemoteTask {
+  remote: RwLock<Reference<Remote>>,
+  direction: Direction,
+  options: Option<ConnectRemoteOptions>,
+}
+
+unsafe impl Send for ConnectRemoteTask {}
+
+#[napi]
+impl Task for ConnectRemoteTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    let mut remote = self
+      .remote
+      .write()
+      .map_err(|e| Error::new(Status::GenericFailure, format!("{e}")))?;
+    let mut callbacks = git2::RemoteCallbacks::new();
+    if let Some(ConnectRemoteOptions {
+      credential: Some(cred), ..
+    }) = &self.options
+    {
+      callbacks.credentials(move |_url, _username, _cred| cred.to_git2_cred());
+    }
+    let proxy = match &self.options {
+      Some(ConnectRemoteOptions { proxy: Some(proxy), .. }) => Some(proxy.to_git2_proxy_options()),
+      _ => None,
+    };
+    // The advertised references are kept by the remote after the connection is
+    // closed, so they can be read with `Remote.list()` afterwards.
+    let connection = remote
+      .inner
+      .connect_auth(self.direction.into(), Some(callbacks), proxy)
+      .map_err(crate::Error::from)?;
+    drop(connection);
+    Ok(())
+  }
+
+  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+    Ok(())
+  }
+}
+
 #[napi]
 /// A class representing a [remote][1] of a git repository.
 ///
@@ -582,13 +1147,14 @@ impl Remote {
   ///     refspecs: string[],
   ///     options?: FetchRemoteOptions | null | undefined,
   ///     signal?: AbortSignal | null | undefined,
-  ///   ): Promise<void>;
+  ///   ): Promise<UpdatedTip[]>;
   /// }
   /// ```
   ///
   /// @param {string[]} refspecs - Refspecs to fetch from remote.
   /// @param {FetchRemoteOptions} [options] - Options for fetch remote.
   /// @param {AbortSignal} [signal] Abort signal.
+  /// @returns References updated by the fetch.
   ///
   /// @example
   /// ```ts
@@ -602,22 +1168,30 @@ impl Remote {
   ///
   /// // Providing an empty array fetches data using the default Refspec configured for the remote
   /// await remote.fetch([]);
+  ///
+  /// // Check which references are updated
+  /// const tips = await remote.fetch([]);
+  /// console.log(tips);
+  /// // [{ refname: 'refs/remotes/origin/main', oldOid: '...', newOid: '...', status: 'FastForward' }]
   /// ```
   pub fn fetch(
     &self,
+    env: Env,
     self_ref: Reference<Remote>,
     refspecs: Vec<String>,
     options: Option<FetchRemoteOptions>,
     signal: Option<AbortSignal>,
-  ) -> AsyncTask<FetchRemoteTask> {
-    AsyncTask::with_optional_signal(
+  ) -> crate::Result<AsyncTask<FetchRemoteTask>> {
+    let repo_path = self.inner.clone_owner(env)?.inner.path().to_path_buf();
+    Ok(AsyncTask::with_optional_signal(
       FetchRemoteTask {
         remote: RwLock::new(self_ref),
+        repo_path,
         refspecs,
         options,
       },
       signal,
-    )
+    ))
   }
 
   #[napi]
@@ -626,6 +1200,9 @@ impl Remote {
   /// Perform all the steps for a push.
   /// If no refspecs are passed, then the configured refspecs will be used.
   ///
+  /// Atomic pushes (`git push --atomic`) are not supported, because libgit2
+  /// can't request the `atomic` capability of the remote.
+  ///
   /// @category Remote/Methods
   /// @signature
   /// ```ts
@@ -634,13 +1211,17 @@ impl Remote {
   ///     refspecs: string[],
   ///     options?: PushOptions | null | undefined,
   ///     signal?: AbortSignal | null | undefined,
-  ///   ): Promise<void>;
+  ///   ): Promise<PushRefResult[]>;
   /// }
   /// ```
   ///
   /// @param {string[]} refspecs - Refspecs to push to remote.
   /// @param {FetchRemoteOptions} [options] - Options for push remote.
   /// @param {AbortSignal} [signal] Abort signal.
+  /// @returns Results of each pushed reference. References rejected by the remote are returned with
+  /// the `Rejected` status only if `throwOnRejected` is `false`.
+  /// @throws If the remote rejects any of the reference updates, unless `throwOnRejected` is `false`.
+  /// @throws If `atomic` is `true`, since atomic pushes are not supported.
   ///
   /// @example
   /// ```ts
@@ -659,22 +1240,44 @@ impl Remote {
   ///     password: '<personal access token>',
   ///   },
   /// });
+  ///
+  /// // Push only if the remote "main" branch is not changed by others.
+  /// const results = await remote.push(['+refs/heads/main:refs/heads/main'], {
+  ///   leases: [{ refname: 'refs/heads/main', expected: '<oid of the remote branch>' }],
+  /// });
+  /// console.log(results); // [{ refname: 'refs/heads/main', status: 'Ok' }]
   /// ```
   pub fn push(
     &self,
+    env: Env,
     self_ref: Reference<Remote>,
     refspecs: Vec<String>,
     options: Option<PushOptions>,
     signal: Option<AbortSignal>,
-  ) -> AsyncTask<PushRemoteTask> {
-    AsyncTask::with_optional_signal(
+  ) -> crate::Result<AsyncTask<PushRemoteTask>> {
+    if options.as_ref().and_then(|x| x.atomic).unwrap_or_default() {
+      return Err(
+        git2::Error::new(
+          git2::ErrorCode::Invalid,
+          git2::ErrorClass::Net,
+          "atomic push is not supported",
+        )
+        .into(),
+      );
+    }
+    let hooks = match options.as_ref().and_then(|x| x.run_hooks) {
+      Some(true) => Some(Hooks::new(&self.inner.clone_owner(env)?.inner)?),
+      _ => None,
+    };
+    Ok(AsyncTask::with_optional_signal(
       PushRemoteTask {
         remote: RwLock::new(self_ref),
         refspecs,
         options,
+        hooks,
       },
       signal,
-    )
+    ))
   }
 
   #[napi]
@@ -743,6 +1346,124 @@ impl Remote {
       signal,
     )
   }
+
+  #[napi]
+  /// Open a connection to the remote and receive its advertised references.
+  ///
+  /// No objects are downloaded. The connection is closed once the references have been
+  /// received; use `list()` to read them afterwards.
+  ///
+  /// @category Remote/Methods
+  /// @signature
+  /// ```ts
+  /// class Remote {
+  ///   connect(
+  ///     direction: Direction,
+  ///     options?: ConnectRemoteOptions | null | undefined,
+  ///     signal?: AbortSignal | null | undefined,
+  ///   ): Promise<void>;
+  /// }
+  /// ```
+  ///
+  /// @param {Direction} direction - Whether the connection is for fetching or pushing.
+  /// @param {ConnectRemoteOptions} [options] - Options for connecting to the remote.
+  /// @param {AbortSignal} [signal] Abort signal.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// const remote = repo.getRemote('origin');
+  ///
+  /// await remote.connect('Fetch');
+  /// const main = remote.list().find(x => x.name === 'refs/heads/main');
+  /// console.log(main?.oid);
+  /// ```
+  pub fn connect(
+    &self,
+    self_ref: Reference<Remote>,
+    direction: Direction,
+    options: Option<ConnectRemoteOptions>,
+    signal: Option<AbortSignal>,
+  ) -> AsyncTask<ConnectRemoteTask> {
+    AsyncTask::with_optional_signal(
+      ConnectRemoteTask {
+        remote: RwLock::new(self_ref),
+        direction,
+        options,
+      },
+      signal,
+    )
+  }
+
+  #[napi]
+  /// Get the remote repository's reference advertisement list.
+  ///
+  /// The list is available after `connect()` has been called, and is replaced on every new connection.
+  /// Peeled entries of annotated tags (`refs/tags/v1^{}`) are folded into the `peeled` field of the tag
+  /// they belong to.
+  ///
+  /// The capabilities advertised by the server are not exposed, because libgit2 doesn't provide
+  /// them through its public API.
+  ///
+  /// @category Remote/Methods
+  /// @signature
+  /// ```ts
+  /// class Remote {
+  ///   list(): RemoteHead[];
+  /// }
+  /// ```
+  ///
+  /// @returns References advertised by the remote, in the order they were advertised.
+  /// @throws Throws error if the remote has never connected.
+  pub fn list(&self) -> crate::Result<Vec<RemoteHead>> {
+    let mut heads: Vec<RemoteHead> = Vec::new();
+    for head in self.inner.list()? {
+      let name = head.name();
+      if let Some(base) = name.strip_suffix("^{}") {
+        if let Some(target) = heads.iter_mut().rev().find(|x| x.name == base) {
+          target.peeled = Some(head.oid().to_string());
+          continue;
+        }
+      }
+      heads.push(RemoteHead {
+        name: name.to_string(),
+        oid: head.oid().to_string(),
+        symref_target: head.symref_target().map(|x| x.to_string()),
+        peeled: None,
+      });
+    }
+    Ok(heads)
+  }
+
+  #[napi]
+  /// Get the statistics of the data transferred by the last fetch.
+  ///
+  /// @category Remote/Methods
+  /// @signature
+  /// ```ts
+  /// class Remote {
+  ///   stats(): TransferStats;
+  /// }
+  /// ```
+  ///
+  /// @returns Statistics of the transferred data.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// const remote = repo.getRemote('origin');
+  ///
+  /// await remote.fetch([]);
+  /// const stats = remote.stats();
+  /// console.log(`received ${stats.receivedObjects} objects (${stats.receivedBytes} bytes)`);
+  /// ```
+  pub fn stats(&self) -> TransferStats {
+    TransferStats::from(self.inner.stats())
+  }
 }
 
 #[napi]
@@ -856,4 +1577,255 @@ impl Repository {
     };
     Ok(remote)
   }
+
+  #[napi]
+  /// Create an anonymous remote.
+  ///
+  /// Create a remote with the given URL in-memory. You can use this when you have a URL instead of a remote's name.
+  /// Nothing is written to the repository's configuration.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   createAnonymousRemote(url: string): Remote;
+  /// }
+  /// ```
+  ///
+  /// @param {string} url - Remote url.
+  /// @returns Created anonymous remote.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// const remote = repo.createAnonymousRemote('https://github.com/toss/es-git');
+  /// await remote.fetch(['main']);
+  /// ```
+  pub fn create_anonymous_remote(&self, this: Reference<Repository>, env: Env, url: String) -> crate::Result<Remote> {
+    let remote = Remote {
+      inner: this.share_with(env, move |repo| {
+        repo
+          .inner
+          .remote_anonymous(&url)
+          .map_err(crate::Error::from)
+          .map_err(|e| e.into())
+      })?,
+    };
+    Ok(remote)
+  }
+
+  #[napi]
+  /// Create a detached remote.
+  ///
+  /// Create a remote with the given URL in-memory, which is not attached to this repository.
+  /// Detached remotes can be used to query a remote (e.g. list its references) without any local repository data.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   createDetachedRemote(url: string): Remote;
+  /// }
+  /// ```
+  ///
+  /// @param {string} url - Remote url.
+  /// @returns Created detached remote.
+  pub fn create_detached_remote(&self, this: Reference<Repository>, env: Env, url: String) -> crate::Result<Remote> {
+    let remote = Remote {
+      inner: this.share_with(env, move |_| {
+        git2::Remote::create_detached(url.as_str())
+          .map_err(crate::Error::from)
+          .map_err(|e| e.into())
+      })?,
+    };
+    Ok(remote)
+  }
+
+  #[napi]
+  /// Give a remote a new name.
+  ///
+  /// All remote-tracking branches and configuration settings for the remote are updated.
+  ///
+  /// A temporary in-memory remote cannot be given a name with this method.
+  ///
+  /// No loaded instances of the remote with the old name will change their name or their list of refspecs.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   renameRemote(name: string, newName: string): string[];
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The current name of the remote.
+  /// @param {string} newName - The new name the remote should bear.
+  /// @returns Non-default refspecs which cannot be renamed and are returned for further processing by the caller.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// repo.renameRemote('origin', 'upstream');
+  /// console.log(repo.remoteNames()); // ["upstream"]
+  /// ```
+  pub fn rename_remote(&self, name: String, new_name: String) -> crate::Result<Vec<String>> {
+    let problems = self.inner.remote_rename(&name, &new_name)?;
+    Ok(problems.into_iter().flatten().map(|x| x.to_owned()).collect::<Vec<_>>())
+  }
+
+  #[napi]
+  /// Delete an existing persisted remote.
+  ///
+  /// All remote-tracking branches and configuration settings for the remote will be removed.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   deleteRemote(name: string): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of the remote to delete.
+  /// @throws Throws error if remote does not exist.
+  pub fn delete_remote(&self, name: String) -> crate::Result<()> {
+    self.inner.remote_delete(&name)?;
+    Ok(())
+  }
+
+  #[napi]
+  /// Set the remote's URL in the configuration.
+  ///
+  /// Remote objects already in memory will not be affected. This assumes the common case of a single-url remote and will otherwise throw an error.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   remoteSetUrl(name: string, url: string): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of the remote.
+  /// @param {string} url - The URL to set.
+  pub fn remote_set_url(&self, name: String, url: String) -> crate::Result<()> {
+    self.inner.remote_set_url(&name, &url)?;
+    Ok(())
+  }
+
+  #[napi]
+  /// Set the remote's URL for pushing in the configuration.
+  ///
+  /// Remote objects already in memory will not be affected. This assumes the common case of a single-url remote and will otherwise throw an error.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   remoteSetPushurl(name: string, pushurl?: string | null | undefined): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of the remote.
+  /// @param {string} [pushurl] - The push URL to set. If not provided, the push URL is removed.
+  pub fn remote_set_pushurl(&self, name: String, pushurl: Option<String>) -> crate::Result<()> {
+    self.inner.remote_set_pushurl(&name, pushurl.as_deref())?;
+    Ok(())
+  }
+
+  #[napi]
+  /// Add a fetch refspec to the remote's configuration.
+  ///
+  /// Add the given refspec to the fetch list in the configuration. No loaded remote instances will be affected.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   remoteAddFetch(name: string, refspec: string): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of the remote.
+  /// @param {string} refspec - The fetch refspec to add.
+  pub fn remote_add_fetch(&self, name: String, refspec: String) -> crate::Result<()> {
+    self.inner.remote_add_fetch(&name, &refspec)?;
+    Ok(())
+  }
+
+  #[napi]
+  /// Add a push refspec to the remote's configuration.
+  ///
+  /// Add the given refspec to the push list in the configuration. No loaded remote instances will be affected.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   remoteAddPush(name: string, refspec: string): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - The name of the remote.
+  /// @param {string} refspec - The push refspec to add.
+  pub fn remote_add_push(&self, name: String, refspec: String) -> crate::Result<()> {
+    self.inner.remote_add_push(&name, &refspec)?;
+    Ok(())
+  }
+
+  #[napi(ts_args_type = "callback: (refname: string, remoteUrl: string, oid: string, isMerge: boolean) => boolean")]
+  /// Iterate over each entry of `FETCH_HEAD` calling `callback` on each.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   fetchheadForeach(
+  ///     callback: (refname: string, remoteUrl: string, oid: string, isMerge: boolean) => boolean,
+  ///   ): void;
+  /// }
+  /// ```
+  ///
+  /// @param {(refname: string, remoteUrl: string, oid: string, isMerge: boolean) => boolean} callback - Called with
+  /// the reference name, the remote URL, the object id and whether the entry is to be merged. If you wish to stop
+  /// iteration, return `false` in the callback.
+  /// @throws Throws error if `FETCH_HEAD` does not exist, or the error thrown by the callback.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// await repo.getRemote('origin').fetch(['main']);
+  /// repo.fetchheadForeach((refname, remoteUrl, oid, isMerge) => {
+  ///   console.log(refname, remoteUrl, oid, isMerge);
+  ///   // "refs/heads/main" "https://github.com/toss/es-git" "..." true
+  ///   return true;
+  /// });
+  /// ```
+  pub fn fetchhead_foreach(
+    &self,
+    callback: Function<FnArgs<(String, String, String, bool)>, bool>,
+  ) -> crate::Result<()> {
+    let mut error = None;
+    let result = self.inner.fetchhead_foreach(|refname, remote_url, oid, is_merge| {
+      let remote_url = String::from_utf8_lossy(remote_url).to_string();
+      match callback.call((refname.to_string(), remote_url, oid.to_string(), is_merge).into()) {
+        Ok(next) => next,
+        Err(e) => {
+          error = Some(e);
+          false
+        }
+      }
+    });
+    if let Some(error) = error {
+      return Err(error.into());
+    }
+    result?;
+    Ok(())
+  }
 }
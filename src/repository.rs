@@ -1,6 +1,8 @@
 use crate::annotated_commit::AnnotatedCommit;
 use crate::commit::Commit;
-use crate::remote::FetchOptions;
+use crate::js::{JsCallback, JsCallbackExt};
+use crate::remote::{fetch_deepen, FetchOptions};
+use crate::signing::split_signature;
 use crate::util;
 use crate::worktree::Worktree;
 use napi::bindgen_prelude::*;
@@ -210,7 +212,44 @@ impl RepositoryOpenOptions {
   }
 }
 
+#[napi(string_enum)]
+#[derive(Copy, Clone)]
+/// - `Auto` : Auto-detect (default). Local clone optimizations are used for local paths, but not for `file://` URLs.
+/// - `Local` : Bypass the git-aware transport even for `file://` URLs, and hardlink the objects where possible.
+/// - `None` : Never use local clone optimizations.
+/// - `NoLinks` : Bypass the git-aware transport, but don't hardlink the objects.
+pub enum CloneLocal {
+  Auto,
+  Local,
+  None,
+  NoLinks,
+}
+
+impl From<CloneLocal> for git2::build::CloneLocal {
+  fn from(value: CloneLocal) -> Self {
+    match value {
+      CloneLocal::Auto => git2::build::CloneLocal::Auto,
+      CloneLocal::Local => git2::build::CloneLocal::Local,
+      CloneLocal::None => git2::build::CloneLocal::None,
+      CloneLocal::NoLinks => git2::build::CloneLocal::NoLinks,
+    }
+  }
+}
+
 #[napi(object)]
+/// Options to customize the remote created by a clone.
+pub struct CloneRemoteOptions {
+  /// Fetch refspecs of the remote, which replace the default one.
+  pub fetch_refspecs: Option<Vec<String>>,
+  /// Push refspecs of the remote.
+  pub push_refspecs: Option<Vec<String>>,
+  /// Push URL of the remote.
+  pub pushurl: Option<String>,
+}
+
+pub type RemoteCreateCallback = JsCallback<FnArgs<(String, String)>, Option<CloneRemoteOptions>>;
+
+#[napi(object, object_to_js = false)]
 pub struct RepositoryCloneOptions {
   /// Indicate whether the repository will be cloned as a bare repository or
   /// not.
@@ -226,6 +265,28 @@ pub struct RepositoryCloneOptions {
   pub recursive: Option<bool>,
   /// Options which can be specified to various fetch operations.
   pub fetch: Option<FetchOptions>,
+  /// Create a mirror of the remote repository.
+  ///
+  /// This implies `bare`. All references of the remote are fetched into the
+  /// same names, and `remote.<name>.mirror` is set in the configuration.
+  ///
+  /// This is similar to `git clone --mirror`.
+  pub mirror: Option<bool>,
+  /// Set whether the local clone optimizations are used when cloning from a
+  /// repository on the local filesystem.
+  pub local: Option<CloneLocal>,
+  /// Do not check out the working tree after the clone.
+  ///
+  /// This is similar to `git clone --no-checkout`.
+  pub no_checkout: Option<bool>,
+  /// Name of the remote to create. Defaults to `origin`.
+  pub remote_name: Option<String>,
+  /// Callback to customize the remote before fetching.
+  ///
+  /// Called with the name and URL of the remote. If options are returned,
+  /// they are applied to the created remote.
+  #[napi(ts_type = "(name: string, url: string) => CloneRemoteOptions | null | undefined | void")]
+  pub remote_create: Option<RemoteCreateCallback>,
 }
 
 #[napi(object)]
@@ -279,6 +340,36 @@ impl Repository {
     self.inner.is_shallow()
   }
 
+  #[napi]
+  /// Get the commits at which the history of this shallow repository is cut off.
+  ///
+  /// These are the object ids listed in the `shallow` file of the git directory, which is shared
+  /// by all worktrees of the repository.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   shallowRoots(): string[];
+  /// }
+  /// ```
+  ///
+  /// @returns Object ids of the shallow roots. Returns an empty array if the repository is not shallow.
+  ///
+  /// @example
+  /// ```ts
+  /// import { cloneRepository } from 'es-git';
+  ///
+  /// const repo = await cloneRepository('https://github.com/toss/es-git', '/path/to/clone', {
+  ///   fetch: { depth: 1 },
+  /// });
+  /// console.log(repo.shallowRoots()); // ["a01e9888e46729ef4aa68953ba19b02a7a64eb82"]
+  /// ```
+  pub fn shallow_roots(&self) -> crate::Result<Vec<String>> {
+    let roots = shallow_roots(&self.inner)?.iter().map(|x| x.to_string()).collect();
+    Ok(roots)
+  }
+
   #[napi]
   /// Tests whether this repository is empty.
   ///
@@ -512,6 +603,20 @@ impl Repository {
   /// ```
   pub fn extract_signature(&self, oid: String) -> crate::Result<Option<ExtractedSignature>> {
     let oid_obj = git2::Oid::from_str(&oid)?;
+    let odb = self.inner.odb()?;
+    if let Ok(object) = odb.read(oid_obj) {
+      if object.kind() == git2::ObjectType::Tag {
+        let extracted = match split_signature(object.data()) {
+          Some((data, sig)) => {
+            let signature = std::str::from_utf8(sig)?.to_string();
+            let signed_data = std::str::from_utf8(data)?.to_string();
+            Some(ExtractedSignature { signature, signed_data })
+          }
+          None => None,
+        };
+        return Ok(extracted);
+      }
+    }
     match self.inner.extract_signature(&oid_obj, None) {
       Ok((sig, data)) => {
         let signature = std::str::from_utf8(&sig)?.to_string();
@@ -551,6 +656,21 @@ impl Repository {
   }
 }
 
+pub(crate) fn shallow_roots(repo: &git2::Repository) -> crate::Result<Vec<git2::Oid>> {
+  let content = match std::fs::read_to_string(repo.commondir().join("shallow")) {
+    Ok(content) => content,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+    Err(e) => return Err(e.into()),
+  };
+  let roots = content
+    .lines()
+    .map(|x| x.trim())
+    .filter(|x| !x.is_empty())
+    .map(git2::Oid::from_str)
+    .collect::<std::result::Result<Vec<_>, _>>()?;
+  Ok(roots)
+}
+
 fn update_submodules(repo: &git2::Repository) -> crate::Result<()> {
   fn add_subrepos(repo: &git2::Repository, list: &mut Vec<git2::Repository>) -> crate::Result<()> {
     for mut subm in repo.submodules()? {
@@ -770,16 +890,53 @@ impl Task for CloneRepositoryTask {
         builder.branch(branch);
       }
       if let Some(fetch) = &opts.fetch {
-        let fetch_options = fetch.to_git2_fetch_options();
+        let mut fetch_options = fetch.to_git2_fetch_options()?;
+        if fetch.deepens() && fetch.depth.is_none() {
+          // Start with a single commit, which is deepened after cloning.
+          fetch_options.depth(1);
+        }
         builder.fetch_options(fetch_options);
       }
       if let Some(true) = &opts.recursive {
         recursive = true;
       }
+      let mirror = opts.mirror.unwrap_or(false);
+      if mirror {
+        builder.bare(true);
+      }
+      if let Some(local) = opts.local {
+        builder.clone_local(local.into());
+      }
+      if let Some(true) = opts.no_checkout {
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.dry_run();
+        builder.with_checkout(checkout);
+      }
+      if mirror || opts.remote_name.is_some() || opts.remote_create.is_some() {
+        let remote_name = opts.remote_name.clone();
+        let remote_create = opts.remote_create.clone();
+        builder.remote_create(move |repo, name, url| {
+          let name = remote_name.as_deref().unwrap_or(name);
+          create_clone_remote(repo, name, url, mirror, remote_create.as_ref())
+        });
+      }
     }
     let inner = builder
       .clone(&self.url, Path::new(&self.path))
       .map_err(crate::Error::from)?;
+    if let Some(opts) = &self.options {
+      if let Some(fetch) = opts.fetch.as_ref().filter(|x| x.deepens()) {
+        let mut remote = inner
+          .find_remote(opts.remote_name.as_deref().unwrap_or("origin"))
+          .map_err(crate::Error::from)?;
+        fetch_deepen(inner.path(), fetch, |depth| {
+          let mut fetch_options = fetch.to_git2_fetch_options()?;
+          fetch_options.depth(depth);
+          remote.fetch::<&str>(&[], Some(&mut fetch_options), None)?;
+          Ok(())
+        })?;
+      }
+    }
     if recursive {
       update_submodules(&inner)?;
     }
@@ -791,6 +948,51 @@ impl Task for CloneRepositoryTask {
   }
 }
 
+fn create_clone_remote<'a>(
+  repo: &'a git2::Repository,
+  name: &str,
+  url: &str,
+  mirror: bool,
+  remote_create: Option<&RemoteCreateCallback>,
+) -> std::result::Result<git2::Remote<'a>, git2::Error> {
+  let options = match remote_create {
+    Some(callback) => callback
+      .invoke((name.to_string(), url.to_string()).into())
+      .map_err(|e| git2::Error::from_str(&e.reason))?,
+    None => None,
+  };
+  let mut fetch_refspecs = match &options {
+    Some(CloneRemoteOptions {
+      fetch_refspecs: Some(refspecs),
+      ..
+    }) if !refspecs.is_empty() => refspecs.clone(),
+    _ if mirror => vec!["+refs/*:refs/*".to_string()],
+    _ => vec![],
+  };
+  if fetch_refspecs.is_empty() {
+    repo.remote(name, url)?;
+  } else {
+    let first = fetch_refspecs.remove(0);
+    repo.remote_with_fetch(name, url, &first)?;
+    for refspec in fetch_refspecs {
+      repo.remote_add_fetch(name, &refspec)?;
+    }
+  }
+  if let Some(options) = options {
+    for refspec in options.push_refspecs.unwrap_or_default() {
+      repo.remote_add_push(name, &refspec)?;
+    }
+    if let Some(pushurl) = options.pushurl {
+      repo.remote_set_pushurl(name, Some(&pushurl))?;
+    }
+  }
+  if mirror {
+    repo.config()?.set_bool(&format!("remote.{name}.mirror"), true)?;
+  }
+  // Load the remote again to reflect the changes of the configuration.
+  repo.find_remote(name)
+}
+
 #[napi]
 /// Clone a remote repository.
 ///
@@ -0,0 +1,746 @@
+use crate::repository::Repository;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashSet;
+use std::io::Write;
+
+const FLUSH: &[u8] = b"0000";
+const DELIM: &[u8] = b"0001";
+const AGENT: &str = "agent=es-git";
+
+#[napi(object)]
+/// Update of a reference requested to receive-pack.
+pub struct ReceivePackUpdate {
+  /// Name of the reference (e.g. `refs/heads/main`).
+  pub refname: String,
+  /// Object id which the reference currently points to.
+  ///
+  /// The zero object id if the reference is created.
+  pub old_oid: String,
+  /// Object id which the reference is updated to.
+  ///
+  /// The zero object id if the reference is deleted.
+  pub new_oid: String,
+}
+
+#[napi(object)]
+pub struct ServeUploadPackOptions {
+  /// Version of the protocol, `0` or `2`.
+  ///
+  /// Clients request version 2 with `version=2` in the `Git-Protocol` header
+  /// of smart HTTP.
+  ///
+  /// Default: `0`.
+  pub protocol_version: Option<u32>,
+  #[napi(ts_type = "(chunk: Buffer) => void")]
+  /// Callback which receives the response in chunks while it is generated,
+  /// so the packfile doesn't have to be held in memory.
+  ///
+  /// If provided, the returned buffer is empty.
+  pub on_data: Option<Function<'static, Buffer, ()>>,
+}
+
+#[napi(object)]
+pub struct ServeReceivePackOptions {
+  #[napi(ts_type = "(update: ReceivePackUpdate) => string | null | undefined | void")]
+  /// Callback which is called for each reference update before it is
+  /// applied, like the `update` hook of git.
+  ///
+  /// Returning a message rejects the update with the message.
+  pub on_update: Option<Function<'static, ReceivePackUpdate, Option<String>>>,
+}
+
+fn protocol_error(message: impl Into<String>) -> crate::Error {
+  crate::Error::Protocol(message.into())
+}
+
+fn write_pkt(out: &mut Vec<u8>, data: impl AsRef<[u8]>) {
+  let data = data.as_ref();
+  out.extend_from_slice(format!("{:04x}", data.len() + 4).as_bytes());
+  out.extend_from_slice(data);
+}
+
+enum Pkt<'a> {
+  Flush,
+  Delim,
+  Data(&'a [u8]),
+}
+
+/// Reader of packet lines of a request.
+struct PktReader<'a> {
+  buf: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> PktReader<'a> {
+  fn new(buf: &'a [u8]) -> Self {
+    Self { buf, pos: 0 }
+  }
+
+  fn next_pkt(&mut self) -> crate::Result<Option<Pkt<'a>>> {
+    if self.pos >= self.buf.len() {
+      return Ok(None);
+    }
+    let len = self
+      .buf
+      .get(self.pos..self.pos + 4)
+      .and_then(|x| std::str::from_utf8(x).ok())
+      .and_then(|x| usize::from_str_radix(x, 16).ok())
+      .ok_or_else(|| protocol_error("invalid packet line length"))?;
+    if len == 0 || len == 1 {
+      self.pos += 4;
+      return Ok(Some(if len == 0 { Pkt::Flush } else { Pkt::Delim }));
+    }
+    if len < 4 {
+      return Err(protocol_error("unsupported special packet line"));
+    }
+    let data = self
+      .buf
+      .get(self.pos + 4..self.pos + len)
+      .ok_or_else(|| protocol_error("truncated packet line"))?;
+    self.pos += len;
+    Ok(Some(Pkt::Data(data.strip_suffix(b"\n").unwrap_or(data))))
+  }
+
+  fn rest(&self) -> &'a [u8] {
+    &self.buf[self.pos.min(self.buf.len())..]
+  }
+}
+
+/// Body of a response, which is either collected or streamed to the
+/// `onData` callback.
+struct Response<'a> {
+  buf: Vec<u8>,
+  on_data: Option<&'a Function<'static, Buffer, ()>>,
+}
+
+impl<'a> Response<'a> {
+  fn new(on_data: Option<&'a Function<'static, Buffer, ()>>) -> Self {
+    Self {
+      buf: Vec::new(),
+      on_data,
+    }
+  }
+
+  fn pkt(&mut self, data: impl AsRef<[u8]>) {
+    write_pkt(&mut self.buf, data);
+  }
+
+  fn raw(&mut self, data: &[u8]) {
+    self.buf.extend_from_slice(data);
+  }
+
+  /// Send the collected data to the `onData` callback, if any.
+  fn emit(&mut self) -> crate::Result<()> {
+    if let Some(on_data) = self.on_data {
+      if !self.buf.is_empty() {
+        on_data.call(Buffer::from(std::mem::take(&mut self.buf)))?;
+      }
+    }
+    Ok(())
+  }
+
+  fn finish(mut self) -> crate::Result<Vec<u8>> {
+    self.emit()?;
+    Ok(self.buf)
+  }
+}
+
+fn peel_tag(repo: &git2::Repository, oid: git2::Oid) -> git2::Oid {
+  let mut peeled = oid;
+  while let Ok(tag) = repo.find_tag(peeled) {
+    peeled = tag.target_id();
+  }
+  peeled
+}
+
+/// Create the reference advertisement, in the format of the `info/refs`
+/// response of smart HTTP.
+fn advertise(repo: &git2::Repository, service: &str, capabilities: &[String], upload: bool) -> crate::Result<Vec<u8>> {
+  let lines = advertised_refs(repo, upload)?;
+  let mut out = Vec::new();
+  write_pkt(&mut out, format!("# service={service}\n"));
+  out.extend_from_slice(FLUSH);
+  let capabilities = capabilities.join(" ");
+  match lines.split_first() {
+    Some(((oid, name), rest)) => {
+      write_pkt(&mut out, format!("{oid} {name}\0{capabilities}\n"));
+      for (oid, name) in rest {
+        write_pkt(&mut out, format!("{oid} {name}\n"));
+      }
+    }
+    None => write_pkt(
+      &mut out,
+      format!("{} capabilities^{{}}\0{capabilities}\n", git2::Oid::zero()),
+    ),
+  }
+  out.extend_from_slice(FLUSH);
+  Ok(out)
+}
+
+/// References of the advertisement, with `HEAD` and peeled tags for
+/// upload-pack.
+fn advertised_refs(repo: &git2::Repository, upload: bool) -> crate::Result<Vec<(git2::Oid, String)>> {
+  let mut refs = Vec::new();
+  for reference in repo.references()? {
+    let reference = reference?;
+    if let (Some(name), Some(oid)) = (reference.name(), reference.target()) {
+      refs.push((name.to_string(), oid));
+    }
+  }
+  refs.sort_by(|a, b| a.0.cmp(&b.0));
+  let mut lines = Vec::new();
+  if upload {
+    if let Ok(oid) = repo.refname_to_id("HEAD") {
+      lines.push((oid, "HEAD".to_string()));
+    }
+  }
+  for (name, oid) in refs {
+    lines.push((oid, name.clone()));
+    if upload {
+      let peeled = peel_tag(repo, oid);
+      if peeled != oid {
+        lines.push((peeled, format!("{name}^{{}}")));
+      }
+    }
+  }
+  Ok(lines)
+}
+
+/// Check the wanted objects are advertised or reachable from advertised
+/// commits, as `uploadpack.allowReachableSHA1InWant` of git.
+fn check_wants(repo: &git2::Repository, wants: &[git2::Oid]) -> crate::Result<()> {
+  let tips = advertised_refs(repo, true)?
+    .into_iter()
+    .map(|(oid, _)| oid)
+    .collect::<HashSet<_>>();
+  let mut pending = wants
+    .iter()
+    .filter(|x| !tips.contains(x))
+    .copied()
+    .collect::<HashSet<_>>();
+  if pending.is_empty() {
+    return Ok(());
+  }
+  let mut walk = repo.revwalk()?;
+  for tip in &tips {
+    if repo.find_commit(*tip).is_ok() {
+      walk.push(*tip)?;
+    }
+  }
+  for oid in walk {
+    pending.remove(&oid?);
+    if pending.is_empty() {
+      return Ok(());
+    }
+  }
+  match pending.iter().next() {
+    Some(oid) => Err(protocol_error(format!("not our ref {oid}"))),
+    None => Ok(()),
+  }
+}
+
+fn advertise_upload_pack(repo: &git2::Repository) -> crate::Result<Vec<u8>> {
+  let mut capabilities = vec![
+    "multi_ack_detailed".to_string(),
+    "side-band-64k".to_string(),
+    "side-band".to_string(),
+    "ofs-delta".to_string(),
+  ];
+  if let Some(target) = repo
+    .find_reference("HEAD")
+    .ok()
+    .and_then(|x| x.symbolic_target().map(String::from))
+  {
+    capabilities.push(format!("symref=HEAD:{target}"));
+  }
+  capabilities.push(AGENT.to_string());
+  advertise(repo, "git-upload-pack", &capabilities, true)
+}
+
+/// Capability advertisement of protocol version 2.
+fn advertise_upload_pack_v2() -> Vec<u8> {
+  let mut out = Vec::new();
+  for capability in ["version 2", AGENT, "ls-refs", "fetch", "object-format=sha1"] {
+    write_pkt(&mut out, format!("{capability}\n"));
+  }
+  out.extend_from_slice(FLUSH);
+  out
+}
+
+fn advertise_receive_pack(repo: &git2::Repository) -> crate::Result<Vec<u8>> {
+  let capabilities = ["report-status", "delete-refs", "ofs-delta", AGENT].map(String::from);
+  advertise(repo, "git-receive-pack", &capabilities, false)
+}
+
+/// Send a pack with objects reachable from `wants`, excluding objects
+/// reachable from `common`.
+///
+/// The pack is sent in packets of the first sideband if `max_data_len` is
+/// set, and emitted while it is generated.
+fn send_pack(
+  repo: &git2::Repository,
+  wants: &[git2::Oid],
+  common: &[git2::Oid],
+  response: &mut Response,
+  max_data_len: Option<usize>,
+) -> crate::Result<()> {
+  let mut builder = repo.packbuilder()?;
+  let mut walk = repo.revwalk()?;
+  for want in wants {
+    let mut oid = *want;
+    while let Ok(tag) = repo.find_tag(oid) {
+      builder.insert_object(oid, None)?;
+      oid = tag.target_id();
+    }
+    match repo.find_object(oid, None)?.kind() {
+      Some(git2::ObjectType::Commit) => walk.push(oid)?,
+      _ => builder.insert_recursive(oid, None)?,
+    }
+  }
+  for oid in common {
+    if repo.find_commit(*oid).is_ok() {
+      walk.hide(*oid)?;
+    }
+  }
+  builder.insert_walk(&mut walk)?;
+  let mut error = None;
+  let result = builder.foreach(|chunk| {
+    match max_data_len {
+      Some(max_data_len) => {
+        for chunk in chunk.chunks(max_data_len) {
+          let mut data = Vec::with_capacity(chunk.len() + 1);
+          data.push(1);
+          data.extend_from_slice(chunk);
+          response.pkt(data);
+        }
+      }
+      None => response.raw(chunk),
+    }
+    match response.emit() {
+      Ok(()) => true,
+      Err(e) => {
+        error = Some(e);
+        false
+      }
+    }
+  });
+  if let Some(error) = error {
+    return Err(error);
+  }
+  result?;
+  Ok(())
+}
+
+fn upload_pack(repo: &git2::Repository, request: &[u8], response: &mut Response) -> crate::Result<()> {
+  let mut reader = PktReader::new(request);
+  let mut wants = Vec::new();
+  let mut haves = Vec::new();
+  let mut capabilities = Vec::new();
+  let mut done = false;
+  while let Some(pkt) = reader.next_pkt()? {
+    let Pkt::Data(line) = pkt else {
+      continue;
+    };
+    let line = std::str::from_utf8(line)?;
+    if let Some(rest) = line.strip_prefix("want ") {
+      let mut parts = rest.split(' ');
+      wants.push(git2::Oid::from_str(parts.next().unwrap_or_default())?);
+      if wants.len() == 1 {
+        capabilities = parts.map(String::from).collect();
+      }
+    } else if let Some(oid) = line.strip_prefix("have ") {
+      haves.push(git2::Oid::from_str(oid)?);
+    } else if line == "done" {
+      done = true;
+      break;
+    } else if line.starts_with("shallow ") || line.starts_with("deepen") {
+      return Err(protocol_error("shallow fetch is not supported"));
+    } else {
+      return Err(protocol_error(format!("unexpected line: {line}")));
+    }
+  }
+  if wants.is_empty() {
+    return Err(protocol_error("no objects are wanted"));
+  }
+  check_wants(repo, &wants)?;
+  let odb = repo.odb()?;
+  let common = haves.into_iter().filter(|x| odb.exists(*x)).collect::<Vec<_>>();
+  let has_capability = |name: &str| capabilities.iter().any(|x| x == name);
+
+  if !done {
+    // Response of a negotiation round, the client sends more haves or "done" next.
+    if has_capability("multi_ack_detailed") {
+      for oid in &common {
+        response.pkt(format!("ACK {oid} common\n"));
+      }
+    } else if let Some(oid) = common.first() {
+      response.pkt(format!("ACK {oid}\n"));
+      return Ok(());
+    }
+    response.pkt("NAK\n");
+    return Ok(());
+  }
+
+  match common.last() {
+    Some(oid) => response.pkt(format!("ACK {oid}\n")),
+    None => response.pkt("NAK\n"),
+  }
+  let max_data_len = match (has_capability("side-band-64k"), has_capability("side-band")) {
+    (true, _) => Some(65515),
+    (false, true) => Some(995),
+    (false, false) => None,
+  };
+  send_pack(repo, &wants, &common, response, max_data_len)?;
+  if max_data_len.is_some() {
+    response.raw(FLUSH);
+  }
+  Ok(())
+}
+
+/// Serve a command request of protocol version 2.
+fn upload_pack_v2(repo: &git2::Repository, request: &[u8], response: &mut Response) -> crate::Result<()> {
+  let mut reader = PktReader::new(request);
+  let mut command = None;
+  while let Some(Pkt::Data(line)) = reader.next_pkt()? {
+    let line = std::str::from_utf8(line)?;
+    if let Some(name) = line.strip_prefix("command=") {
+      command = Some(name.to_string());
+    } else if let Some(format) = line.strip_prefix("object-format=") {
+      if format != "sha1" {
+        return Err(protocol_error(format!("unsupported object format: {format}")));
+      }
+    }
+  }
+  let mut args = Vec::new();
+  while let Some(Pkt::Data(line)) = reader.next_pkt()? {
+    args.push(std::str::from_utf8(line)?);
+  }
+  match command.as_deref() {
+    Some("ls-refs") => ls_refs(repo, &args, response),
+    Some("fetch") => fetch(repo, &args, response),
+    Some(command) => Err(protocol_error(format!("unknown command: {command}"))),
+    None => Err(protocol_error("no command is requested")),
+  }
+}
+
+/// `ls-refs` command of protocol version 2.
+fn ls_refs(repo: &git2::Repository, args: &[&str], response: &mut Response) -> crate::Result<()> {
+  let peel = args.contains(&"peel");
+  let symrefs = args.contains(&"symrefs");
+  let prefixes = args
+    .iter()
+    .filter_map(|x| x.strip_prefix("ref-prefix "))
+    .collect::<Vec<_>>();
+  let mut names = vec!["HEAD".to_string()];
+  let mut refs = repo
+    .references()?
+    .filter_map(|x| x.ok().and_then(|x| x.name().map(String::from)))
+    .collect::<Vec<_>>();
+  refs.sort();
+  names.extend(refs);
+  for name in names {
+    if !prefixes.is_empty() && !prefixes.iter().any(|x| name.starts_with(x)) {
+      continue;
+    }
+    let Ok(reference) = repo.find_reference(&name) else {
+      continue;
+    };
+    let Some(oid) = reference.resolve().ok().and_then(|x| x.target()) else {
+      continue;
+    };
+    let mut line = format!("{oid} {name}");
+    if let (true, Some(target)) = (symrefs, reference.symbolic_target()) {
+      line.push_str(&format!(" symref-target:{target}"));
+    }
+    let peeled = peel_tag(repo, oid);
+    if peel && peeled != oid {
+      line.push_str(&format!(" peeled:{peeled}"));
+    }
+    response.pkt(format!("{line}\n"));
+  }
+  response.raw(FLUSH);
+  Ok(())
+}
+
+/// `fetch` command of protocol version 2.
+fn fetch(repo: &git2::Repository, args: &[&str], response: &mut Response) -> crate::Result<()> {
+  let mut wants = Vec::new();
+  let mut haves = Vec::new();
+  let mut done = false;
+  for arg in args {
+    if let Some(oid) = arg.strip_prefix("want ") {
+      wants.push(git2::Oid::from_str(oid)?);
+    } else if let Some(oid) = arg.strip_prefix("have ") {
+      haves.push(git2::Oid::from_str(oid)?);
+    } else if *arg == "done" {
+      done = true;
+    } else if arg.starts_with("shallow ") || arg.starts_with("deepen") {
+      return Err(protocol_error("shallow fetch is not supported"));
+    } else if arg.starts_with("filter ") || arg.starts_with("want-ref ") {
+      return Err(protocol_error(format!("unsupported argument: {arg}")));
+    }
+  }
+  if wants.is_empty() {
+    return Err(protocol_error("no objects are wanted"));
+  }
+  check_wants(repo, &wants)?;
+  let odb = repo.odb()?;
+  let common = haves.into_iter().filter(|x| odb.exists(*x)).collect::<Vec<_>>();
+
+  if !done {
+    response.pkt("acknowledgments\n");
+    if common.is_empty() {
+      response.pkt("NAK\n");
+      response.raw(FLUSH);
+      return Ok(());
+    }
+    for oid in &common {
+      response.pkt(format!("ACK {oid}\n"));
+    }
+    response.pkt("ready\n");
+    response.raw(DELIM);
+  }
+  response.pkt("packfile\n");
+  send_pack(repo, &wants, &common, response, Some(65515))?;
+  response.raw(FLUSH);
+  Ok(())
+}
+
+struct ReceiveCommand {
+  old: git2::Oid,
+  new: git2::Oid,
+  refname: String,
+}
+
+fn write_pack(repo: &git2::Repository, pack: &[u8]) -> crate::Result<()> {
+  // Skip empty packs, which only have the header and the trailer.
+  match pack.get(8..12) {
+    Some(count) if count != [0, 0, 0, 0] => {}
+    _ => return Ok(()),
+  }
+  let odb = repo.odb()?;
+  let mut writer = odb.packwriter()?;
+  writer.write_all(pack)?;
+  writer.commit()?;
+  Ok(())
+}
+
+/// Apply the reference update, returning the reason if it is rejected.
+fn update_ref(
+  repo: &git2::Repository,
+  command: &ReceiveCommand,
+  on_update: Option<&Function<'static, ReceivePackUpdate, Option<String>>>,
+) -> crate::Result<Option<String>> {
+  let ReceiveCommand { old, new, refname } = command;
+  if !refname.starts_with("refs/") {
+    return Ok(Some("funny refname".to_string()));
+  }
+  let current = repo.refname_to_id(refname).unwrap_or_else(|_| git2::Oid::zero());
+  if current != *old {
+    return Ok(Some("stale info".to_string()));
+  }
+  if !repo.is_bare() {
+    let head = repo.find_reference("HEAD")?;
+    if head.symbolic_target() == Some(refname.as_str()) {
+      return Ok(Some("branch is currently checked out".to_string()));
+    }
+  }
+  if !new.is_zero() && !repo.odb()?.exists(*new) {
+    return Ok(Some("missing necessary objects".to_string()));
+  }
+  if let Some(callback) = on_update {
+    let update = ReceivePackUpdate {
+      refname: refname.clone(),
+      old_oid: old.to_string(),
+      new_oid: new.to_string(),
+    };
+    if let Some(reason) = callback.call(update)? {
+      return Ok(Some(reason));
+    }
+  }
+  let result = match (old.is_zero(), new.is_zero()) {
+    // libgit2 deletes the reference only if it still points to the target
+    // which is read here, so checking the target makes it compare-and-delete.
+    (_, true) => match repo.find_reference(refname) {
+      Ok(reference) if reference.target() != Some(*old) => return Ok(Some("stale info".to_string())),
+      Ok(mut reference) => reference.delete(),
+      Err(e) => Err(e),
+    },
+    (true, false) => repo.reference(refname, *new, false, "push").map(|_| ()),
+    (false, false) => repo.reference_matching(refname, *new, true, *old, "push").map(|_| ()),
+  };
+  Ok(result.err().map(|e| e.message().to_string()))
+}
+
+fn receive_pack(
+  repo: &git2::Repository,
+  request: &[u8],
+  on_update: Option<&Function<'static, ReceivePackUpdate, Option<String>>>,
+) -> crate::Result<Vec<u8>> {
+  let mut reader = PktReader::new(request);
+  let mut commands = Vec::new();
+  let mut capabilities = Vec::new();
+  while let Some(Pkt::Data(line)) = reader.next_pkt()? {
+    let line = std::str::from_utf8(line)?;
+    let (command, caps) = line.split_once('\0').unwrap_or((line, ""));
+    if commands.is_empty() {
+      capabilities = caps.split(' ').map(String::from).collect::<Vec<_>>();
+    }
+    let mut parts = command.splitn(3, ' ');
+    let (Some(old), Some(new), Some(refname)) = (parts.next(), parts.next(), parts.next()) else {
+      return Err(protocol_error(format!("invalid command: {command}")));
+    };
+    commands.push(ReceiveCommand {
+      old: git2::Oid::from_str(old)?,
+      new: git2::Oid::from_str(new)?,
+      refname: refname.to_string(),
+    });
+  }
+
+  let unpacked = write_pack(repo, reader.rest());
+  let mut results = Vec::with_capacity(commands.len());
+  for command in &commands {
+    let reason = match &unpacked {
+      Ok(()) => update_ref(repo, command, on_update)?,
+      Err(_) => Some("unpacker error".to_string()),
+    };
+    results.push((command.refname.as_str(), reason));
+  }
+
+  let mut out = Vec::new();
+  if capabilities.iter().any(|x| x == "report-status") {
+    match &unpacked {
+      Ok(()) => write_pkt(&mut out, "unpack ok\n"),
+      Err(e) => write_pkt(&mut out, format!("unpack {e}\n")),
+    }
+    for (refname, reason) in results {
+      match reason {
+        Some(reason) => write_pkt(&mut out, format!("ng {refname} {reason}\n")),
+        None => write_pkt(&mut out, format!("ok {refname}\n")),
+      }
+    }
+    out.extend_from_slice(FLUSH);
+  }
+  Ok(out)
+}
+
+#[napi]
+impl Repository {
+  #[napi]
+  /// Serve a request of the upload-pack service, which sends objects to
+  /// clients fetching from this repository.
+  ///
+  /// Requests and responses are in the format of smart HTTP with protocol
+  /// version 0 or 2. If the request is not provided or empty, returns the
+  /// reference advertisement (the response of
+  /// `info/refs?service=git-upload-pack`), or the capability advertisement for
+  /// protocol version 2. Otherwise, the request is the body of
+  /// `POST git-upload-pack`.
+  ///
+  /// Only objects which are advertised or reachable from advertised commits
+  /// can be wanted. Shallow and partial fetches are not supported.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   serveUploadPack(
+  ///     request?: Buffer | null | undefined,
+  ///     options?: ServeUploadPackOptions | null | undefined,
+  ///   ): Buffer;
+  /// }
+  /// ```
+  ///
+  /// @param {Buffer} [request] - Body of the request.
+  /// @param {ServeUploadPackOptions} [options] - Options for upload-pack.
+  /// @returns Body of the response, or an empty buffer if `onData` is set.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository, registerTransport } from 'es-git';
+  ///
+  /// const server = await openRepository('/path/to/server');
+  /// registerTransport('myproto', (url, service, body) => {
+  ///   switch (service) {
+  ///     case 'UploadPackLs':
+  ///     case 'UploadPack':
+  ///       return server.serveUploadPack(body);
+  ///     case 'ReceivePackLs':
+  ///     case 'ReceivePack':
+  ///       return server.serveReceivePack(body);
+  ///   }
+  /// });
+  /// ```
+  pub fn serve_upload_pack(
+    &self,
+    request: Option<Buffer>,
+    options: Option<ServeUploadPackOptions>,
+  ) -> crate::Result<Buffer> {
+    let version = options.as_ref().and_then(|x| x.protocol_version).unwrap_or(0);
+    let on_data = options.and_then(|x| x.on_data);
+    let mut response = Response::new(on_data.as_ref());
+    match (request.as_deref(), version) {
+      (None | Some([]), 2) => response.raw(&advertise_upload_pack_v2()),
+      (None | Some([]), _) => response.raw(&advertise_upload_pack(&self.inner)?),
+      (Some(request), 2) => upload_pack_v2(&self.inner, request, &mut response)?,
+      (Some(request), _) => upload_pack(&self.inner, request, &mut response)?,
+    }
+    Ok(response.finish()?.into())
+  }
+
+  #[napi]
+  /// Serve a request of the receive-pack service, which receives objects and
+  /// updates references from clients pushing to this repository.
+  ///
+  /// Requests and responses are in the format of smart HTTP with protocol
+  /// version 0. If the request is not provided or empty, returns the reference
+  /// advertisement (the response of `info/refs?service=git-receive-pack`).
+  /// Otherwise, the request is the body of `POST git-receive-pack`.
+  ///
+  /// Protocol version 2 doesn't define receive-pack, so pushes always use
+  /// protocol version 0 as git does.
+  ///
+  /// Updating the checked out branch of a non-bare repository is rejected.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   serveReceivePack(
+  ///     request?: Buffer | null | undefined,
+  ///     options?: ServeReceivePackOptions | null | undefined,
+  ///   ): Buffer;
+  /// }
+  /// ```
+  ///
+  /// @param {Buffer} [request] - Body of the request.
+  /// @param {ServeReceivePackOptions} [options] - Options for receive-pack.
+  /// @returns Body of the response.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const server = await openRepository('/path/to/server');
+  /// const response = server.serveReceivePack(request, {
+  ///   onUpdate: update => {
+  ///     if (update.refname === 'refs/heads/main' && update.newOid === '0000000000000000000000000000000000000000') {
+  ///       return 'deleting main branch is not allowed';
+  ///     }
+  ///   },
+  /// });
+  /// ```
+  pub fn serve_receive_pack(
+    &self,
+    request: Option<Buffer>,
+    options: Option<ServeReceivePackOptions>,
+  ) -> crate::Result<Buffer> {
+    let on_update = options.and_then(|x| x.on_update);
+    let response = match request.as_deref() {
+      None | Some([]) => advertise_receive_pack(&self.inner)?,
+      Some(request) => receive_pack(&self.inner, request, on_update.as_ref())?,
+    };
+    Ok(response.into())
+  }
+}
@@ -0,0 +1,579 @@
+use crate::repository::Repository;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[napi(string_enum)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Format of signatures. Same as the `gpg.format` git config.
+///
+/// - `OpenPgp` : Sign with OpenPGP keys using `gpg`. (default)
+/// - `X509` : Sign with X.509 certificates using `gpgsm`.
+/// - `Ssh` : Sign with SSH keys using `ssh-keygen`.
+pub enum SigningFormat {
+  #[default]
+  OpenPgp,
+  X509,
+  Ssh,
+}
+
+impl SigningFormat {
+  fn from_config(value: &str) -> Option<Self> {
+    match value {
+      "openpgp" => Some(Self::OpenPgp),
+      "x509" => Some(Self::X509),
+      "ssh" => Some(Self::Ssh),
+      _ => None,
+    }
+  }
+
+  fn config_name(&self) -> &'static str {
+    match self {
+      Self::OpenPgp => "openpgp",
+      Self::X509 => "x509",
+      Self::Ssh => "ssh",
+    }
+  }
+
+  fn default_program(&self) -> &'static str {
+    match self {
+      Self::OpenPgp => "gpg",
+      Self::X509 => "gpgsm",
+      Self::Ssh => "ssh-keygen",
+    }
+  }
+
+  /// Detect the format of a signature from its armor header.
+  pub(crate) fn detect(signature: &str) -> Option<Self> {
+    let signature = signature.trim_start();
+    if signature.starts_with("-----BEGIN PGP SIGNATURE-----") || signature.starts_with("-----BEGIN PGP MESSAGE-----") {
+      Some(Self::OpenPgp)
+    } else if signature.starts_with("-----BEGIN SIGNED MESSAGE-----") {
+      Some(Self::X509)
+    } else if signature.starts_with("-----BEGIN SSH SIGNATURE-----") {
+      Some(Self::Ssh)
+    } else {
+      None
+    }
+  }
+}
+
+#[napi(object)]
+pub struct SignerOptions {
+  #[napi(ts_type = "(payload: string) => string")]
+  /// Callback which signs the given payload and returns the armored
+  /// signature.
+  ///
+  /// If provided, all other options are ignored.
+  pub callback: Option<Function<'static, String, String>>,
+  /// Format of the signature.
+  ///
+  /// If not provided, `gpg.format` config will be used. Defaults to `OpenPgp`.
+  pub format: Option<SigningFormat>,
+  /// Key to sign with.
+  ///
+  /// For `OpenPgp` and `X509`, this is the key id passed to the signing
+  /// program. For `Ssh`, this is the path to the private key, or a public
+  /// key (file or literal `ssh-*` / `key::` string) whose private key is
+  /// held by `ssh-agent`.
+  ///
+  /// If not provided, `user.signingKey` config will be used.
+  pub key: Option<String>,
+  /// Program used to create the signature.
+  ///
+  /// If not provided, `gpg.<format>.program` config (or `gpg.program` for
+  /// `OpenPgp`) will be used. Defaults to `gpg`, `gpgsm` or `ssh-keygen`
+  /// depending on the format.
+  pub program: Option<String>,
+}
+
+#[napi(object)]
+pub struct VerifySignatureOptions {
+  /// Path to the allowed signers file used to verify SSH signatures.
+  ///
+  /// If not provided, `gpg.ssh.allowedSignersFile` config will be used.
+  pub allowed_signers: Option<String>,
+  /// Program used to verify the signature.
+  ///
+  /// If not provided, same program as for signing will be used.
+  pub program: Option<String>,
+}
+
+#[napi(object)]
+pub struct SignatureVerification {
+  /// Format of the signature.
+  pub format: SigningFormat,
+  /// Whether the signature is cryptographically good.
+  ///
+  /// For `Ssh`, the key must also be found in the allowed signers file, if
+  /// one is configured.
+  pub valid: bool,
+  /// Identity of the signer.
+  ///
+  /// User id for `OpenPgp` and `X509`, principal from the allowed signers
+  /// file for `Ssh`.
+  pub signer: Option<String>,
+  /// Id of the key which made the signature.
+  pub key: Option<String>,
+  /// Fingerprint of the key which made the signature.
+  pub fingerprint: Option<String>,
+  /// Trust level of the key.
+  ///
+  /// One of `undefined`, `never`, `marginal`, `fully` or `ultimate`. SSH keys
+  /// found in the allowed signers file are `fully` trusted.
+  pub trust: Option<String>,
+  /// Raw output of the verification program.
+  pub output: String,
+}
+
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A file in the temporary directory which is removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+  /// Create a new file, which is never an existing file or a symlink planted
+  /// under the same name.
+  fn new(contents: &[u8]) -> crate::Result<Self> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    loop {
+      let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|x| x.subsec_nanos())
+        .unwrap_or_default();
+      let name = format!(
+        "es-git-{}-{}-{nanos:08x}",
+        std::process::id(),
+        TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+      );
+      let path = std::env::temp_dir().join(name);
+      match options.open(&path) {
+        Ok(mut file) => {
+          let temp_file = Self(path);
+          file.write_all(contents)?;
+          return Ok(temp_file);
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+        Err(e) => return Err(e.into()),
+      }
+    }
+  }
+
+  fn path(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_file(&self.0);
+  }
+}
+
+fn run_program(program: &str, args: &[&str], input: &[u8]) -> crate::Result<std::process::Output> {
+  let mut child = Command::new(program)
+    .args(args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|e| crate::Error::Signing(format!("failed to run '{program}': {e}")))?;
+  let mut stdin = child.stdin.take().expect("stdin is piped");
+  let input = input.to_vec();
+  let writer = std::thread::spawn(move || stdin.write_all(&input));
+  let output = child.wait_with_output()?;
+  writer
+    .join()
+    .map_err(|_| crate::Error::Signing(format!("failed to write to '{program}'")))??;
+  Ok(output)
+}
+
+fn config_string(repo: &git2::Repository, name: &str) -> Option<String> {
+  repo.config().ok().and_then(|config| config.get_string(name).ok())
+}
+
+fn config_format(repo: &git2::Repository) -> SigningFormat {
+  config_string(repo, "gpg.format")
+    .and_then(|x| SigningFormat::from_config(&x))
+    .unwrap_or_default()
+}
+
+fn config_program(repo: &git2::Repository, format: SigningFormat) -> String {
+  config_string(repo, &format!("gpg.{}.program", format.config_name()))
+    .or_else(|| match format {
+      SigningFormat::OpenPgp => config_string(repo, "gpg.program"),
+      _ => None,
+    })
+    .unwrap_or_else(|| format.default_program().to_string())
+}
+
+fn gpg_sign(program: &str, key: &str, payload: &str) -> crate::Result<String> {
+  let output = run_program(program, &["--status-fd=2", "-bsau", key], payload.as_bytes())?;
+  let status = String::from_utf8_lossy(&output.stderr);
+  if !output.status.success() || !status.lines().any(|x| x.starts_with("[GNUPG:] SIG_CREATED ")) {
+    return Err(crate::Error::Signing(format!(
+      "'{program}' failed to sign the data: {status}"
+    )));
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn ssh_sign(program: &str, key: &str, payload: &str) -> crate::Result<String> {
+  let literal_key = key
+    .strip_prefix("key::")
+    .or_else(|| key.starts_with("ssh-").then_some(key));
+  let key_file = match literal_key {
+    Some(literal) => Some(TempFile::new(literal.as_bytes())?),
+    None => None,
+  };
+  let key_path = match &key_file {
+    Some(file) => file.path().to_string_lossy().to_string(),
+    None => key.to_string(),
+  };
+  let mut args = vec!["-Y", "sign", "-n", "git", "-f", key_path.as_str()];
+  if key_file.is_some() {
+    args.push("-U");
+  }
+  let output = run_program(program, &args, payload.as_bytes())?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(crate::Error::Signing(format!(
+      "'{program}' failed to sign the data: {stderr}"
+    )));
+  }
+  Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Sign the payload with the given signer.
+pub(crate) fn sign_payload(repo: &git2::Repository, signer: &SignerOptions, payload: &str) -> crate::Result<String> {
+  if let Some(callback) = &signer.callback {
+    let signature = callback.call(payload.to_string())?;
+    return Ok(signature);
+  }
+  let format = signer.format.unwrap_or_else(|| config_format(repo));
+  let program = signer
+    .program
+    .to_owned()
+    .unwrap_or_else(|| config_program(repo, format));
+  let key = signer.key.to_owned().or_else(|| config_string(repo, "user.signingKey"));
+  match format {
+    SigningFormat::OpenPgp | SigningFormat::X509 => {
+      let key = match key {
+        Some(key) => key,
+        None => repo
+          .signature()
+          .ok()
+          .and_then(|x| x.email().map(|email| email.to_string()))
+          .ok_or_else(|| crate::Error::Signing("signing key is not configured".to_string()))?,
+      };
+      gpg_sign(&program, &key, payload)
+    }
+    SigningFormat::Ssh => {
+      let key = key.ok_or_else(|| crate::Error::Signing("signing key is not configured".to_string()))?;
+      ssh_sign(&program, &key, payload)
+    }
+  }
+}
+
+fn gpg_verify(
+  format: SigningFormat,
+  program: &str,
+  signature: &str,
+  payload: &[u8],
+) -> crate::Result<SignatureVerification> {
+  let signature_file = TempFile::new(signature.as_bytes())?;
+  let signature_path = signature_file.path().to_string_lossy().to_string();
+  let output = run_program(
+    program,
+    &["--keyid-format=long", "--status-fd=1", "--verify", &signature_path, "-"],
+    payload,
+  )?;
+  let status = String::from_utf8_lossy(&output.stdout).to_string();
+  let mut verification = SignatureVerification {
+    format,
+    valid: false,
+    signer: None,
+    key: None,
+    fingerprint: None,
+    trust: None,
+    output: format!("{}{}", String::from_utf8_lossy(&output.stderr), status),
+  };
+  for line in status.lines() {
+    let Some(line) = line.strip_prefix("[GNUPG:] ") else {
+      continue;
+    };
+    let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+    match keyword {
+      "GOODSIG" | "BADSIG" | "EXPSIG" | "EXPKEYSIG" | "REVKEYSIG" => {
+        let (key, signer) = rest.split_once(' ').unwrap_or((rest, ""));
+        verification.valid = keyword == "GOODSIG";
+        verification.key = Some(key.to_string());
+        if !signer.is_empty() {
+          verification.signer = Some(signer.to_string());
+        }
+      }
+      "ERRSIG" => {
+        verification.key = rest.split(' ').next().map(|x| x.to_string());
+      }
+      "VALIDSIG" => {
+        verification.fingerprint = rest.split(' ').next().map(|x| x.to_string());
+      }
+      _ => {
+        if let Some(trust) = keyword.strip_prefix("TRUST_") {
+          verification.trust = Some(trust.to_lowercase());
+        }
+      }
+    }
+  }
+  if !output.status.success() {
+    verification.valid = false;
+  }
+  Ok(verification)
+}
+
+fn ssh_fingerprint(output: &str) -> Option<String> {
+  output
+    .lines()
+    .find_map(|line| line.rsplit_once(" key ").map(|(_, x)| x.trim().to_string()))
+}
+
+fn ssh_verify(
+  program: &str,
+  allowed_signers: Option<&str>,
+  signature: &str,
+  payload: &[u8],
+) -> crate::Result<SignatureVerification> {
+  let signature_file = TempFile::new(signature.as_bytes())?;
+  let signature_path = signature_file.path().to_string_lossy().to_string();
+  let Some(allowed_signers) = allowed_signers else {
+    // Without allowed signers, only the signature itself can be checked.
+    let output = run_program(
+      program,
+      &["-Y", "check-novalidate", "-n", "git", "-s", &signature_path],
+      payload,
+    )?;
+    return Ok(ssh_verification(&output, None));
+  };
+  let output = run_program(
+    program,
+    &["-Y", "find-principals", "-f", allowed_signers, "-s", &signature_path],
+    &[],
+  )?;
+  let principal = match output.status.success() {
+    true => String::from_utf8_lossy(&output.stdout)
+      .lines()
+      .next()
+      .map(|x| x.trim().to_string())
+      .filter(|x| !x.is_empty()),
+    false => None,
+  };
+  // Like git, a signature by a key which isn't in the allowed signers file is
+  // not valid, even if it's cryptographically good.
+  let Some(principal) = principal else {
+    let mut verification = ssh_verification(&output, None);
+    verification.valid = false;
+    return Ok(verification);
+  };
+  let output = run_program(
+    program,
+    &[
+      "-Y",
+      "verify",
+      "-n",
+      "git",
+      "-f",
+      allowed_signers,
+      "-I",
+      &principal,
+      "-s",
+      &signature_path,
+    ],
+    payload,
+  )?;
+  Ok(ssh_verification(&output, Some(principal)))
+}
+
+/// Build the verification from the output of `ssh-keygen`, where the key is
+/// `fully` trusted if it's found in the allowed signers file as the principal.
+fn ssh_verification(output: &std::process::Output, principal: Option<String>) -> SignatureVerification {
+  let text = format!(
+    "{}{}",
+    String::from_utf8_lossy(&output.stdout),
+    String::from_utf8_lossy(&output.stderr)
+  );
+  let valid = output.status.success();
+  let trust = match principal {
+    Some(_) if valid => "fully",
+    _ => "undefined",
+  };
+  SignatureVerification {
+    format: SigningFormat::Ssh,
+    valid,
+    trust: Some(trust.to_string()),
+    signer: principal,
+    key: None,
+    fingerprint: ssh_fingerprint(&text),
+    output: text,
+  }
+}
+
+/// Split a raw object buffer into the signed payload and the trailing
+/// signature block, as used by signed tags.
+///
+/// Like git, the last line starting a signature block wins.
+pub(crate) fn split_signature(content: &[u8]) -> Option<(&[u8], &[u8])> {
+  let mut found = None;
+  let mut start = 0;
+  while start < content.len() {
+    let rest = &content[start..];
+    let end = rest
+      .iter()
+      .position(|x| *x == b'\n')
+      .map(|x| x + 1)
+      .unwrap_or(rest.len());
+    let is_signature = std::str::from_utf8(&rest[..end])
+      .ok()
+      .filter(|line| line.starts_with("-----BEGIN "))
+      .and_then(SigningFormat::detect)
+      .is_some();
+    if is_signature {
+      found = Some(start);
+    }
+    start += end;
+  }
+  found.map(|x| content.split_at(x))
+}
+
+/// Verify the signature of the signed payload.
+pub(crate) fn verify_signature(
+  repo: &git2::Repository,
+  signature: &str,
+  payload: &[u8],
+  options: Option<VerifySignatureOptions>,
+) -> crate::Result<SignatureVerification> {
+  let format =
+    SigningFormat::detect(signature).ok_or_else(|| crate::Error::Signing("unknown signature format".to_string()))?;
+  let (allowed_signers, program) = match options {
+    Some(opts) => (opts.allowed_signers, opts.program),
+    None => (None, None),
+  };
+  let program = program.unwrap_or_else(|| config_program(repo, format));
+  match format {
+    SigningFormat::OpenPgp | SigningFormat::X509 => gpg_verify(format, &program, signature, payload),
+    SigningFormat::Ssh => {
+      let allowed_signers = allowed_signers.or_else(|| {
+        repo
+          .config()
+          .ok()
+          .and_then(|config| config.get_path("gpg.ssh.allowedSignersFile").ok())
+          .map(|x| x.to_string_lossy().to_string())
+      });
+      ssh_verify(&program, allowed_signers.as_deref(), signature, payload)
+    }
+  }
+}
+
+#[napi]
+impl Repository {
+  #[napi]
+  /// Verify the signature of a commit.
+  ///
+  /// The signature is verified with the program matching its format: `gpg`
+  /// for OpenPGP, `gpgsm` for X.509 and `ssh-keygen` for SSH signatures.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   verifyCommitSignature(
+  ///     oid: string,
+  ///     options?: VerifySignatureOptions | null | undefined,
+  ///   ): SignatureVerification | null;
+  /// }
+  /// ```
+  ///
+  /// @param {string} oid - Commit ID(SHA1) to verify.
+  /// @param {VerifySignatureOptions} [options] - Options for verifying the signature.
+  /// @returns Result of the verification. Returns `null` if the commit is not signed.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// const result = repo.verifyCommitSignature(repo.head().target()!, {
+  ///   allowedSigners: '/path/to/allowed_signers',
+  /// });
+  /// if (result?.valid) {
+  ///   console.log(`Signed by ${result.signer} (${result.fingerprint})`);
+  /// }
+  /// ```
+  pub fn verify_commit_signature(
+    &self,
+    oid: String,
+    options: Option<VerifySignatureOptions>,
+  ) -> crate::Result<Option<SignatureVerification>> {
+    let oid = self.inner.revparse_single(&oid)?.peel_to_commit()?.id();
+    match self.inner.extract_signature(&oid, None) {
+      Ok((signature, payload)) => {
+        let signature = std::str::from_utf8(&signature)?;
+        let verification = verify_signature(&self.inner, signature, &payload, options)?;
+        Ok(Some(verification))
+      }
+      Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+      Err(e) => Err(e.into()),
+    }
+  }
+
+  #[napi]
+  /// Verify the signature of an annotated tag.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   verifyTagSignature(
+  ///     oid: string,
+  ///     options?: VerifySignatureOptions | null | undefined,
+  ///   ): SignatureVerification | null;
+  /// }
+  /// ```
+  ///
+  /// @param {string} oid - Tag ID(SHA1) or tag name to verify.
+  /// @param {VerifySignatureOptions} [options] - Options for verifying the signature.
+  /// @returns Result of the verification. Returns `null` if the tag is not signed.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// const result = repo.verifyTagSignature('v1.0.0');
+  /// if (!result?.valid || result.trust !== 'ultimate') {
+  ///   throw new Error('Release tag is not signed by a trusted key');
+  /// }
+  /// ```
+  pub fn verify_tag_signature(
+    &self,
+    oid: String,
+    options: Option<VerifySignatureOptions>,
+  ) -> crate::Result<Option<SignatureVerification>> {
+    let oid = self.inner.revparse_single(&oid)?.peel_to_tag()?.id();
+    let odb = self.inner.odb()?;
+    let object = odb.read(oid)?;
+    match split_signature(object.data()) {
+      Some((payload, signature)) => {
+        let signature = std::str::from_utf8(signature)?;
+        let verification = verify_signature(&self.inner, signature, payload, options)?;
+        Ok(Some(verification))
+      }
+      None => Ok(None),
+    }
+  }
+}
@@ -0,0 +1,483 @@
+use crate::repository::Repository;
+use crate::util::wildmatch;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+const SKIP_WORKTREE: u16 = git2::IndexEntryExtendedFlag::SKIP_WORKTREE.bits();
+
+#[napi(object)]
+/// Options for setting sparse checkout patterns.
+pub struct SparseCheckoutSetOptions {
+  /// Use cone mode, where patterns are directories which are checked out
+  /// recursively, along with the files directly in their parent directories.
+  ///
+  /// Defaults to the `core.sparseCheckoutCone` config, or `true` if it is not set.
+  pub cone: Option<bool>,
+}
+
+#[napi]
+/// A class to manage the [sparse checkout][1] of a repository.
+///
+/// Patterns are stored in `info/sparse-checkout` of the git directory, so
+/// they are shared with the `git sparse-checkout` command.
+///
+/// Sparse checkout only limits the files in the working tree. Partial clone
+/// filters (the `filter` fetch option) are not supported because libgit2
+/// can't negotiate them, so clones and fetches still download all objects.
+///
+/// [1]: https://git-scm.com/docs/git-sparse-checkout
+pub struct SparseCheckout {
+  pub(crate) repo: Reference<Repository>,
+}
+
+#[napi]
+impl SparseCheckout {
+  #[napi]
+  /// Check if sparse checkout is enabled.
+  ///
+  /// @category SparseCheckout/Methods
+  /// @signature
+  /// ```ts
+  /// class SparseCheckout {
+  ///   isEnabled(): boolean;
+  /// }
+  /// ```
+  ///
+  /// @returns Returns `true` if the `core.sparseCheckout` config is enabled.
+  pub fn is_enabled(&self) -> crate::Result<bool> {
+    is_enabled(&self.repo.inner)
+  }
+
+  #[napi]
+  /// Enable sparse checkout and replace the patterns with the given ones.
+  ///
+  /// Files which are no longer matched are removed from the working tree
+  /// (unless they have local modifications), and files which are matched are
+  /// checked out.
+  ///
+  /// @category SparseCheckout/Methods
+  /// @signature
+  /// ```ts
+  /// class SparseCheckout {
+  ///   set(patterns: string[], options?: SparseCheckoutSetOptions | null | undefined): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string[]} patterns - Directories in cone mode, or gitignore-style patterns otherwise.
+  /// @param {SparseCheckoutSetOptions} [options] - Options for setting sparse checkout.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// repo.sparseCheckout.set(['packages/app', 'tools']);
+  /// console.log(repo.sparseCheckout.list()); // ["packages/app", "tools"]
+  /// ```
+  pub fn set(&self, patterns: Vec<String>, options: Option<SparseCheckoutSetOptions>) -> crate::Result<()> {
+    let repo = &self.repo.inner;
+    let mut config = repo.config()?;
+    let cone = match options.and_then(|x| x.cone) {
+      Some(cone) => cone,
+      None => config.get_bool("core.sparseCheckoutCone").unwrap_or(true),
+    };
+    let patterns = match cone {
+      true => SparsePatterns::Cone(normalize_dirs(patterns)),
+      false => SparsePatterns::NonCone(patterns),
+    };
+    write_patterns(repo, &patterns)?;
+    config.set_bool("core.sparseCheckout", true)?;
+    config.set_bool("core.sparseCheckoutCone", cone)?;
+    apply(repo)
+  }
+
+  #[napi]
+  /// Add patterns to the sparse checkout.
+  ///
+  /// The patterns are interpreted the same way as the existing patterns,
+  /// depending on whether cone mode is in use.
+  ///
+  /// @category SparseCheckout/Methods
+  /// @signature
+  /// ```ts
+  /// class SparseCheckout {
+  ///   add(patterns: string[]): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string[]} patterns - Patterns to add.
+  /// @throws Throws error if sparse checkout is not enabled.
+  pub fn add(&self, patterns: Vec<String>) -> crate::Result<()> {
+    let repo = &self.repo.inner;
+    if !is_enabled(repo)? {
+      return Err(not_enabled_error());
+    }
+    let patterns = match read_patterns(repo)? {
+      SparsePatterns::Cone(mut dirs) => {
+        dirs.extend(patterns);
+        SparsePatterns::Cone(normalize_dirs(dirs))
+      }
+      SparsePatterns::NonCone(mut lines) => {
+        lines.extend(patterns);
+        SparsePatterns::NonCone(lines)
+      }
+    };
+    write_patterns(repo, &patterns)?;
+    apply(repo)
+  }
+
+  #[napi]
+  /// List the sparse checkout patterns.
+  ///
+  /// @category SparseCheckout/Methods
+  /// @signature
+  /// ```ts
+  /// class SparseCheckout {
+  ///   list(): string[];
+  /// }
+  /// ```
+  ///
+  /// @returns Directories in cone mode, or the patterns otherwise.
+  /// @throws Throws error if sparse checkout is not enabled.
+  pub fn list(&self) -> crate::Result<Vec<String>> {
+    let repo = &self.repo.inner;
+    if !is_enabled(repo)? {
+      return Err(not_enabled_error());
+    }
+    let list = match read_patterns(repo)? {
+      SparsePatterns::Cone(dirs) => dirs,
+      SparsePatterns::NonCone(lines) => lines,
+    };
+    Ok(list)
+  }
+
+  #[napi]
+  /// Update the working tree to match the sparse checkout patterns again.
+  ///
+  /// This is useful when files outside of the patterns were brought back
+  /// by other operations.
+  ///
+  /// @category SparseCheckout/Methods
+  /// @signature
+  /// ```ts
+  /// class SparseCheckout {
+  ///   reapply(): void;
+  /// }
+  /// ```
+  pub fn reapply(&self) -> crate::Result<()> {
+    apply(&self.repo.inner)
+  }
+
+  #[napi]
+  /// Disable sparse checkout and restore all files in the working tree.
+  ///
+  /// The patterns are kept, so that sparse checkout can be enabled again.
+  ///
+  /// @category SparseCheckout/Methods
+  /// @signature
+  /// ```ts
+  /// class SparseCheckout {
+  ///   disable(): void;
+  /// }
+  /// ```
+  pub fn disable(&self) -> crate::Result<()> {
+    let repo = &self.repo.inner;
+    repo.config()?.set_bool("core.sparseCheckout", false)?;
+    apply(repo)
+  }
+}
+
+#[napi]
+impl Repository {
+  #[napi(getter)]
+  /// Get the sparse checkout of this repository.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   get sparseCheckout(): SparseCheckout;
+  /// }
+  /// ```
+  ///
+  /// @returns Sparse checkout of this repository.
+  pub fn sparse_checkout(&self, this: Reference<Repository>) -> SparseCheckout {
+    SparseCheckout { repo: this }
+  }
+}
+
+enum SparsePatterns {
+  /// Directories which are checked out recursively.
+  Cone(Vec<String>),
+  /// Lines of gitignore-style patterns.
+  NonCone(Vec<String>),
+}
+
+impl SparsePatterns {
+  fn includes(&self, path: &str) -> bool {
+    match self {
+      Self::Cone(dirs) => {
+        let parent = path.rsplit_once('/').map(|(x, _)| x).unwrap_or("");
+        parent.is_empty()
+          || dirs.iter().any(|dir| {
+            parent == dir || parent.starts_with(&format!("{dir}/")) || dir.starts_with(&format!("{parent}/"))
+          })
+      }
+      Self::NonCone(lines) => {
+        // Like git, a path which no pattern matches is decided by its parent directories.
+        let mut candidate = Some((path, false));
+        while let Some((current, is_dir)) = candidate {
+          if let Some(included) = match_lines(lines, current, is_dir) {
+            return included;
+          }
+          candidate = current.rsplit_once('/').map(|(x, _)| (x, true));
+        }
+        false
+      }
+    }
+  }
+}
+
+/// Match the path against gitignore-style lines, where the last matching line wins.
+fn match_lines(lines: &[String], path: &str, is_dir: bool) -> Option<bool> {
+  let mut result = None;
+  for line in lines {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    let (negated, pattern) = match line.strip_prefix('!') {
+      Some(pattern) => (true, pattern),
+      None => (false, line),
+    };
+    let (dir_only, pattern) = match pattern.strip_suffix('/') {
+      Some(pattern) => (true, pattern),
+      None => (false, pattern),
+    };
+    if dir_only && !is_dir {
+      continue;
+    }
+    let matched = match pattern.contains('/') {
+      true => wildmatch(pattern.trim_start_matches('/'), path, false),
+      false => wildmatch(pattern, path.rsplit('/').next().unwrap_or(path), false),
+    };
+    if matched {
+      result = Some(!negated);
+    }
+  }
+  result
+}
+
+fn normalize_dirs(dirs: Vec<String>) -> Vec<String> {
+  let mut dirs = dirs
+    .iter()
+    .map(|x| x.trim_matches('/').to_string())
+    .filter(|x| !x.is_empty())
+    .collect::<Vec<_>>();
+  dirs.sort();
+  dirs.dedup();
+  dirs
+}
+
+fn not_enabled_error() -> crate::Error {
+  git2::Error::new(
+    git2::ErrorCode::NotFound,
+    git2::ErrorClass::Config,
+    "sparse checkout is not enabled",
+  )
+  .into()
+}
+
+fn sparse_checkout_file(repo: &git2::Repository) -> PathBuf {
+  repo.path().join("info").join("sparse-checkout")
+}
+
+pub(crate) fn is_enabled(repo: &git2::Repository) -> crate::Result<bool> {
+  Ok(repo.config()?.get_bool("core.sparseCheckout").unwrap_or(false))
+}
+
+fn read_patterns(repo: &git2::Repository) -> crate::Result<SparsePatterns> {
+  let content = match std::fs::read_to_string(sparse_checkout_file(repo)) {
+    Ok(content) => content,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+    Err(e) => return Err(e.into()),
+  };
+  let lines = content
+    .lines()
+    .map(|x| x.trim_end())
+    .filter(|x| !x.is_empty() && !x.starts_with('#'))
+    .map(|x| x.to_string())
+    .collect::<Vec<_>>();
+  let cone = repo.config()?.get_bool("core.sparseCheckoutCone").unwrap_or(true);
+  if !cone {
+    return Ok(SparsePatterns::NonCone(lines));
+  }
+  // Recursive directories are the ones not followed by a `!/dir/*/` line,
+  // which is written for parent directories only.
+  let dirs = lines
+    .iter()
+    .filter_map(|x| x.strip_prefix('/').and_then(|x| x.strip_suffix('/')))
+    .filter(|dir| !lines.contains(&format!("!/{dir}/*/")))
+    .map(|x| x.to_string())
+    .collect::<Vec<_>>();
+  Ok(SparsePatterns::Cone(dirs))
+}
+
+fn write_patterns(repo: &git2::Repository, patterns: &SparsePatterns) -> crate::Result<()> {
+  let mut content = String::new();
+  match patterns {
+    SparsePatterns::Cone(dirs) => {
+      content.push_str("/*\n!/*/\n");
+      let mut parents = dirs
+        .iter()
+        .flat_map(|dir| {
+          dir
+            .match_indices('/')
+            .map(|(i, _)| dir[..i].to_string())
+            .collect::<Vec<_>>()
+        })
+        .filter(|parent| {
+          !dirs
+            .iter()
+            .any(|dir| parent == dir || parent.starts_with(&format!("{dir}/")))
+        })
+        .collect::<Vec<_>>();
+      parents.sort();
+      parents.dedup();
+      for parent in parents {
+        content.push_str(&format!("/{parent}/\n!/{parent}/*/\n"));
+      }
+      for dir in dirs {
+        if !dirs.iter().any(|x| dir.starts_with(&format!("{x}/"))) {
+          content.push_str(&format!("/{dir}/\n"));
+        }
+      }
+    }
+    SparsePatterns::NonCone(lines) => {
+      for line in lines {
+        content.push_str(line);
+        content.push('\n');
+      }
+    }
+  }
+  let file = sparse_checkout_file(repo);
+  if let Some(parent) = file.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(file, content)?;
+  Ok(())
+}
+
+/// Update the skip-worktree bits of the index and the working tree to match
+/// the sparse checkout patterns. All files are restored if sparse checkout is
+/// disabled.
+pub(crate) fn apply(repo: &git2::Repository) -> crate::Result<()> {
+  let Some(workdir) = repo.workdir() else {
+    return Err(
+      git2::Error::new(
+        git2::ErrorCode::BareRepo,
+        git2::ErrorClass::Repository,
+        "cannot use sparse checkout in a bare repository",
+      )
+      .into(),
+    );
+  };
+  let patterns = match is_enabled(repo)? {
+    true => Some(read_patterns(repo)?),
+    false => None,
+  };
+  let mut index = repo.index()?;
+  let mut restore = Vec::new();
+  let mut modified = None;
+  for mut entry in index.iter().collect::<Vec<_>>() {
+    // Skip conflicted entries.
+    if (entry.flags >> 12) & 0x3 != 0 {
+      continue;
+    }
+    let path = String::from_utf8_lossy(&entry.path).to_string();
+    let included = patterns.as_ref().map(|x| x.includes(&path)).unwrap_or(true);
+    let skipped = entry.flags_extended & SKIP_WORKTREE != 0;
+    let file = workdir.join(&path);
+    if included {
+      if skipped {
+        entry.flags_extended &= !SKIP_WORKTREE;
+        index.add(&entry)?;
+      }
+      if file.symlink_metadata().is_err() {
+        restore.push(path);
+      }
+      continue;
+    }
+    if file.symlink_metadata().is_ok() {
+      if modified.is_none() {
+        modified = Some(modified_paths(repo)?);
+      }
+      if modified.as_ref().is_some_and(|x| x.contains(&path)) {
+        // Keep local modifications, like git does.
+        continue;
+      }
+      std::fs::remove_file(&file)?;
+      remove_empty_parents(workdir, &file);
+    }
+    if !skipped {
+      entry.flags_extended |= SKIP_WORKTREE;
+      index.add(&entry)?;
+    }
+  }
+  index.write()?;
+  if !restore.is_empty() {
+    let mut builder = git2::build::CheckoutBuilder::new();
+    builder.recreate_missing(true).disable_pathspec_match(true);
+    for path in &restore {
+      builder.path(path);
+    }
+    repo.checkout_index(Some(&mut index), Some(&mut builder))?;
+  }
+  Ok(())
+}
+
+/// Paths of files which are modified in the working tree.
+fn modified_paths(repo: &git2::Repository) -> crate::Result<HashSet<String>> {
+  let mut options = git2::StatusOptions::new();
+  options
+    .include_untracked(false)
+    .include_ignored(false)
+    .exclude_submodules(true);
+  let modified = git2::Status::WT_MODIFIED | git2::Status::WT_TYPECHANGE | git2::Status::WT_RENAMED;
+  let statuses = repo.statuses(Some(&mut options))?;
+  let paths = statuses
+    .iter()
+    .filter(|x| x.status().intersects(modified))
+    .filter_map(|x| x.path().map(String::from))
+    .collect();
+  Ok(paths)
+}
+
+/// Apply sparse checkout after other operations update the working tree, if
+/// it is enabled.
+pub(crate) fn apply_if_enabled(repo: &git2::Repository) -> crate::Result<()> {
+  if repo.workdir().is_some() && is_enabled(repo)? {
+    apply(repo)?;
+  }
+  Ok(())
+}
+
+fn remove_empty_parents(workdir: &Path, file: &Path) {
+  let mut dir = file.parent();
+  while let Some(current) = dir {
+    if current == workdir || !current.starts_with(workdir) || std::fs::remove_dir(current).is_err() {
+      break;
+    }
+    dir = current.parent();
+  }
+}
+
+/// Check if the index entry of the path is marked as skip-worktree.
+pub(crate) fn is_skip_worktree(index: &git2::Index, path: &str) -> bool {
+  index
+    .get_path(Path::new(path), 0)
+    .map(|x| x.flags_extended & SKIP_WORKTREE != 0)
+    .unwrap_or(false)
+}
@@ -1,8 +1,9 @@
+use crate::checkout::{checkout_builder, CheckoutOptions, CheckoutRecords};
+use crate::diff::Diff;
 use crate::repository::Repository;
 use crate::signature::{Signature, SignaturePayload};
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
-use std::ops::Deref;
 
 #[napi(object)]
 #[derive(Default)]
@@ -45,6 +46,50 @@ pub struct StashSaveOptions {
   /// If true, staged changes remain in the index after stashing.
   /// Default: false
   pub keep_index: Option<bool>,
+  /// Pathspecs of the files to stash, like `git stash push -- <paths>`.
+  /// If not provided, all changes are stashed.
+  pub paths: Option<Vec<String>>,
+  /// Whether to stash only the changes staged in the index, like
+  /// `git stash push --staged`. Unstaged changes remain in the working directory.
+  /// Can't be used with `includeUntracked` or `includeIgnored`.
+  /// Default: false
+  pub staged_only: Option<bool>,
+}
+
+#[napi(string_enum)]
+#[derive(Copy, Clone)]
+/// Stages of applying a stash.
+///
+/// - `LoadingStash` : Loading the stashed data from the object database.
+/// - `AnalyzeIndex` : The stored index is being analyzed.
+/// - `AnalyzeModified` : The modified files are being analyzed.
+/// - `AnalyzeUntracked` : The untracked and ignored files are being analyzed.
+/// - `CheckoutUntracked` : The untracked files are being written to disk.
+/// - `CheckoutModified` : The modified files are being written to disk.
+/// - `Done` : The stash was applied successfully.
+pub enum StashApplyProgress {
+  LoadingStash,
+  AnalyzeIndex,
+  AnalyzeModified,
+  AnalyzeUntracked,
+  CheckoutUntracked,
+  CheckoutModified,
+  Done,
+}
+
+impl StashApplyProgress {
+  fn from_git2(value: git2::StashApplyProgress) -> Option<Self> {
+    match value {
+      git2::StashApplyProgress::None => None,
+      git2::StashApplyProgress::LoadingStash => Some(Self::LoadingStash),
+      git2::StashApplyProgress::AnalyzeIndex => Some(Self::AnalyzeIndex),
+      git2::StashApplyProgress::AnalyzeModified => Some(Self::AnalyzeModified),
+      git2::StashApplyProgress::AnalyzeUntracked => Some(Self::AnalyzeUntracked),
+      git2::StashApplyProgress::CheckoutUntracked => Some(Self::CheckoutUntracked),
+      git2::StashApplyProgress::CheckoutModified => Some(Self::CheckoutModified),
+      git2::StashApplyProgress::Done => Some(Self::Done),
+    }
+  }
 }
 
 #[napi(object)]
@@ -62,49 +107,203 @@ pub struct StashSaveOptions {
 /// repo.stashApply(0);
 ///
 /// // With options
-/// repo.stashApply(0, { reinstantiateIndex: true });
+/// repo.stashApply(0, {
+///   reinstantiateIndex: true,
+///   progress: stage => console.log(stage),
+/// });
 /// ```
 pub struct StashApplyOptions {
   /// Whether to reinstall the index from the stash.
   /// If true, the index state recorded in the stash is also restored.
   /// Default: false
   pub reinstantiate_index: Option<bool>,
+  /// Options for the checkout of the stashed files.
+  /// If the checkout is prevented by conflicts, the paths of the conflicting
+  /// files are listed in the error message.
+  pub checkout: Option<CheckoutOptions>,
+  #[napi(ts_type = "(progress: StashApplyProgress) => boolean | void")]
+  /// Callback which is called to report the progress of applying the stash.
+  /// Returning `false` cancels applying the stash. An error thrown by the
+  /// callback also cancels applying the stash, and is thrown.
+  pub progress: Option<Function<'static, StashApplyProgress, Option<bool>>>,
 }
 
-impl From<StashApplyOptions> for git2::StashApplyOptions<'_> {
-  fn from(value: StashApplyOptions) -> Self {
-    let mut opts = git2::StashApplyOptions::new();
-    if let Some(true) = value.reinstantiate_index {
-      opts.reinstantiate_index();
+/// Apply a stash with the options, listing paths of the conflicts in the
+/// error message if the checkout is prevented by conflicts.
+fn run_stash_apply(
+  env: Env,
+  options: Option<StashApplyOptions>,
+  apply: impl FnOnce(&mut git2::StashApplyOptions<'static>) -> std::result::Result<(), git2::Error>,
+) -> crate::Result<()> {
+  let (reinstantiate_index, checkout, progress) = match options {
+    Some(options) => (options.reinstantiate_index, options.checkout, options.progress),
+    None => (None, None, None),
+  };
+  let records: CheckoutRecords = Default::default();
+  let mut opts = git2::StashApplyOptions::new();
+  if let Some(true) = reinstantiate_index {
+    opts.reinstantiate_index();
+  }
+  opts.checkout_options(checkout_builder(
+    env,
+    checkout,
+    git2::CheckoutNotificationType::CONFLICT,
+    records.clone(),
+  )?);
+  if let Some(callback) = progress {
+    let js_ref = callback.create_ref()?;
+    let records = records.clone();
+    opts.progress_cb(move |progress| {
+      let Some(progress) = StashApplyProgress::from_git2(progress) else {
+        return true;
+      };
+      let result = js_ref.borrow_back(&env).and_then(|callback| callback.call(progress));
+      records.catch(result).map(|x| x.unwrap_or(true)).unwrap_or(false)
+    });
+  }
+  let result = apply(&mut opts);
+  drop(opts);
+  records.finish(result)
+}
+
+#[napi(object)]
+/// Options for showing a stash.
+pub struct StashShowOptions {
+  /// Whether to include the untracked files of the stash in the diff, like
+  /// `git stash show --include-untracked`.
+  /// Default: false
+  pub include_untracked: Option<bool>,
+}
+
+/// Find the commit id of the stash at the index.
+fn stash_oid(repo: &mut git2::Repository, index: usize) -> crate::Result<git2::Oid> {
+  let mut found = None;
+  repo.stash_foreach(|i, _, oid| {
+    if i == index {
+      found = Some(*oid);
+    }
+    found.is_none()
+  })?;
+  found.ok_or_else(|| {
+    git2::Error::new(
+      git2::ErrorCode::NotFound,
+      git2::ErrorClass::Stash,
+      format!("no stashed state at position {index}"),
+    )
+    .into()
+  })
+}
+
+/// Diff between the commit at which the stash was created and the stash.
+fn stash_diff(repo: &git2::Repository, oid: git2::Oid, include_untracked: bool) -> crate::Result<git2::Diff<'_>> {
+  let commit = repo.find_commit(oid)?;
+  let base = commit.parent(0)?;
+  let mut diff = repo.diff_tree_to_tree(Some(&base.tree()?), Some(&commit.tree()?), None)?;
+  // Untracked files are stored in the third parent of the stash commit.
+  if let (true, Ok(untracked)) = (include_untracked, commit.parent(2)) {
+    let untracked_diff = repo.diff_tree_to_tree(None, Some(&untracked.tree()?), None)?;
+    diff.merge(&untracked_diff)?;
+  }
+  Ok(diff)
+}
+
+/// Stash only the changes staged in the index, which git2 doesn't support.
+///
+/// The stash commit is created like `git stash push --staged`, and the staged
+/// changes are removed from both the index and the working directory.
+fn stash_staged(
+  repo: &git2::Repository,
+  stasher: &git2::Signature,
+  message: Option<&str>,
+  paths: &[String],
+) -> crate::Result<git2::Oid> {
+  let head = repo.head()?;
+  let branch = match repo.head_detached()? {
+    true => "(no branch)".to_string(),
+    false => head.shorthand().unwrap_or_default().to_string(),
+  };
+  let head = head.peel_to_commit()?;
+  let head_tree = head.tree()?;
+  let index = repo.index()?;
+  let staged_diff = |reverse: bool| {
+    let mut opts = git2::DiffOptions::new();
+    opts.reverse(reverse);
+    for path in paths {
+      opts.pathspec(path);
     }
-    opts
+    repo.diff_tree_to_index(Some(&head_tree), Some(&index), Some(&mut opts))
+  };
+  let staged = staged_diff(false)?;
+  if staged.deltas().len() == 0 {
+    return Err(
+      git2::Error::new(
+        git2::ErrorCode::NotFound,
+        git2::ErrorClass::Stash,
+        "no staged changes to stash",
+      )
+      .into(),
+    );
   }
+  let tree = repo.apply_to_tree(&head_tree, &staged, None)?.write_tree_to(repo)?;
+  let tree = repo.find_tree(tree)?;
+  let summary = format!("{} {}", &head.id().to_string()[..7], head.summary().unwrap_or_default());
+  let index_message = format!("index on {branch}: {summary}");
+  let index_commit = repo.commit(None, stasher, stasher, &index_message, &tree, &[&head])?;
+  let index_commit = repo.find_commit(index_commit)?;
+  let message = match message {
+    Some(message) => format!("On {branch}: {message}"),
+    None => format!("WIP on {branch}: {summary}"),
+  };
+  let oid = repo.commit(None, stasher, stasher, &message, &tree, &[&head, &index_commit])?;
+  // Remove the staged changes before writing the stash reference, so that no
+  // stash is left if the changes can't be removed.
+  repo.apply(&staged_diff(true)?, git2::ApplyLocation::Both, None)?;
+  repo.reference_ensure_log("refs/stash")?;
+  repo.reference("refs/stash", oid, true, &message)?;
+  Ok(oid)
+}
+
+/// Replace the message of the latest stash, because git2 doesn't support the
+/// message of stash with pathspecs.
+fn reword_stash(repo: &git2::Repository, oid: git2::Oid, message: &str) -> crate::Result<git2::Oid> {
+  let commit = repo.find_commit(oid)?;
+  // The default message is `WIP on <branch>: <summary>`, and branch names
+  // can't contain `:`.
+  let branch = commit
+    .message()
+    .and_then(|x| x.strip_prefix("WIP on "))
+    .and_then(|x| x.split_once(':'))
+    .map(|(branch, _)| branch)
+    .unwrap_or("(no branch)");
+  let message = format!("On {branch}: {message}");
+  let reworded = commit.amend(None, None, None, None, Some(&message), None)?;
+  let mut reflog = repo.reflog("refs/stash")?;
+  reflog.remove(0, false)?;
+  reflog.write()?;
+  repo.reference("refs/stash", reworded, true, &message)?;
+  Ok(reworded)
 }
 
 pub(crate) enum StashEntryInner {
   StashList(SharedReference<StashList, (usize, git2::Oid, Option<String>)>),
-  Owned((usize, git2::Oid, Option<String>)),
 }
 
 impl StashEntryInner {
   fn index(&self) -> usize {
     match self {
       Self::StashList(list) => list.0,
-      Self::Owned((index, _, _)) => *index,
     }
   }
 
   fn oid(&self) -> &git2::Oid {
     match self {
       Self::StashList(list) => &list.1,
-      Self::Owned((_, oid, _)) => oid,
     }
   }
 
   fn message(&self) -> Option<&str> {
     match self {
       Self::StashList(list) => list.2.as_deref(),
-      Self::Owned((_, _, message)) => message.as_deref(),
     }
   }
 }
@@ -205,6 +404,38 @@ impl StashEntry {
   pub fn message(&self) -> Option<String> {
     self.inner.message().map(|s| s.to_string())
   }
+
+  #[napi]
+  /// Get the diff between the commit at which this stash was created and the
+  /// stash, like `git stash show`.
+  ///
+  /// @category Stash/Methods
+  /// @signature
+  /// ```ts
+  /// class StashEntry {
+  ///   diff(options?: StashShowOptions): Diff;
+  /// }
+  /// ```
+  ///
+  /// @param {StashShowOptions} [options] - Options for showing the stash.
+  /// @returns {Diff} Diff of the changes stashed in this entry.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('./path/to/repo');
+  /// const stash = repo.stashList().get(0);
+  /// const diff = stash?.diff({ includeUntracked: true });
+  /// ```
+  pub fn diff(&self, env: Env, options: Option<StashShowOptions>) -> crate::Result<Diff> {
+    let include_untracked = options.and_then(|x| x.include_untracked).unwrap_or(false);
+    let oid = *self.inner.oid();
+    let StashEntryInner::StashList(entry) = &self.inner;
+    let repo = entry.clone_owner(env)?.inner.clone_owner(env)?;
+    let inner = repo.share_with(env, move |repo| Ok(stash_diff(&repo.inner, oid, include_untracked)?))?;
+    Ok(Diff { inner })
+  }
 }
 
 #[napi]
@@ -354,16 +585,24 @@ impl StashList {
   /// }
   /// ```
   pub fn iter(&self, this: Reference<StashList>, env: Env) -> crate::Result<StashListIter> {
-    let entries = self.inner.deref().clone();
-    let inner = this.share_with(env, move |_| Ok(entries))?;
-    Ok(StashListIter { inner, index: 0 })
+    let mut entries = Vec::with_capacity(self.inner.len());
+    for index in 0..self.inner.len() {
+      let entry = this
+        .clone(env)?
+        .share_with(env, move |list| Ok(list.inner[index].clone()))?;
+      entries.push(StashEntry {
+        inner: StashEntryInner::StashList(entry),
+      });
+    }
+    Ok(StashListIter {
+      inner: entries.into_iter(),
+    })
   }
 }
 
 #[napi(iterator)]
 pub struct StashListIter {
-  pub(crate) inner: SharedReference<StashList, Vec<(usize, git2::Oid, Option<String>)>>,
-  index: usize,
+  pub(crate) inner: std::vec::IntoIter<StashEntry>,
 }
 
 #[napi]
@@ -373,16 +612,7 @@ impl Generator for StashListIter {
   type Return = ();
 
   fn next(&mut self, _value: Option<Self::Next>) -> Option<Self::Yield> {
-    let current_index = self.index;
-    if current_index >= self.inner.len() {
-      return None;
-    }
-
-    self.index += 1;
-
-    self.inner.get(current_index).map(|entry| StashEntry {
-      inner: StashEntryInner::Owned(entry.clone()),
-    })
+    self.inner.next()
   }
 }
 
@@ -446,9 +676,37 @@ impl Repository {
       flags |= git2::StashFlags::KEEP_INDEX;
     }
 
-    let oid = self
-      .inner
-      .stash_save2(&stasher, options.message.as_deref(), Some(flags))?;
+    let paths = options.paths.unwrap_or_default();
+    if options.staged_only.unwrap_or(false) {
+      if flags.intersects(git2::StashFlags::INCLUDE_UNTRACKED | git2::StashFlags::INCLUDE_IGNORED) {
+        return Err(
+          git2::Error::new(
+            git2::ErrorCode::Invalid,
+            git2::ErrorClass::Stash,
+            "can't stash staged changes with untracked or ignored files",
+          )
+          .into(),
+        );
+      }
+      let oid = stash_staged(&self.inner, &stasher, options.message.as_deref(), &paths)?;
+      return Ok(oid.to_string());
+    }
+
+    if paths.is_empty() {
+      let oid = self
+        .inner
+        .stash_save2(&stasher, options.message.as_deref(), Some(flags))?;
+      return Ok(oid.to_string());
+    }
+    let mut opts = git2::StashSaveOptions::new(stasher);
+    opts.flags(Some(flags));
+    for path in paths {
+      opts.pathspec(path);
+    }
+    let mut oid = self.inner.stash_save_ext(Some(&mut opts))?;
+    if let Some(message) = options.message.as_deref() {
+      oid = reword_stash(&self.inner, oid, message)?;
+    }
     Ok(oid.to_string())
   }
 
@@ -483,18 +741,8 @@ impl Repository {
   /// // Apply with options
   /// repo.stashApply(0, { reinstantiateIndex: true });
   /// ```
-  pub fn stash_apply(&mut self, index: u32, options: Option<StashApplyOptions>) -> crate::Result<()> {
-    let idx = index as usize;
-    match options {
-      Some(o) => {
-        let mut opts = o.into();
-        self.inner.stash_apply(idx, Some(&mut opts))?;
-      }
-      None => {
-        self.inner.stash_apply(idx, None)?;
-      }
-    }
-    Ok(())
+  pub fn stash_apply(&mut self, env: Env, index: u32, options: Option<StashApplyOptions>) -> crate::Result<()> {
+    run_stash_apply(env, options, |opts| self.inner.stash_apply(index as usize, Some(opts)))
   }
 
   #[napi]
@@ -563,18 +811,8 @@ impl Repository {
   /// // Pop with options
   /// repo.stashPop(0, { reinstantiateIndex: true });
   /// ```
-  pub fn stash_pop(&mut self, index: u32, options: Option<StashApplyOptions>) -> crate::Result<()> {
-    let idx = index as usize;
-    match options {
-      Some(o) => {
-        let mut opts = o.into();
-        self.inner.stash_pop(idx, Some(&mut opts))?;
-      }
-      None => {
-        self.inner.stash_pop(idx, None)?;
-      }
-    }
-    Ok(())
+  pub fn stash_pop(&mut self, env: Env, index: u32, options: Option<StashApplyOptions>) -> crate::Result<()> {
+    run_stash_apply(env, options, |opts| self.inner.stash_pop(index as usize, Some(opts)))
   }
 
   #[napi]
@@ -622,4 +860,102 @@ impl Repository {
     })?;
     Ok(StashList { inner })
   }
+
+  #[napi]
+  /// Get the changes recorded in a stash, like `git stash show -p`.
+  ///
+  /// The diff is between the commit at which the stash was created and the
+  /// stashed state of the working directory.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   stashShow(index: number, options?: StashShowOptions): Diff;
+  /// }
+  /// ```
+  ///
+  /// @param {number} index - The index of the stash to show (0 is the most recent).
+  /// @param {StashShowOptions} [options] - Options for showing the stash.
+  /// @returns {Diff} Diff of the changes recorded in the stash.
+  /// @throws {Error} If the stash index is invalid.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('./path/to/repo');
+  /// const diff = repo.stashShow(0, { includeUntracked: true });
+  /// for (const delta of diff.deltas()) {
+  ///   console.log(delta.newFile().path());
+  /// }
+  /// ```
+  pub fn stash_show(
+    &self,
+    this: Reference<Repository>,
+    env: Env,
+    index: u32,
+    options: Option<StashShowOptions>,
+  ) -> crate::Result<Diff> {
+    let include_untracked = options.and_then(|x| x.include_untracked).unwrap_or(false);
+    let inner = this.share_with(env, move |repo| {
+      let oid = stash_oid(&mut repo.inner, index as usize)?;
+      Ok(stash_diff(&repo.inner, oid, include_untracked)?)
+    })?;
+    Ok(Diff { inner })
+  }
+
+  #[napi]
+  /// Create and check out a new branch from the commit at which the stash was
+  /// created, and apply the stash on it, like `git stash branch`.
+  ///
+  /// The index state recorded in the stash is also restored. If the stash is
+  /// applied successfully, it is removed from the stash list.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   stashBranch(name: string, index: number, options?: StashApplyOptions): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string} name - Name of the branch to create.
+  /// @param {number} index - The index of the stash to apply (0 is the most recent).
+  /// @param {StashApplyOptions} [options] - Options for applying the stash.
+  /// @throws {Error} If the stash index is invalid, the branch already exists or if there are
+  /// conflicts during application.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('./path/to/repo');
+  /// repo.stashBranch('wip-feature', 0);
+  /// ```
+  pub fn stash_branch(
+    &mut self,
+    env: Env,
+    name: String,
+    index: u32,
+    options: Option<StashApplyOptions>,
+  ) -> crate::Result<()> {
+    let index = index as usize;
+    let oid = stash_oid(&mut self.inner, index)?;
+    {
+      let base = self.inner.find_commit(oid)?.parent(0)?;
+      self.inner.branch(&name, &base, false)?;
+      let mut checkout = git2::build::CheckoutBuilder::new();
+      checkout.safe();
+      self.inner.checkout_tree(base.as_object(), Some(&mut checkout))?;
+    }
+    self.inner.set_head(&format!("refs/heads/{name}"))?;
+    let mut options = options.unwrap_or(StashApplyOptions {
+      reinstantiate_index: None,
+      checkout: None,
+      progress: None,
+    });
+    options.reinstantiate_index = Some(true);
+    run_stash_apply(env, Some(options), |opts| self.inner.stash_pop(index, Some(opts)))
+  }
 }
@@ -1,5 +1,6 @@
 use crate::diff::DiffDelta;
 use crate::repository::Repository;
+use crate::sparse_checkout;
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use std::ops::Deref;
@@ -186,6 +187,18 @@ impl From<StatusOptions> for git2::StatusOptions {
 /// allowing indexing, as well as providing an iterator.
 pub struct Statuses {
   pub(crate) inner: SharedReference<Repository, git2::Statuses<'static>>,
+  /// Sorted positions of the entries which are hidden from this list, because
+  /// they are outside of the sparse checkout.
+  pub(crate) hidden: Vec<usize>,
+}
+
+impl Statuses {
+  fn position(&self, index: usize) -> usize {
+    self.hidden.iter().fold(
+      index,
+      |position, hidden| if *hidden <= position { position + 1 } else { position },
+    )
+  }
 }
 
 #[napi]
@@ -207,9 +220,10 @@ impl Statuses {
   pub fn get(&self, index: u32, this: Reference<Statuses>, env: Env) -> Option<StatusEntry> {
     this
       .share_with(env, move |repo| {
+        let position = repo.position(index as usize);
         repo
           .inner
-          .get(index as usize)
+          .get(position)
           .ok_or(Error::new(napi::Status::GenericFailure, "not found"))
       })
       .ok()
@@ -232,7 +246,7 @@ impl Statuses {
   /// @returns If there are no changes in status (according to the options given
   /// when the status list was created), this should return 0.
   pub fn len(&self) -> usize {
-    self.inner.len()
+    self.inner.len() - self.hidden.len()
   }
 
   #[napi]
@@ -246,20 +260,27 @@ impl Statuses {
   ///
   /// @returns Return `true` if there is no status entry in this list.
   pub fn is_empty(&self) -> bool {
-    self.inner.is_empty()
+    self.len() == 0
   }
 
   #[napi]
   /// Returns an iterator over the statuses in this list.
   pub fn iter(&self, this: Reference<Statuses>, env: Env) -> crate::Result<StatusesIter> {
+    let hidden = self.hidden.clone();
     let inner = this.share_with(env, move |statuses| Ok(statuses.inner.iter()))?;
-    Ok(StatusesIter { inner })
+    Ok(StatusesIter {
+      inner,
+      hidden,
+      position: 0,
+    })
   }
 }
 
 #[napi(iterator)]
 pub struct StatusesIter {
   pub(crate) inner: SharedReference<Statuses, git2::StatusIter<'static>>,
+  pub(crate) hidden: Vec<usize>,
+  pub(crate) position: usize,
 }
 
 #[napi]
@@ -269,9 +290,16 @@ impl Generator for StatusesIter {
   type Return = ();
 
   fn next(&mut self, _value: Option<Self::Next>) -> Option<Self::Yield> {
-    self.inner.next().map(|entry| StatusEntry {
-      inner: StatusEntryInner::Owned(entry),
-    })
+    loop {
+      let entry = self.inner.next()?;
+      let position = self.position;
+      self.position += 1;
+      if self.hidden.binary_search(&position).is_err() {
+        return Some(StatusEntry {
+          inner: StatusEntryInner::Owned(entry),
+        });
+      }
+    }
   }
 }
 
@@ -484,6 +512,29 @@ impl Repository {
         .map_err(crate::Error::from)
         .map_err(|e| e.into())
     })?;
-    Ok(Statuses { inner })
+    let hidden = skip_worktree_positions(&self.inner, &inner)?;
+    Ok(Statuses { inner, hidden })
+  }
+}
+
+/// Positions of the entries which are deleted from the working tree only
+/// because they are marked as skip-worktree by sparse checkout.
+fn skip_worktree_positions(repo: &git2::Repository, statuses: &git2::Statuses) -> crate::Result<Vec<usize>> {
+  if repo.is_bare() || !sparse_checkout::is_enabled(repo)? {
+    return Ok(Vec::new());
   }
+  let index = repo.index()?;
+  let positions = statuses
+    .iter()
+    .enumerate()
+    .filter(|(_, entry)| entry.status() == git2::Status::WT_DELETED)
+    .filter(|(_, entry)| {
+      entry
+        .path()
+        .map(|path| sparse_checkout::is_skip_worktree(&index, path))
+        .unwrap_or(false)
+    })
+    .map(|(position, _)| position)
+    .collect::<Vec<_>>();
+  Ok(positions)
 }
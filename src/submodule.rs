@@ -303,6 +303,24 @@ pub struct SubmoduleUpdateOptions {
   pub allow_fetch: Option<bool>,
 }
 
+fn update_options<'a>(
+  checkout: Option<&CheckoutOptions>,
+  fetch: Option<&'a FetchOptions>,
+  allow_fetch: Option<bool>,
+) -> crate::Result<git2::SubmoduleUpdateOptions<'a>> {
+  let mut opts = git2::SubmoduleUpdateOptions::default();
+  if let Some(checkout) = checkout {
+    opts.checkout(checkout.into());
+  }
+  if let Some(fetch) = fetch {
+    opts.fetch(fetch.to_git2_fetch_options()?);
+  }
+  if let Some(allow_fetch) = allow_fetch {
+    opts.allow_fetch(allow_fetch);
+  }
+  Ok(opts)
+}
+
 pub struct SubmoduleUpdateTask {
   inner: SharedReference<Repository, git2::Submodule<'static>>,
   init: bool,
@@ -317,18 +335,10 @@ impl Task for SubmoduleUpdateTask {
   type JsValue = ();
 
   fn compute(&mut self) -> Result<Self::Output> {
-    let mut opts = git2::SubmoduleUpdateOptions::default();
-    if let Some(options) = &self.options {
-      if let Some(checkout) = &options.checkout {
-        opts.checkout(checkout.clone().into());
-      }
-      if let Some(fetch) = &options.fetch {
-        opts.fetch(fetch.to_git2_fetch_options());
-      }
-      if let Some(allow_fetch) = options.allow_fetch {
-        opts.allow_fetch(allow_fetch);
-      }
-    }
+    let mut opts = match &self.options {
+      Some(options) => update_options(options.checkout.as_ref(), options.fetch.as_ref(), options.allow_fetch)?,
+      None => git2::SubmoduleUpdateOptions::default(),
+    };
     self
       .inner
       .update(self.init, Some(&mut opts))
@@ -354,18 +364,10 @@ impl Task for SubmoduleCloneTask {
   type JsValue = Repository;
 
   fn compute(&mut self) -> Result<Self::Output> {
-    let mut opts = git2::SubmoduleUpdateOptions::default();
-    if let Some(options) = &self.options {
-      if let Some(checkout) = &options.checkout {
-        opts.checkout(checkout.clone().into());
-      }
-      if let Some(fetch) = &options.fetch {
-        opts.fetch(fetch.to_git2_fetch_options());
-      }
-      if let Some(allow_fetch) = options.allow_fetch {
-        opts.allow_fetch(allow_fetch);
-      }
-    }
+    let mut opts = match &self.options {
+      Some(options) => update_options(options.checkout.as_ref(), options.fetch.as_ref(), options.allow_fetch)?,
+      None => git2::SubmoduleUpdateOptions::default(),
+    };
     let inner = self
       .inner
       .deref_mut()
@@ -379,6 +381,388 @@ impl Task for SubmoduleCloneTask {
   }
 }
 
+#[napi(object, object_to_js = false)]
+/// Options to add a submodule.
+pub struct SubmoduleAddOptions {
+  /// Branch of the submodule repository to check out, which is recorded in
+  /// `.gitmodules`.
+  ///
+  /// If not provided, the default branch of the remote is checked out.
+  pub branch: Option<String>,
+  /// Create a shallow clone with a history truncated to the specified number
+  /// of commits.
+  pub depth: Option<i32>,
+  /// Options which control the fetch, including callbacks.
+  pub fetch: Option<FetchOptions>,
+  /// Should the workdir contain a gitlink to the repo in `.git/modules` vs.
+  /// repo directly in workdir.
+  ///
+  /// Default: `true`.
+  pub use_gitlink: Option<bool>,
+}
+
+pub struct SubmoduleAddTask {
+  repo: Reference<Repository>,
+  url: String,
+  path: String,
+  options: Option<SubmoduleAddOptions>,
+}
+
+unsafe impl Send for SubmoduleAddTask {}
+
+impl SubmoduleAddTask {
+  fn add(&self) -> crate::Result<()> {
+    let repo = &self.repo.inner;
+    let options = self.options.as_ref();
+    let use_gitlink = options.and_then(|x| x.use_gitlink).unwrap_or(true);
+    let mut submodule = repo.submodule(&self.url, Path::new(&self.path), use_gitlink)?;
+    let mut fetch = match options.and_then(|x| x.fetch.as_ref()) {
+      Some(fetch) => fetch.to_git2_fetch_options()?,
+      None => git2::FetchOptions::new(),
+    };
+    if let Some(depth) = options.and_then(|x| x.depth) {
+      fetch.depth(depth);
+    }
+    let mut update = git2::SubmoduleUpdateOptions::new();
+    update.fetch(fetch);
+    let sub_repo = submodule.clone(Some(&mut update))?;
+    if let Some(branch) = options.and_then(|x| x.branch.as_deref()) {
+      checkout_remote_branch(&sub_repo, branch)?;
+      let name = submodule.name().unwrap_or(&self.path).to_string();
+      git2::Repository::open(repo.path())?.submodule_set_branch(&name, branch)?;
+    }
+    submodule.add_finalize()?;
+    Ok(())
+  }
+}
+
+/// Name of the remote a submodule fetches from.
+///
+/// This is the remote of the upstream of the current branch, or the only
+/// configured remote, falling back to `origin` like git does.
+fn default_remote(repo: &git2::Repository) -> crate::Result<String> {
+  if let Some(branch) = repo.head().ok().filter(|x| x.is_branch()) {
+    if let Some(name) = branch.name() {
+      if let Ok(remote) = repo.branch_upstream_remote(name) {
+        if let Some(remote) = remote.as_str() {
+          return Ok(remote.to_string());
+        }
+      }
+    }
+  }
+  let remotes = repo.remotes()?;
+  match (remotes.len(), remotes.get(0)) {
+    (1, Some(remote)) => Ok(remote.to_string()),
+    _ => Ok("origin".to_string()),
+  }
+}
+
+/// Check out the local branch tracking the remote branch of the submodule's
+/// remote.
+fn checkout_remote_branch(repo: &git2::Repository, branch: &str) -> crate::Result<()> {
+  let remote = default_remote(repo)?;
+  let commit = repo
+    .find_reference(&format!("refs/remotes/{remote}/{branch}"))?
+    .peel_to_commit()?;
+  if repo.find_branch(branch, git2::BranchType::Local).is_err() {
+    let mut local = repo.branch(branch, &commit, false)?;
+    local.set_upstream(Some(&format!("{remote}/{branch}")))?;
+  }
+  let mut checkout = git2::build::CheckoutBuilder::new();
+  checkout.safe();
+  repo.checkout_tree(commit.as_object(), Some(&mut checkout))?;
+  repo.set_head(&format!("refs/heads/{branch}"))?;
+  Ok(())
+}
+
+#[napi]
+impl Task for SubmoduleAddTask {
+  type Output = ();
+  type JsValue = Submodule;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.add()?;
+    Ok(())
+  }
+
+  fn resolve(&mut self, env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+    let path = self.path.clone();
+    let inner = self.repo.clone(env)?.share_with(env, move |repo| {
+      repo
+        .inner
+        .find_submodule(&path)
+        .map_err(crate::Error::from)
+        .map_err(Into::into)
+    })?;
+    Ok(Submodule { inner })
+  }
+}
+
+#[napi(object, object_to_js = false)]
+/// Options to update all submodules.
+pub struct SubmoduleUpdateAllOptions {
+  /// Initialize submodules which are not initialized yet.
+  pub init: Option<bool>,
+  /// Update submodules of the submodules recursively.
+  pub recursive: Option<bool>,
+  /// Update submodules to the latest commit of the remote branch instead of
+  /// the commit recorded in the superproject, like `git submodule update --remote`.
+  ///
+  /// The branch is the `branch` of the submodule, or the default branch of
+  /// the remote if not set. `HEAD` of the submodule is detached at the commit.
+  pub remote: Option<bool>,
+  /// These options are passed to the checkout step.
+  pub checkout: Option<CheckoutOptions>,
+  /// Options which control the fetch, including callbacks.
+  pub fetch: Option<FetchOptions>,
+  /// Allow fetching from the submodule's default remote if the target commit isn't found.
+  /// Default: `true`.
+  pub allow_fetch: Option<bool>,
+}
+
+pub struct SubmoduleUpdateAllTask {
+  repo: Reference<Repository>,
+  options: Option<SubmoduleUpdateAllOptions>,
+}
+
+unsafe impl Send for SubmoduleUpdateAllTask {}
+
+fn update_all(repo: &git2::Repository, options: Option<&SubmoduleUpdateAllOptions>) -> crate::Result<()> {
+  let init = options.and_then(|x| x.init).unwrap_or_default();
+  for mut submodule in repo.submodules()? {
+    let mut opts = match options {
+      Some(options) => update_options(options.checkout.as_ref(), options.fetch.as_ref(), options.allow_fetch)?,
+      None => git2::SubmoduleUpdateOptions::default(),
+    };
+    // Uninitialized submodules are skipped unless `init` is set, like git does.
+    if !init
+      && repo
+        .config()?
+        .get_string(&submodule_config_key(&submodule, "url"))
+        .is_err()
+    {
+      continue;
+    }
+    submodule.update(init, Some(&mut opts))?;
+    let sub_repo = submodule.open()?;
+    if let Some(true) = options.and_then(|x| x.remote) {
+      update_to_remote(&sub_repo, submodule.branch(), options)?;
+    }
+    if let Some(true) = options.and_then(|x| x.recursive) {
+      update_all(&sub_repo, options)?;
+    }
+  }
+  Ok(())
+}
+
+fn submodule_config_key(submodule: &git2::Submodule, key: &str) -> String {
+  format!("submodule.{}.{key}", String::from_utf8_lossy(submodule.name_bytes()))
+}
+
+/// Fetch the remote branch and detach `HEAD` of the submodule at the fetched commit.
+///
+/// Without a configured branch, the default branch of the remote is used.
+fn update_to_remote(
+  repo: &git2::Repository,
+  branch: Option<&str>,
+  options: Option<&SubmoduleUpdateAllOptions>,
+) -> crate::Result<()> {
+  let remote_name = default_remote(repo)?;
+  let mut remote = repo.find_remote(&remote_name)?;
+  let mut fetch = match options.and_then(|x| x.fetch.as_ref()) {
+    Some(fetch) => fetch.to_git2_fetch_options()?,
+    None => git2::FetchOptions::new(),
+  };
+  let branch = match branch {
+    Some(branch) => branch.to_string(),
+    None => {
+      remote.fetch::<&str>(&[], Some(&mut fetch), None)?;
+      let default_branch = remote.default_branch()?;
+      let default_branch = std::str::from_utf8(&default_branch)?;
+      default_branch
+        .strip_prefix("refs/heads/")
+        .unwrap_or(default_branch)
+        .to_string()
+    }
+  };
+  let tracking = format!("refs/remotes/{remote_name}/{branch}");
+  let refspec = format!("+refs/heads/{branch}:{tracking}");
+  let mut fetch = match options.and_then(|x| x.fetch.as_ref()) {
+    Some(fetch) => fetch.to_git2_fetch_options()?,
+    None => git2::FetchOptions::new(),
+  };
+  remote.fetch(&[refspec.as_str()], Some(&mut fetch), None)?;
+  let commit = repo.find_reference(&tracking)?.peel_to_commit()?;
+  let mut checkout = match options.and_then(|x| x.checkout.as_ref()) {
+    Some(checkout) => checkout.into(),
+    None => git2::build::CheckoutBuilder::new(),
+  };
+  repo.checkout_tree(commit.as_object(), Some(&mut checkout))?;
+  repo.set_head_detached(commit.id())?;
+  Ok(())
+}
+
+#[napi]
+impl Task for SubmoduleUpdateAllTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    update_all(&self.repo.inner, self.options.as_ref())?;
+    Ok(())
+  }
+
+  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+    Ok(())
+  }
+}
+
+#[napi(object)]
+/// Options to deinitialize a submodule.
+pub struct SubmoduleDeinitOptions {
+  /// Remove the working tree of the submodule even if it contains local
+  /// modifications.
+  pub force: Option<bool>,
+}
+
+#[napi(object)]
+/// A submodule visited by `submoduleForeach`.
+pub struct SubmoduleForeachEntry {
+  /// Name of the submodule.
+  pub name: String,
+  /// Path of the submodule, relative to the top-level repository.
+  pub path: String,
+  /// Commit id of the submodule recorded in the immediate superproject.
+  pub oid: Option<String>,
+  /// Absolute path of the top-level repository.
+  pub toplevel: String,
+}
+
+#[napi(object)]
+/// Options to iterate submodules.
+pub struct SubmoduleForeachOptions {
+  /// Iterate submodules of the submodules recursively.
+  pub recursive: Option<bool>,
+}
+
+/// Visit checked out submodules, returning `false` if the iteration is stopped.
+fn foreach_submodule(
+  repo: &git2::Repository,
+  prefix: &Path,
+  toplevel: &str,
+  recursive: bool,
+  callback: &Function<'_, SubmoduleForeachEntry, Option<bool>>,
+) -> crate::Result<bool> {
+  for submodule in repo.submodules()? {
+    // Like git, only submodules which are checked out are visited.
+    let Ok(sub_repo) = submodule.open() else {
+      continue;
+    };
+    let path = prefix.join(submodule.path());
+    let entry = SubmoduleForeachEntry {
+      name: String::from_utf8_lossy(submodule.name_bytes()).to_string(),
+      path: util::path_to_string(&path),
+      oid: submodule.index_id().or(submodule.head_id()).map(|x| x.to_string()),
+      toplevel: toplevel.to_string(),
+    };
+    if let Some(false) = callback.call(entry)? {
+      return Ok(false);
+    }
+    if recursive && !foreach_submodule(&sub_repo, &path, toplevel, recursive, callback)? {
+      return Ok(false);
+    }
+  }
+  Ok(true)
+}
+
+#[napi(object)]
+/// Options to summarize submodules.
+pub struct SubmoduleSummaryOptions {
+  /// Summarize submodules of the submodules recursively.
+  pub recursive: Option<bool>,
+  /// The ignore rules to follow when checking the working tree of
+  /// submodules.
+  ///
+  /// Default: `Unspecified`, which uses the `submodule.<name>.ignore`
+  /// configuration.
+  pub ignore: Option<SubmoduleIgnore>,
+}
+
+#[napi(object)]
+/// Summary of a submodule, like `git submodule status` and
+/// `git submodule summary`.
+pub struct SubmoduleSummary {
+  /// Name of the submodule.
+  pub name: String,
+  /// Path of the submodule, relative to the top-level repository.
+  pub path: String,
+  /// Commit id of the submodule recorded in the index of the superproject,
+  /// or `HEAD` if the submodule is not in the index.
+  pub recorded_oid: Option<String>,
+  /// Commit id of `HEAD` of the submodule which is checked out.
+  pub checked_out_oid: Option<String>,
+  /// Number of commits the checked out commit is ahead of the recorded
+  /// commit.
+  ///
+  /// `null` if either commit is missing in the submodule repository.
+  pub ahead: Option<u32>,
+  /// Number of commits the checked out commit is behind the recorded commit.
+  ///
+  /// `null` if either commit is missing in the submodule repository.
+  pub behind: Option<u32>,
+  /// Whether the submodule is initialized, which means the URL of the
+  /// submodule is in the repository configuration.
+  pub initialized: bool,
+  /// Whether the submodule is checked out in the working directory.
+  pub checked_out: bool,
+  /// Whether the working directory or the index of the submodule has
+  /// modified files.
+  pub modified: bool,
+  /// Whether the working directory of the submodule has untracked files.
+  pub untracked: bool,
+}
+
+fn summarize_submodules(
+  repo: &git2::Repository,
+  prefix: &Path,
+  recursive: bool,
+  ignore: git2::SubmoduleIgnore,
+  summaries: &mut Vec<SubmoduleSummary>,
+) -> crate::Result<()> {
+  let config = repo.config()?;
+  for submodule in repo.submodules()? {
+    let name = String::from_utf8_lossy(submodule.name_bytes()).to_string();
+    let status = repo.submodule_status(&name, ignore)?;
+    let path = prefix.join(submodule.path());
+    let recorded = submodule.index_id().or(submodule.head_id());
+    let checked_out = submodule.workdir_id();
+    let sub_repo = submodule.open().ok();
+    let (ahead, behind) = match (&sub_repo, checked_out, recorded) {
+      (Some(sub_repo), Some(checked_out), Some(recorded)) => match sub_repo.graph_ahead_behind(checked_out, recorded) {
+        Ok((ahead, behind)) => (Some(ahead as u32), Some(behind as u32)),
+        Err(_) => (None, None),
+      },
+      _ => (None, None),
+    };
+    summaries.push(SubmoduleSummary {
+      name,
+      path: util::path_to_string(&path),
+      recorded_oid: recorded.map(|x| x.to_string()),
+      checked_out_oid: checked_out.map(|x| x.to_string()),
+      ahead,
+      behind,
+      initialized: config.get_string(&submodule_config_key(&submodule, "url")).is_ok(),
+      checked_out: sub_repo.is_some(),
+      modified: status.intersects(git2::SubmoduleStatus::WD_INDEX_MODIFIED | git2::SubmoduleStatus::WD_WD_MODIFIED),
+      untracked: status.contains(git2::SubmoduleStatus::WD_UNTRACKED),
+    });
+    if let (true, Some(sub_repo)) = (recursive, &sub_repo) {
+      summarize_submodules(sub_repo, &path, recursive, ignore, summaries)?;
+    }
+  }
+  Ok(())
+}
+
 #[napi]
 impl Submodule {
   #[napi]
@@ -809,6 +1193,89 @@ impl Submodule {
       signal,
     ))
   }
+
+  #[napi]
+  /// Deinitialize the submodule, like `git submodule deinit`.
+  ///
+  /// This removes the working tree of the submodule (leaving an empty
+  /// directory) and the `submodule.<name>.*` entries from the repository
+  /// configuration. The submodule is kept in `.gitmodules` and the index, so
+  /// it can be initialized again with `init()`.
+  ///
+  /// @category Submodule/Methods
+  /// @signature
+  /// ```ts
+  /// class Submodule {
+  ///   deinit(options?: SubmoduleDeinitOptions | null | undefined): void;
+  /// }
+  /// ```
+  ///
+  /// @param {SubmoduleDeinitOptions} [options] - Options to deinitialize the submodule.
+  /// @throws If the working tree of the submodule contains local modifications,
+  /// unless `force` is set.
+  /// @throws If the working tree of the submodule contains a `.git` directory.
+  pub fn deinit(&self, env: Env, options: Option<SubmoduleDeinitOptions>) -> crate::Result<()> {
+    let repo = self.inner.clone_owner(env)?;
+    let name = std::str::from_utf8(self.inner.name_bytes())?.to_string();
+    let force = options.and_then(|x| x.force).unwrap_or_default();
+    if !force {
+      let status = repo.inner.submodule_status(&name, git2::SubmoduleIgnore::None)?;
+      let modified = git2::SubmoduleStatus::WD_INDEX_MODIFIED
+        | git2::SubmoduleStatus::WD_WD_MODIFIED
+        | git2::SubmoduleStatus::WD_UNTRACKED;
+      if status.intersects(modified) {
+        return Err(
+          git2::Error::new(
+            git2::ErrorCode::Modified,
+            git2::ErrorClass::Submodule,
+            format!("submodule '{name}' contains local modifications; use 'force' to discard them"),
+          )
+          .into(),
+        );
+      }
+    }
+    if let Some(workdir) = repo.inner.workdir() {
+      let path = workdir.join(self.inner.path());
+      if path.join(".git").is_dir() {
+        return Err(
+          git2::Error::new(
+            git2::ErrorCode::Invalid,
+            git2::ErrorClass::Submodule,
+            format!(
+              "submodule work tree '{}' contains a .git directory; absorb it into the superproject first",
+              self.inner.path().display()
+            ),
+          )
+          .into(),
+        );
+      }
+      if path.is_dir() {
+        for entry in std::fs::read_dir(&path)? {
+          let entry = entry?;
+          match entry.file_type()?.is_dir() {
+            true => std::fs::remove_dir_all(entry.path())?,
+            false => std::fs::remove_file(entry.path())?,
+          }
+        }
+      }
+    }
+    let mut config = repo.inner.config()?.open_level(git2::ConfigLevel::Local)?;
+    let prefix = format!("submodule.{name}.");
+    let mut names = Vec::new();
+    let mut entries = config.entries(None)?;
+    while let Some(entry) = entries.next() {
+      if let Some(entry_name) = entry?.name() {
+        if entry_name.starts_with(&prefix) {
+          names.push(entry_name.to_string());
+        }
+      }
+    }
+    drop(entries);
+    for entry_name in names {
+      config.remove(&entry_name)?;
+    }
+    Ok(())
+  }
 }
 
 #[napi]
@@ -969,6 +1436,40 @@ impl Repository {
     Ok(status)
   }
 
+  #[napi]
+  /// Summarize the submodules of the repository, like `git submodule status`
+  /// and `git submodule summary`.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   submoduleSummary(options?: SubmoduleSummaryOptions | null | undefined): SubmoduleSummary[];
+  /// }
+  /// ```
+  ///
+  /// @param {SubmoduleSummaryOptions} [options] - Options to summarize submodules.
+  /// @returns Summaries of the submodules.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// for (const summary of repo.submoduleSummary({ recursive: true })) {
+  ///   if (summary.ahead || summary.behind) {
+  ///     console.log(`${summary.path}: +${summary.ahead} -${summary.behind}`);
+  ///   }
+  /// }
+  /// ```
+  pub fn submodule_summary(&self, options: Option<SubmoduleSummaryOptions>) -> crate::Result<Vec<SubmoduleSummary>> {
+    let recursive = options.as_ref().and_then(|x| x.recursive).unwrap_or_default();
+    let ignore = options.and_then(|x| x.ignore).unwrap_or(SubmoduleIgnore::Unspecified);
+    let mut summaries = Vec::new();
+    summarize_submodules(&self.inner, Path::new(""), recursive, ignore.into(), &mut summaries)?;
+    Ok(summaries)
+  }
+
   #[napi]
   /// Set the ignore rule for the submodule in the configuration
   ///
@@ -1050,4 +1551,138 @@ impl Repository {
     self.inner.submodule_set_branch(&name, &branch_name)?;
     Ok(())
   }
+
+  #[napi]
+  /// Add a submodule, like `git submodule add`.
+  ///
+  /// This sets up the submodule, clones the repository, checks out the branch
+  /// and stages the submodule and `.gitmodules` in the index to be ready to
+  /// commit.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   submoduleAdd(
+  ///     url: string,
+  ///     path: string,
+  ///     options?: SubmoduleAddOptions | null | undefined,
+  ///     signal?: AbortSignal | null | undefined,
+  ///   ): Promise<Submodule>;
+  /// }
+  /// ```
+  ///
+  /// @param {string} url - URL for the submodule's remote.
+  /// @param {string} path - Path at which the submodule should be created.
+  /// @param {SubmoduleAddOptions} [options] - Options to add the submodule.
+  /// @param {AbortSignal} [signal] - Optional AbortSignal to cancel the operation.
+  /// @returns The added submodule.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// await repo.submoduleAdd('https://github.com/toss/es-toolkit', 'vendor/es-toolkit', {
+  ///   branch: 'main',
+  ///   depth: 1,
+  /// });
+  /// ```
+  pub fn submodule_add(
+    &self,
+    this: Reference<Repository>,
+    url: String,
+    path: String,
+    options: Option<SubmoduleAddOptions>,
+    signal: Option<AbortSignal>,
+  ) -> AsyncTask<SubmoduleAddTask> {
+    AsyncTask::with_optional_signal(
+      SubmoduleAddTask {
+        repo: this,
+        url,
+        path,
+        options,
+      },
+      signal,
+    )
+  }
+
+  #[napi]
+  /// Update all submodules of the repository, like `git submodule update`.
+  ///
+  /// Submodules which are not initialized are skipped, unless `init` is set.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   submoduleUpdateAll(
+  ///     options?: SubmoduleUpdateAllOptions | null | undefined,
+  ///     signal?: AbortSignal | null | undefined,
+  ///   ): Promise<void>;
+  /// }
+  /// ```
+  ///
+  /// @param {SubmoduleUpdateAllOptions} [options] - Options to update submodules.
+  /// @param {AbortSignal} [signal] - Optional AbortSignal to cancel the operation.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// // Same as `git submodule update --init --recursive`
+  /// await repo.submoduleUpdateAll({ init: true, recursive: true });
+  /// ```
+  pub fn submodule_update_all(
+    &self,
+    this: Reference<Repository>,
+    options: Option<SubmoduleUpdateAllOptions>,
+    signal: Option<AbortSignal>,
+  ) -> AsyncTask<SubmoduleUpdateAllTask> {
+    AsyncTask::with_optional_signal(SubmoduleUpdateAllTask { repo: this, options }, signal)
+  }
+
+  #[napi]
+  /// Call the callback for each submodule which is checked out, like
+  /// `git submodule foreach`.
+  ///
+  /// @category Repository/Methods
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   submoduleForeach(
+  ///     callback: (submodule: SubmoduleForeachEntry) => boolean | void,
+  ///     options?: SubmoduleForeachOptions | null | undefined,
+  ///   ): void;
+  /// }
+  /// ```
+  ///
+  /// @param {(submodule: SubmoduleForeachEntry) => boolean | void} callback - Callback for each
+  /// submodule. Returning `false` stops the iteration.
+  /// @param {SubmoduleForeachOptions} [options] - Options to iterate submodules.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('/path/to/repo');
+  /// repo.submoduleForeach(({ path, oid }) => {
+  ///   console.log(`${path}: ${oid}`);
+  /// }, { recursive: true });
+  /// ```
+  pub fn submodule_foreach(
+    &self,
+    #[napi(ts_arg_type = "(submodule: SubmoduleForeachEntry) => boolean | void")] callback: Function<
+      SubmoduleForeachEntry,
+      Option<bool>,
+    >,
+    options: Option<SubmoduleForeachOptions>,
+  ) -> crate::Result<()> {
+    let toplevel = self.inner.workdir().unwrap_or_else(|| self.inner.path());
+    let toplevel = util::path_to_string(toplevel);
+    let recursive = options.and_then(|x| x.recursive).unwrap_or_default();
+    foreach_submodule(&self.inner, Path::new(""), &toplevel, recursive, &callback)?;
+    Ok(())
+  }
 }
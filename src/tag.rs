@@ -1,6 +1,7 @@
 use crate::object::{GitObject, ObjectInner, ObjectType};
 use crate::repository::Repository;
 use crate::signature::{Signature, SignaturePayload};
+use crate::signing::{sign_payload, split_signature, SignerOptions};
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
@@ -104,6 +105,50 @@ impl Tag {
     })
   }
 
+  #[napi]
+  /// Get the signature of a signed tag.
+  ///
+  /// @category Tag/Methods
+  /// @signature
+  /// ```ts
+  /// class Tag {
+  ///   signature(): string | null;
+  /// }
+  /// ```
+  ///
+  /// @returns Armored PGP, X.509 or SSH signature block of this tag.
+  /// Returns `null` if the tag is not signed.
+  pub fn signature(&self, env: Env) -> crate::Result<Option<String>> {
+    let content = self.raw_content(env)?;
+    let signature = match split_signature(&content) {
+      Some((_, signature)) => Some(std::str::from_utf8(signature)?.to_string()),
+      None => None,
+    };
+    Ok(signature)
+  }
+
+  #[napi]
+  /// Get the payload of a signed tag, which is the raw tag object without
+  /// its signature.
+  ///
+  /// @category Tag/Methods
+  /// @signature
+  /// ```ts
+  /// class Tag {
+  ///   signedPayload(): string | null;
+  /// }
+  /// ```
+  ///
+  /// @returns Data which is signed. Returns `null` if the tag is not signed.
+  pub fn signed_payload(&self, env: Env) -> crate::Result<Option<String>> {
+    let content = self.raw_content(env)?;
+    let payload = match split_signature(&content) {
+      Some((payload, _)) => Some(std::str::from_utf8(payload)?.to_string()),
+      None => None,
+    };
+    Ok(payload)
+  }
+
   #[napi]
   /// Get the tagger (author) of a tag.
   ///
@@ -175,6 +220,15 @@ impl Tag {
   }
 }
 
+impl Tag {
+  fn raw_content(&self, env: Env) -> crate::Result<Vec<u8>> {
+    let repo = self.inner.clone_owner(env)?;
+    let odb = repo.inner.odb()?;
+    let object = odb.read(self.inner.id())?;
+    Ok(object.data().to_vec())
+  }
+}
+
 #[napi(object)]
 pub struct CreateTagOptions {
   /// Signature for tagger.
@@ -184,6 +238,8 @@ pub struct CreateTagOptions {
   pub tagger: Option<SignaturePayload>,
   /// If `force` is true and a reference already exists with the given name, it'll be replaced.
   pub force: Option<bool>,
+  /// Signer to create a signed tag.
+  pub signer: Option<SignerOptions>,
 }
 
 #[napi(object)]
@@ -193,6 +249,52 @@ pub struct CreateAnnotationTagOptions {
   /// If not provided, default signature of repository will be used.
   /// If there is no default signature set for the repository, an error will occur.
   pub tagger: Option<SignaturePayload>,
+  /// Signer to create a signed tag.
+  pub signer: Option<SignerOptions>,
+}
+
+fn format_signature(signature: &git2::Signature) -> String {
+  let when = signature.when();
+  let offset = when.offset_minutes().abs();
+  format!(
+    "{} <{}> {} {}{:02}{:02}",
+    String::from_utf8_lossy(signature.name_bytes()),
+    String::from_utf8_lossy(signature.email_bytes()),
+    when.seconds(),
+    when.sign(),
+    offset / 60,
+    offset % 60,
+  )
+}
+
+/// Write a tag object signed with the signer, without creating a reference.
+pub(crate) fn create_signed_tag_object(
+  repo: &git2::Repository,
+  name: &str,
+  target: &git2::Object,
+  tagger: &git2::Signature,
+  message: &str,
+  signer: &SignerOptions,
+) -> crate::Result<git2::Oid> {
+  if !git2::Tag::is_valid_name(name) {
+    return Err(git2::Error::from_str(&format!("'{name}' is not a valid tag name")).into());
+  }
+  let kind = target.kind().unwrap_or(git2::ObjectType::Commit);
+  let mut content = format!(
+    "object {}\ntype {}\ntag {}\ntagger {}\n\n{}",
+    target.id(),
+    kind.str(),
+    name,
+    format_signature(tagger),
+    message,
+  );
+  if !content.ends_with('\n') {
+    content.push('\n');
+  }
+  let signature = sign_payload(repo, signer, &content)?;
+  content.push_str(&signature);
+  let oid = repo.odb()?.write(git2::ObjectType::Tag, content.as_bytes())?;
+  Ok(oid)
 }
 
 #[napi(object)]
@@ -388,23 +490,31 @@ impl Repository {
     message: String,
     options: Option<CreateTagOptions>,
   ) -> crate::Result<String> {
-    let (tagger, force) = match options {
+    let (tagger, force, signer) = match options {
       Some(opts) => {
         let tagger = opts.tagger.and_then(|x| Signature::try_from(x).ok());
         let force = opts.force.unwrap_or_default();
-        (tagger, force)
+        (tagger, force, opts.signer)
       }
-      None => (None, false),
+      None => (None, false, None),
     };
     let tagger = tagger
       .and_then(|x| git2::Signature::try_from(x).ok())
       .or_else(|| self.inner.signature().ok())
       .ok_or(crate::Error::SignatureNotFound)?;
-    let oid = self
-      .inner
-      .tag(&name, &target.inner, &tagger, &message, force)?
-      .to_string();
-    Ok(oid)
+    let oid = match signer {
+      Some(signer) => {
+        let refname = format!("refs/tags/{name}");
+        if !force && self.inner.find_reference(&refname).is_ok() {
+          return Err(git2::Error::new(git2::ErrorCode::Exists, git2::ErrorClass::Tag, "tag already exists").into());
+        }
+        let oid = create_signed_tag_object(&self.inner, &name, &target.inner, &tagger, &message, &signer)?;
+        self.inner.reference(&refname, oid, force, "")?;
+        oid
+      }
+      None => self.inner.tag(&name, &target.inner, &tagger, &message, force)?,
+    };
+    Ok(oid.to_string())
   }
 
   #[napi]
@@ -442,17 +552,22 @@ impl Repository {
     message: String,
     options: Option<CreateAnnotationTagOptions>,
   ) -> crate::Result<String> {
-    let tagger = options
-      .and_then(|x| x.tagger)
+    let (tagger, signer) = match options {
+      Some(opts) => (opts.tagger, opts.signer),
+      None => (None, None),
+    };
+    let tagger = tagger
       .and_then(|x| Signature::try_from(x).ok())
       .and_then(|x| git2::Signature::try_from(x).ok())
       .or_else(|| self.inner.signature().ok())
       .ok_or(crate::Error::SignatureNotFound)?;
-    let oid = self
-      .inner
-      .tag_annotation_create(&name, &target.inner, &tagger, &message)?
-      .to_string();
-    Ok(oid)
+    let oid = match signer {
+      Some(signer) => create_signed_tag_object(&self.inner, &name, &target.inner, &tagger, &message, &signer)?,
+      None => self
+        .inner
+        .tag_annotation_create(&name, &target.inner, &tagger, &message)?,
+    };
+    Ok(oid.to_string())
   }
 
   #[napi]
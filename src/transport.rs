@@ -0,0 +1,217 @@
+use crate::js::{JsCallback, JsCallbackExt};
+use napi::bindgen_prelude::{Buffer, FnArgs};
+use napi_derive::napi;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::thread::ThreadId;
+
+/// Services of the smart protocol requested to a transport.
+///
+/// - `UploadPackLs` : List references for fetching.
+/// - `UploadPack` : Negotiate and receive a packfile for fetching.
+/// - `ReceivePackLs` : List references for pushing.
+/// - `ReceivePack` : Send references updates and a packfile for pushing.
+#[napi(string_enum)]
+#[derive(Copy, Clone)]
+pub enum TransportService {
+  UploadPackLs,
+  UploadPack,
+  ReceivePackLs,
+  ReceivePack,
+}
+
+impl From<git2::transport::Service> for TransportService {
+  fn from(value: git2::transport::Service) -> Self {
+    match value {
+      git2::transport::Service::UploadPackLs => TransportService::UploadPackLs,
+      git2::transport::Service::UploadPack => TransportService::UploadPack,
+      git2::transport::Service::ReceivePackLs => TransportService::ReceivePackLs,
+      git2::transport::Service::ReceivePack => TransportService::ReceivePack,
+    }
+  }
+}
+
+pub type TransportHandler = JsCallback<FnArgs<(String, TransportService, Buffer)>, Buffer>;
+
+#[derive(Default)]
+struct Transports {
+  /// Schemes registered to libgit2, which can't be unregistered.
+  registered: HashSet<String>,
+  /// Handlers with the JavaScript thread they were registered on.
+  handlers: HashMap<String, (ThreadId, TransportHandler)>,
+}
+
+static TRANSPORTS: OnceLock<Mutex<Transports>> = OnceLock::new();
+
+fn transports() -> std::result::Result<MutexGuard<'static, Transports>, git2::Error> {
+  TRANSPORTS
+    .get_or_init(Default::default)
+    .lock()
+    .map_err(|_| git2::Error::from_str("transports are unavailable: a previous registration panicked"))
+}
+
+struct JsSubtransport {
+  scheme: String,
+}
+
+impl git2::transport::SmartSubtransport for JsSubtransport {
+  fn action(
+    &self,
+    url: &str,
+    action: git2::transport::Service,
+  ) -> std::result::Result<Box<dyn git2::transport::SmartSubtransportStream>, git2::Error> {
+    let (thread, handler) = transports()?
+      .handlers
+      .get(&self.scheme)
+      .cloned()
+      .ok_or_else(|| git2::Error::from_str(&format!("transport for '{}' is unregistered", self.scheme)))?;
+    // The handler runs on the JavaScript thread, so waiting for it there
+    // would never return.
+    if thread == std::thread::current().id() {
+      return Err(git2::Error::from_str(&format!(
+        "transport for '{}' can only be used by asynchronous operations",
+        self.scheme
+      )));
+    }
+    Ok(Box::new(JsStream {
+      handler,
+      url: url.to_string(),
+      service: action.into(),
+      request: Vec::new(),
+      response: None,
+      pos: 0,
+    }))
+  }
+
+  fn close(&self) -> std::result::Result<(), git2::Error> {
+    Ok(())
+  }
+}
+
+/// Stream of a single request, which is sent to the handler on the first read.
+struct JsStream {
+  handler: TransportHandler,
+  url: String,
+  service: TransportService,
+  request: Vec<u8>,
+  response: Option<Vec<u8>>,
+  pos: usize,
+}
+
+impl Read for JsStream {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    if self.response.is_none() {
+      let request = Buffer::from(std::mem::take(&mut self.request));
+      let response = self
+        .handler
+        .invoke((self.url.clone(), self.service, request).into())
+        .map_err(|e| std::io::Error::other(e.reason.clone()))?;
+      self.response = Some(response.to_vec());
+    }
+    let response = self.response.as_deref().unwrap_or_default();
+    let len = buf.len().min(response.len() - self.pos);
+    buf[..len].copy_from_slice(&response[self.pos..self.pos + len]);
+    self.pos += len;
+    Ok(len)
+  }
+}
+
+impl Write for JsStream {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.request.extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+#[napi]
+/// Register a custom transport for the URL scheme.
+///
+/// The transport works like the smart HTTP protocol: the handler is called
+/// once for each request with the URL of the remote, the requested service
+/// and the request body, and returns the whole response body. Responses of
+/// `UploadPackLs` and `ReceivePackLs` must start with the
+/// `# service=git-upload-pack` (or `git-receive-pack`) packet line followed by
+/// a flush packet, as the `info/refs` response of smart HTTP.
+///
+/// The handler is called synchronously while the remote operation waits for
+/// the response, so the transport is only available for asynchronous
+/// operations like `fetch`, `push`, `connect` and `cloneRepository`.
+/// Synchronous operations on the thread which registered the transport fail
+/// instead of waiting for the handler.
+///
+/// Registering a handler for a scheme which is already registered replaces
+/// the handler. `file://` and local paths are supported without registering
+/// a transport.
+///
+/// @category Transport
+/// @signature
+/// ```ts
+/// function registerTransport(
+///   scheme: string,
+///   handler: (url: string, service: TransportService, body: Buffer) => Buffer,
+/// ): void;
+/// ```
+///
+/// @param {string} scheme - URL scheme without `://` (e.g. `myproto`).
+/// @param {(url: string, service: TransportService, body: Buffer) => Buffer} handler - Handler which
+/// responds to requests of the smart protocol.
+///
+/// @example
+/// ```ts
+/// import { cloneRepository, registerTransport } from 'es-git';
+///
+/// registerTransport('myproto', (url, service, body) => {
+///   return myServer.handle(url, service, body);
+/// });
+/// const repo = await cloneRepository('myproto://example/repo', '/path/to/clone');
+/// ```
+pub fn register_transport(
+  scheme: String,
+  #[napi(ts_arg_type = "(url: string, service: TransportService, body: Buffer) => Buffer")] handler: TransportHandler,
+) -> crate::Result<()> {
+  let mut transports = transports()?;
+  if !transports.registered.contains(&scheme) {
+    let subtransport_scheme = scheme.clone();
+    // SAFETY: transports are registered while holding the lock, so no other
+    // thread registers a transport at the same time.
+    unsafe {
+      git2::transport::register(&scheme, move |remote| {
+        git2::transport::Transport::smart(
+          remote,
+          true,
+          JsSubtransport {
+            scheme: subtransport_scheme.clone(),
+          },
+        )
+      })?;
+    }
+    transports.registered.insert(scheme.clone());
+  }
+  transports
+    .handlers
+    .insert(scheme, (std::thread::current().id(), handler));
+  Ok(())
+}
+
+#[napi]
+/// Unregister the custom transport for the URL scheme.
+///
+/// Remote operations for the scheme fail after unregistering, until a
+/// transport is registered again.
+///
+/// @category Transport
+/// @signature
+/// ```ts
+/// function unregisterTransport(scheme: string): void;
+/// ```
+///
+/// @param {string} scheme - URL scheme without `://` (e.g. `myproto`).
+pub fn unregister_transport(scheme: String) -> crate::Result<()> {
+  transports()?.handlers.remove(&scheme);
+  Ok(())
+}
@@ -18,3 +18,113 @@ pub(crate) fn path_to_string(p: &Path) -> String {
 pub(crate) fn bitflags_contain<T: Flags>(source: T, target: T) -> bool {
   source.contains(target)
 }
+
+/// Match the text against the pattern like git's `wildmatch` with the
+/// `WM_PATHNAME` flag: `*` and `?` don't match `/`, while `**` matches
+/// across directories when it is a whole path component (`**/`, `/**/` or
+/// `/**`). Any other `**` behaves like `*`.
+///
+/// The match runs in linear passes without recursion: only the last `*` and
+/// the last `**` are remembered, as an earlier wildcard can't match anything
+/// a later one can't.
+pub(crate) fn wildmatch(pattern: &str, text: &str, ignore_case: bool) -> bool {
+  let p = pattern.chars().collect::<Vec<_>>();
+  let t = text.chars().collect::<Vec<_>>();
+  let eq = |a: char, b: char| match ignore_case {
+    true => a.to_lowercase().eq(b.to_lowercase()),
+    false => a == b,
+  };
+  let (mut pi, mut ti) = (0, 0);
+  // Pattern and text positions to resume from when a `*` has to match one
+  // more character.
+  let mut star: Option<(usize, usize)> = None;
+  // Pattern and text positions to resume from when a `**/` has to match one
+  // more directory.
+  let mut globstar: Option<(usize, usize)> = None;
+  while ti < t.len() {
+    if p.get(pi) == Some(&'*') {
+      let stars = p[pi..].iter().take_while(|x| **x == '*').count();
+      let delimited = (pi == 0 || p[pi - 1] == '/') && matches!(p.get(pi + stars), None | Some('/'));
+      if stars > 1 && delimited {
+        if pi + stars == p.len() {
+          return true;
+        }
+        pi += stars + 1;
+        globstar = Some((pi, ti));
+        star = None;
+      } else {
+        pi += stars;
+        star = Some((pi, ti));
+      }
+      continue;
+    }
+    let matched = match p.get(pi) {
+      None => None,
+      Some('?') => (t[ti] != '/').then_some(pi + 1),
+      Some('[') => match match_class(&p, pi, t[ti], ignore_case) {
+        Some((matched, end)) => (matched && t[ti] != '/').then_some(end),
+        // Unterminated bracket expression, match it literally.
+        None => eq('[', t[ti]).then_some(pi + 1),
+      },
+      Some('\\') if pi + 1 < p.len() => eq(p[pi + 1], t[ti]).then_some(pi + 2),
+      Some(c) => eq(*c, t[ti]).then_some(pi + 1),
+    };
+    if let Some(next) = matched {
+      pi = next;
+      ti += 1;
+      continue;
+    }
+    match (star, globstar) {
+      (Some((star_pi, star_ti)), _) if t[star_ti] != '/' => {
+        pi = star_pi;
+        ti = star_ti + 1;
+        star = Some((star_pi, ti));
+      }
+      (_, Some((globstar_pi, globstar_ti))) => {
+        let Some(slash) = t[globstar_ti..].iter().position(|x| *x == '/') else {
+          return false;
+        };
+        pi = globstar_pi;
+        ti = globstar_ti + slash + 1;
+        globstar = Some((pi, ti));
+        star = None;
+      }
+      _ => return false,
+    }
+  }
+  p[pi..].iter().all(|x| *x == '*')
+}
+
+/// Match the character against the bracket expression starting at `start`.
+///
+/// Returns whether it matched and the position after the expression, or
+/// `None` if the expression is unterminated.
+fn match_class(p: &[char], start: usize, c: char, ignore_case: bool) -> Option<(bool, usize)> {
+  let candidates = match ignore_case {
+    true => c.to_lowercase().chain(c.to_uppercase()).chain([c]).collect::<Vec<_>>(),
+    false => vec![c],
+  };
+  let negate = matches!(p.get(start + 1), Some('!') | Some('^'));
+  let mut i = if negate { start + 2 } else { start + 1 };
+  let mut matched = false;
+  let mut first = true;
+  while i < p.len() && (first || p[i] != ']') {
+    first = false;
+    if p.get(i + 1) == Some(&'-') && p.get(i + 2).is_some_and(|x| *x != ']') {
+      let (lo, hi) = (p[i], p[i + 2]);
+      if candidates.iter().any(|x| (lo..=hi).contains(x)) {
+        matched = true;
+      }
+      i += 3;
+    } else {
+      if candidates.contains(&p[i]) {
+        matched = true;
+      }
+      i += 1;
+    }
+  }
+  if i >= p.len() {
+    return None;
+  }
+  Some((matched != negate, i + 1))
+}
@@ -1,8 +1,9 @@
 use crate::repository::Repository;
 use crate::util::path_to_string;
 use napi_derive::napi;
+use std::fs;
 use std::ops::Deref;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[napi(object)]
 /// Lock Status of a worktree
@@ -90,6 +91,78 @@ impl From<git2::WorktreeLockStatus> for WorktreeLockStatus {
   }
 }
 
+#[napi(object)]
+/// Options to remove a worktree.
+pub struct WorktreeRemoveOptions {
+  /// Remove the worktree even if it is locked or contains modified or
+  /// untracked files.
+  ///
+  /// Defaults to `false`.
+  pub force: Option<bool>,
+}
+
+#[napi(object)]
+/// Details of a worktree, like `git worktree list --porcelain`.
+pub struct WorktreeInfo {
+  /// Name of the worktree. `null` for the main worktree.
+  pub name: Option<String>,
+  /// Path of the worktree.
+  pub path: String,
+  /// Commit id of `HEAD` of the worktree.
+  pub head: Option<String>,
+  /// Full name of the branch checked out in the worktree.
+  pub branch: Option<String>,
+  /// Whether the worktree is the main worktree of a bare repository.
+  pub bare: bool,
+  /// Whether `HEAD` of the worktree is detached.
+  pub detached: bool,
+  /// Whether the worktree is locked.
+  pub locked: bool,
+  /// Reason why the worktree is locked, if given.
+  pub lock_reason: Option<String>,
+  /// Whether the worktree can be pruned, because the working tree is missing.
+  pub prunable: bool,
+  /// Reason why the worktree can be pruned.
+  pub prunable_reason: Option<String>,
+}
+
+/// Read `HEAD` in the git directory of a worktree, returning the commit id and
+/// the branch checked out.
+fn read_head(repo: &git2::Repository, gitdir: &Path) -> (Option<git2::Oid>, Option<String>) {
+  let Ok(content) = fs::read_to_string(gitdir.join("HEAD")) else {
+    return (None, None);
+  };
+  let content = content.trim();
+  match content.strip_prefix("ref: ") {
+    Some(refname) => (repo.refname_to_id(refname).ok(), Some(refname.to_string())),
+    None => (git2::Oid::from_str(content).ok(), None),
+  }
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+  match (a.canonicalize(), b.canonicalize()) {
+    (Ok(a), Ok(b)) => a == b,
+    _ => false,
+  }
+}
+
+/// Point the `.git` file of a worktree to its git directory, returning `true`
+/// if the file is rewritten.
+fn repair_dotgit(dotgit: &Path, gitdir: &Path) -> crate::Result<bool> {
+  let content = fs::read_to_string(dotgit)?;
+  let target = content.trim().strip_prefix("gitdir: ").map(PathBuf::from);
+  let base = dotgit.parent().unwrap_or(Path::new(""));
+  if let Some(true) = target.map(|x| same_path(&base.join(x), gitdir)) {
+    return Ok(false);
+  }
+  fs::write(dotgit, format!("gitdir: {}\n", path_to_string(gitdir)))?;
+  Ok(true)
+}
+
+fn worktree_error(code: git2::ErrorCode, message: &str) -> crate::Error {
+  git2::Error::new(code, git2::ErrorClass::Worktree, message).into()
+}
+
 pub(crate) enum WorktreeInner {
   Owned(git2::Worktree),
 }
@@ -261,6 +334,85 @@ impl Worktree {
       .is_prunable(git2_worktree_prune_options.as_mut())
       .map_err(crate::Error::from)
   }
+
+  #[napi(js_name = "move")]
+  /// Move the worktree to a new path, like `git worktree move`.
+  ///
+  /// @category Worktree/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Worktree {
+  ///   move(newPath: string): void;
+  /// }
+  /// ```
+  ///
+  /// @param {string} newPath - New path of the worktree, which must not exist.
+  /// @throws Throws error if the worktree is locked or invalid, or if the new path already exists.
+  pub fn move_to(&mut self, new_path: String) -> crate::Result<()> {
+    if let git2::WorktreeLockStatus::Locked(_) = self.inner.is_locked()? {
+      return Err(worktree_error(git2::ErrorCode::Locked, "cannot move a locked worktree"));
+    }
+    self.inner.validate()?;
+    let repo = git2::Repository::open_from_worktree(&self.inner)?;
+    let new_path = std::env::current_dir()?.join(new_path);
+    if new_path.exists() {
+      return Err(worktree_error(
+        git2::ErrorCode::Exists,
+        &format!("'{}' already exists", path_to_string(&new_path)),
+      ));
+    }
+    fs::rename(self.inner.path(), &new_path)?;
+    fs::write(
+      repo.path().join("gitdir"),
+      format!("{}\n", path_to_string(&new_path.join(".git"))),
+    )?;
+    let repo = git2::Repository::open(&new_path)?;
+    self.inner = WorktreeInner::Owned(git2::Worktree::open_from_repository(&repo)?);
+    Ok(())
+  }
+
+  #[napi]
+  /// Remove the worktree and delete its working tree, like `git worktree remove`.
+  ///
+  /// @category Worktree/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Worktree {
+  ///   remove(options?: WorktreeRemoveOptions | null | undefined): void;
+  /// }
+  /// ```
+  ///
+  /// @param {WorktreeRemoveOptions} [options] - Options for removing the worktree.
+  /// @throws Throws error if the worktree is locked or contains modified or untracked files,
+  /// unless `force` is set.
+  pub fn remove(&self, options: Option<WorktreeRemoveOptions>) -> crate::Result<()> {
+    let force = options.and_then(|x| x.force).unwrap_or_default();
+    if !force {
+      if let git2::WorktreeLockStatus::Locked(_) = self.inner.is_locked()? {
+        return Err(worktree_error(
+          git2::ErrorCode::Locked,
+          "cannot remove a locked worktree; use 'force' to remove it",
+        ));
+      }
+      if self.inner.validate().is_ok() {
+        let repo = git2::Repository::open_from_worktree(&self.inner)?;
+        let mut status_options = git2::StatusOptions::new();
+        status_options.include_untracked(true);
+        if !repo.statuses(Some(&mut status_options))?.is_empty() {
+          return Err(worktree_error(
+            git2::ErrorCode::Modified,
+            "worktree contains modified or untracked files; use 'force' to remove it",
+          ));
+        }
+      }
+    }
+    let mut prune_options = git2::WorktreePruneOptions::new();
+    prune_options.valid(true).locked(true).working_tree(true);
+    self.inner.prune(Some(&mut prune_options))?;
+    Ok(())
+  }
 }
 
 #[napi]
@@ -332,6 +484,153 @@ impl Repository {
     Ok(worktree_names)
   }
 
+  #[napi]
+  /// List the main worktree and linked worktrees of the repository with their
+  /// details, like `git worktree list --porcelain`.
+  ///
+  /// @category Repository/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   worktreeList(): WorktreeInfo[];
+  /// }
+  /// ```
+  ///
+  /// @returns Details of the worktrees. The main worktree comes first.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// for (const worktree of repo.worktreeList()) {
+  ///   console.log(worktree.path, worktree.branch ?? worktree.head);
+  /// }
+  /// ```
+  pub fn worktree_list(&self) -> crate::Result<Vec<WorktreeInfo>> {
+    let commondir = self.inner.commondir();
+    let main = git2::Repository::open(commondir)?;
+    let bare = main.is_bare();
+    let (head, branch) = match bare {
+      true => (None, None),
+      false => read_head(&main, commondir),
+    };
+    let main_path = main.workdir().unwrap_or(commondir).components().collect::<PathBuf>();
+    let mut list = vec![WorktreeInfo {
+      name: None,
+      path: path_to_string(&main_path),
+      head: head.map(|x| x.to_string()),
+      detached: !bare && branch.is_none(),
+      branch,
+      bare,
+      locked: false,
+      lock_reason: None,
+      prunable: false,
+      prunable_reason: None,
+    }];
+    let mut names = self.worktrees()?;
+    names.sort();
+    for name in names {
+      let worktree = main.find_worktree(&name)?;
+      let (head, branch) = read_head(&main, &commondir.join("worktrees").join(&name));
+      let lock_reason = match worktree.is_locked()? {
+        git2::WorktreeLockStatus::Locked(reason) => Some(reason),
+        git2::WorktreeLockStatus::Unlocked => None,
+      };
+      let prunable = worktree.is_prunable(None)?;
+      list.push(WorktreeInfo {
+        name: Some(name),
+        path: path_to_string(worktree.path()),
+        head: head.map(|x| x.to_string()),
+        detached: branch.is_none(),
+        branch,
+        bare: false,
+        locked: lock_reason.is_some(),
+        lock_reason: lock_reason.flatten(),
+        prunable,
+        prunable_reason: match prunable {
+          true => worktree.validate().err().map(|e| e.message().to_string()),
+          false => None,
+        },
+      });
+    }
+    Ok(list)
+  }
+
+  #[napi]
+  /// Repair the links between the repository and its worktrees, like
+  /// `git worktree repair`.
+  ///
+  /// The `.git` file of each worktree is fixed to point to the repository. If
+  /// a worktree was moved manually, pass its new path to fix the repository's
+  /// link to the worktree.
+  ///
+  /// @category Repository/Methods
+  ///
+  /// @signature
+  /// ```ts
+  /// class Repository {
+  ///   worktreeRepair(paths?: string[] | null | undefined): string[];
+  /// }
+  /// ```
+  ///
+  /// @param {string[]} [paths] - New paths of worktrees which were moved manually.
+  /// @returns Names of the repaired worktrees.
+  ///
+  /// @example
+  /// ```ts
+  /// import { openRepository } from 'es-git';
+  ///
+  /// const repo = await openRepository('.');
+  /// // The worktree is moved with `mv ../feature ../feature-moved`.
+  /// repo.worktreeRepair(['../feature-moved']);
+  /// ```
+  pub fn worktree_repair(&self, paths: Option<Vec<String>>) -> crate::Result<Vec<String>> {
+    let worktrees_dir = self.inner.commondir().join("worktrees");
+    let mut repaired = Vec::new();
+    for name in self.worktrees()? {
+      let gitdir = worktrees_dir.join(&name);
+      let Ok(dotgit) = fs::read_to_string(gitdir.join("gitdir")) else {
+        continue;
+      };
+      let dotgit = PathBuf::from(dotgit.trim());
+      if dotgit.is_file() && repair_dotgit(&dotgit, &gitdir)? {
+        repaired.push(name);
+      }
+    }
+    let cwd = std::env::current_dir()?;
+    for path in paths.unwrap_or_default() {
+      let dotgit = cwd.join(path).join(".git");
+      let content = fs::read_to_string(&dotgit)?;
+      let Some(name) = content
+        .trim()
+        .strip_prefix("gitdir: ")
+        .and_then(|x| Path::new(x).file_name())
+        .and_then(|x| x.to_str())
+        .map(String::from)
+      else {
+        continue;
+      };
+      let gitdir = worktrees_dir.join(&name);
+      if !gitdir.is_dir() {
+        continue;
+      }
+      let mut changed = repair_dotgit(&dotgit, &gitdir)?;
+      let linked = fs::read_to_string(gitdir.join("gitdir"))
+        .map(|x| same_path(Path::new(x.trim()), &dotgit))
+        .unwrap_or_default();
+      if !linked {
+        fs::write(gitdir.join("gitdir"), format!("{}\n", path_to_string(&dotgit)))?;
+        changed = true;
+      }
+      if changed && !repaired.contains(&name) {
+        repaired.push(name);
+      }
+    }
+    Ok(repaired)
+  }
+
   #[napi]
   /// Tests whether this repository is a worktree.
   ///
@@ -1,4 +1,6 @@
-import { describe, expect, it } from 'vitest';
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { describe, expect, it, vi } from 'vitest';
 import { type BlameHunk, openRepository } from '../index';
 import { useFixture } from './fixtures';
 
@@ -161,4 +163,145 @@ describe('blame', () => {
     const collectedHunks: BlameHunk[] = [...blame.iter()];
     expect(collectedHunks.length).toBe(blame.getHunkCount());
   });
+
+  it('should iterate lines with their hunks', async () => {
+    const p = await useFixture('blame');
+    const repo = await openRepository(p);
+    const blame = repo.blameFile('blame');
+
+    const lines = [...blame.iterLines()];
+    expect(lines).toHaveLength(10);
+    expect(lines[1]).toMatchObject({
+      lineNumber: 2,
+      content: 'Line 2 modified by Seokju Me',
+      hunk: { finalCommitId: 'a4187e439680498bd49c7d1623b345853b315d24' },
+    });
+    expect(lines[9]?.content).toBe('Line 10');
+  });
+
+  it('should ignore revisions', async () => {
+    const p = await useFixture('blame');
+    const repo = await openRepository(p);
+    const blame = repo.blameFile('blame', { ignoreRevs: ['a4187e439680498bd49c7d1623b345853b315d24'] });
+
+    const line2Hunk = blame.getHunkByLine(2);
+    expect(line2Hunk.finalCommitId).toBe('31592cf12bf62547f7650d494a21ef13ec948726');
+    expect(line2Hunk.finalSignature?.name).toBe('Seokju Na');
+    expect(line2Hunk.finalStartLineNumber).toBe(1);
+    expect(line2Hunk.linesInHunk).toBe(5);
+    expect(blame.getHunkByLine(7).finalCommitId).toBe('e4ef0685ef71b4a7902b17bc1e2386413520af52');
+    expect(() => blame.buffer(Buffer.from('Line 1\n'))).toThrowError();
+  });
+
+  it('should ignore revisions from ignore revs files', async () => {
+    const p = await useFixture('blame');
+    const repo = await openRepository(p);
+    await fs.writeFile(
+      path.join(p, '.git-blame-ignore-revs'),
+      '# Reformat\ne4ef0685ef71b4a7902b17bc1e2386413520af52\n'
+    );
+    repo.config().setString('blame.ignoreRevsFile', '.git-blame-ignore-revs');
+    const blame = repo.blameFile('blame');
+
+    expect(blame.getHunkByLine(7).finalCommitId).toBe('bf919193432351369043c79546ebef2f2c0ae88c');
+    expect(blame.getHunkByLine(9).finalCommitId).toBe('bf919193432351369043c79546ebef2f2c0ae88c');
+    expect(blame.getHunkByLine(2).finalCommitId).toBe('a4187e439680498bd49c7d1623b345853b315d24');
+  });
+
+  it('should skip a missing ignore revs file from config', async () => {
+    const p = await useFixture('blame');
+    const repo = await openRepository(p);
+    repo.config().setString('blame.ignoreRevsFile', '.git-blame-ignore-revs');
+    const blame = repo.blameFile('blame');
+
+    expect(blame.getHunkByLine(7).finalCommitId).toBe('e4ef0685ef71b4a7902b17bc1e2386413520af52');
+    expect(() => repo.blameFile('blame', { ignoreRevsFile: '.git-blame-ignore-revs' })).toThrowError();
+  });
+
+  it('should blame file asynchronously with progress', async () => {
+    const p = await useFixture('blame');
+    const repo = await openRepository(p);
+    const progress = vi.fn();
+    const blame = await repo.blameFileAsync('blame', { progress });
+
+    expect(progress).toHaveBeenLastCalledWith(10, 10);
+    expect([...blame.iter()]).toEqual([...repo.blameFile('blame').iter()]);
+    expect([...blame.iterLines()].map(x => x.content)).toContain('Line 10');
+
+    const ignoreRevs = ['a4187e439680498bd49c7d1623b345853b315d24'];
+    const ignored = await repo.blameFileAsync('blame', { ignoreRevs, progress });
+    expect([...ignored.iter()]).toEqual([...repo.blameFile('blame', { ignoreRevs }).iter()]);
+  });
+
+  it('should list lines with original paths', async () => {
+    const p = await useFixture('blame');
+    const repo = await openRepository(p);
+    const blame = repo.blameFile('blame', { minLine: 2, maxLine: 3 });
+
+    const lines = blame.lines();
+    expect(lines.map(x => x.lineNumber)).toEqual([2, 3]);
+    expect(lines[0]).toMatchObject({
+      content: 'Line 2 modified by Seokju Me',
+      origLineNumber: 2,
+      origPath: 'blame',
+    });
+  });
+
+  it('should format blame in porcelain format', async () => {
+    const p = await useFixture('blame');
+    const repo = await openRepository(p);
+    const blame = repo.blameFile('blame');
+
+    const porcelain = blame.toPorcelain();
+    expect(porcelain.startsWith('31592cf12bf62547f7650d494a21ef13ec948726 1 1 1\nauthor Seokju Na\n')).toBe(true);
+    expect(porcelain).toContain('author-mail <seokju.na@gmail.com>\n');
+    expect(porcelain).toContain('boundary\nfilename blame\n\tLine 1\n');
+    expect(porcelain).toContain(
+      'previous 31592cf12bf62547f7650d494a21ef13ec948726 blame\nfilename blame\n\tLine 2 modified by Seokju Me\n'
+    );
+    expect(porcelain).toContain('31592cf12bf62547f7650d494a21ef13ec948726 3 3 3\n\tLine 3\n');
+    expect(porcelain.match(/^author /gm)).toHaveLength(4);
+
+    const linePorcelain = blame.toPorcelain({ lineFormat: true });
+    expect(linePorcelain.match(/^author /gm)).toHaveLength(10);
+    expect(linePorcelain.match(/^filename blame$/gm)).toHaveLength(10);
+  });
+
+  it('should map the committer with the mailmap in porcelain format', async () => {
+    const p = await useFixture('blame');
+    const repo = await openRepository(p);
+    await fs.writeFile(path.join(p, '.mailmap'), 'Mapped Name <mapped@example.com> <seokju.na@gmail.com>\n');
+
+    const porcelain = repo.blameFile('blame', { useMailmap: true }).toPorcelain();
+    expect(porcelain).toContain('author Mapped Name\nauthor-mail <mapped@example.com>\n');
+    expect(porcelain).toContain('committer Mapped Name\ncommitter-mail <mapped@example.com>\n');
+    expect(porcelain).not.toContain('seokju.na@gmail.com');
+
+    const unmapped = repo.blameFile('blame').toPorcelain();
+    expect(unmapped).toContain('committer Seokju Na\ncommitter-mail <seokju.na@gmail.com>\n');
+  });
+
+  it('should use the path before a rename as previous in porcelain format', async () => {
+    const p = await useFixture('blame');
+    const repo = await openRepository(p);
+    const parent = repo.head().target()!;
+    const content = await fs.readFile(path.join(p, 'blame'), 'utf-8');
+    await fs.rm(path.join(p, 'blame'));
+    await fs.writeFile(path.join(p, 'renamed'), content.replace('Line 3\n', 'Line 3 renamed\n'));
+    const index = repo.index();
+    index.removePath('blame');
+    index.addPath('renamed');
+    index.write();
+    const signature = { name: 'Renamer', email: 'renamer@example.com' };
+    const oid = repo.commit(repo.getTree(index.writeTree()), 'rename', {
+      updateRef: 'HEAD',
+      author: signature,
+      committer: signature,
+      parents: [parent],
+    });
+
+    const porcelain = repo.blameFile('renamed').toPorcelain();
+    expect(porcelain).toContain(`previous ${parent} blame\nfilename renamed\n\tLine 3 renamed\n`);
+    expect(porcelain).toContain(`${oid} 3 3 1\nauthor Renamer\n`);
+  });
 });
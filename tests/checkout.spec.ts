@@ -1,6 +1,6 @@
 import fs from 'node:fs/promises';
 import path from 'node:path';
-import { describe, expect, it } from 'vitest';
+import { describe, expect, it, vi } from 'vitest';
 import { openRepository } from '../index';
 import { useFixture } from './fixtures';
 
@@ -73,4 +73,83 @@ describe('checkout', () => {
     expect(() => repo.checkoutIndex()).toThrowError();
     expect(() => repo.checkoutIndex(index)).toThrowError();
   });
+
+  it('list conflicted paths in error when checkout is prevented', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    await fs.writeFile(path.join(p, 'second'), 'modified');
+    const firstCommit = repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002');
+    expect(() => repo.checkoutTree(firstCommit.asObject())).toThrowError(/conflict prevents checkout: second/);
+  });
+
+  it('notify and progress callbacks', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const firstCommit = repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002');
+    const notify = vi.fn();
+    const progress = vi.fn();
+    repo.checkoutTree(firstCommit.asObject(), {
+      notifyOn: ['Updated'],
+      notify,
+      progress,
+    });
+    expect(notify).toHaveBeenCalledWith(
+      expect.objectContaining({
+        type: 'Updated',
+        path: 'second',
+        baseline: expect.objectContaining({ path: 'second', mode: 'Blob' }),
+      })
+    );
+    expect(notify.mock.calls.every(([x]) => x.type === 'Updated')).toBe(true);
+    expect(progress).toHaveBeenCalled();
+    const [, completed, total] = progress.mock.calls.at(-1)!;
+    expect(completed).toEqual(total);
+  });
+
+  it('cancel checkout from notify callback', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const firstCommit = repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002');
+    expect(() =>
+      repo.checkoutTree(firstCommit.asObject(), {
+        notify: () => false,
+      })
+    ).toThrowError();
+    await expect(fs.readFile(path.join(p, 'second'), 'utf8')).resolves.toBeDefined();
+  });
+
+  it('throw error of notify callback', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const firstCommit = repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002');
+    expect(() =>
+      repo.checkoutTree(firstCommit.asObject(), {
+        notify: () => {
+          throw new Error('notify failed');
+        },
+      })
+    ).toThrowError(/notify failed/);
+  });
+
+  it('checkout dry run', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    await fs.writeFile(path.join(p, 'second'), 'modified');
+    const firstCommit = repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002');
+    const result = repo.checkoutDryRun(firstCommit.asObject());
+    expect(result).toContainEqual(
+      expect.objectContaining({
+        type: 'Conflict',
+        path: 'second',
+      })
+    );
+    await expect(fs.readFile(path.join(p, 'second'), 'utf8')).resolves.toEqual('modified');
+  });
+
+  it('checkout dry run lists only touched files', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    await fs.writeFile(path.join(p, 'untracked'), 'untracked');
+    expect(repo.checkoutDryRun()).toEqual([]);
+  });
 });
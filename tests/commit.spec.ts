@@ -1,8 +1,11 @@
+import { execFileSync } from 'node:child_process';
 import fs from 'node:fs/promises';
 import path from 'node:path';
-import { describe, expect, it } from 'vitest';
+import { describe, expect, it, vi } from 'vitest';
 import { isValidOid, openRepository } from '../index';
+import { isTarget } from './env';
 import { useFixture } from './fixtures';
+import { makeTmpDir } from './tmp';
 
 describe('commit', () => {
   const signature = { name: 'Seokju Na', email: 'seokju.me@gmail.com' };
@@ -116,4 +119,207 @@ describe('commit', () => {
     const signatureInfo = repo.extractSignature(oid);
     expect(signatureInfo).toBeNull();
   });
+
+  it('create signed commit with signer callback', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const tree = repo.head().peelToTree();
+    const fakeSignature = '-----BEGIN PGP SIGNATURE-----\n\niQEcBAABAgAGBQJTest123\n-----END PGP SIGNATURE-----';
+    const callback = vi.fn().mockReturnValue(fakeSignature);
+    const oid = repo.commit(tree, 'signed commit', {
+      updateRef: 'HEAD',
+      author: signature,
+      committer: signature,
+      parents: [repo.head().target()!],
+      signer: { callback },
+    });
+    expect(callback).toHaveBeenCalledTimes(1);
+    expect(repo.head().target()).toEqual(oid);
+    const signatureInfo = repo.extractSignature(oid);
+    expect(signatureInfo?.signature).toEqual(fakeSignature);
+    expect(signatureInfo?.signedData).toEqual(callback.mock.calls[0]![0]);
+  });
+
+  it('refuse to update the reference of a signed commit if it moved', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const tree = repo.head().peelToTree();
+    const fakeSignature = '-----BEGIN PGP SIGNATURE-----\n\niQEcBAABAgAGBQJTest123\n-----END PGP SIGNATURE-----';
+    expect(() =>
+      repo.commit(tree, 'signed commit', {
+        updateRef: 'HEAD',
+        author: signature,
+        committer: signature,
+        parents: ['b33e0101b828225f77eeff4dfa31259dcf379002'],
+        signer: { callback: () => fakeSignature },
+      })
+    ).toThrowError(/current tip is not the expected commit/);
+    expect(repo.head().target()).toEqual('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+  });
+
+  it('amend commit with signer callback', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const commit = repo.getCommit(repo.head().target()!);
+    const fakeSignature = '-----BEGIN PGP SIGNATURE-----\n\niQEcBAABAgAGBQJTest123\n-----END PGP SIGNATURE-----';
+    const oid = commit.amend({
+      updateRef: 'HEAD',
+      message: 'amended',
+      signer: { callback: () => fakeSignature },
+    });
+    expect(repo.head().target()).toEqual(oid);
+    const amended = repo.getCommit(oid);
+    expect(amended.message()).toEqual('amended');
+    expect(amended.author()).toEqual(commit.author());
+    expect(repo.extractSignature(oid)?.signature).toEqual(fakeSignature);
+  });
+
+  it('verify commit signature returns null for unsigned commit', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    expect(repo.verifyCommitSignature(repo.head().target()!)).toBeNull();
+  });
+
+  it.skipIf(isTarget('win32'))('sign and verify commit with ssh key', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const keyDir = await makeTmpDir('ssh-key');
+    const keyPath = path.join(keyDir, 'id_ed25519');
+    execFileSync('ssh-keygen', ['-q', '-t', 'ed25519', '-N', '', '-C', 'signer@example.com', '-f', keyPath]);
+    const publicKey = await fs.readFile(`${keyPath}.pub`, 'utf8');
+    const allowedSigners = path.join(keyDir, 'allowed_signers');
+    await fs.writeFile(allowedSigners, `signer@example.com ${publicKey}`);
+
+    const tree = repo.head().peelToTree();
+    const oid = repo.commit(tree, 'ssh signed commit', {
+      author: signature,
+      committer: signature,
+      parents: [repo.head().target()!],
+      signer: { format: 'Ssh', key: keyPath },
+    });
+    const verification = repo.verifyCommitSignature(oid, { allowedSigners });
+    expect(verification).toEqual(
+      expect.objectContaining({
+        format: 'Ssh',
+        valid: true,
+        signer: 'signer@example.com',
+        trust: 'fully',
+        fingerprint: expect.stringMatching(/^SHA256:/),
+      })
+    );
+
+    const unsigned = repo.verifyCommitSignature(oid);
+    expect(unsigned?.valid).toBe(true);
+    expect(unsigned?.signer).toBeUndefined();
+    expect(unsigned?.trust).toEqual('undefined');
+  });
+
+  it.skipIf(isTarget('win32'))('reject ssh signature of key not in allowed signers', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const keyDir = await makeTmpDir('ssh-key');
+    const keyPath = path.join(keyDir, 'id_ed25519');
+    const otherKeyPath = path.join(keyDir, 'other_ed25519');
+    execFileSync('ssh-keygen', ['-q', '-t', 'ed25519', '-N', '', '-C', 'signer@example.com', '-f', keyPath]);
+    execFileSync('ssh-keygen', ['-q', '-t', 'ed25519', '-N', '', '-C', 'other@example.com', '-f', otherKeyPath]);
+    const otherPublicKey = await fs.readFile(`${otherKeyPath}.pub`, 'utf8');
+    const allowedSigners = path.join(keyDir, 'allowed_signers');
+    await fs.writeFile(allowedSigners, `other@example.com ${otherPublicKey}`);
+
+    const tree = repo.head().peelToTree();
+    const oid = repo.commit(tree, 'ssh signed commit', {
+      author: signature,
+      committer: signature,
+      parents: [repo.head().target()!],
+      signer: { format: 'Ssh', key: keyPath },
+    });
+    const verification = repo.verifyCommitSignature(oid, { allowedSigners });
+    expect(verification).toEqual(expect.objectContaining({ format: 'Ssh', valid: false, trust: 'undefined' }));
+    expect(verification?.signer).toBeUndefined();
+  });
+
+  it('create commit buffer with custom headers and commit signed', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const tree = repo.head().peelToTree();
+    const parent = repo.head().target()!;
+    const buffer = repo.createCommitBuffer(tree, [parent], signature, signature, 'custom headers', {
+      messageEncoding: 'ISO-8859-1',
+      headers: [
+        { name: 'change-id', value: 'I0123456789abcdef' },
+        { name: 'multi', value: 'line 1\nline 2' },
+      ],
+    });
+    expect(buffer).toContain(`tree ${tree.id()}\nparent ${parent}\n`);
+    expect(buffer).toContain('\nencoding ISO-8859-1\nchange-id I0123456789abcdef\nmulti line 1\n line 2\n\ncustom headers');
+    const fakeSignature = '-----BEGIN PGP SIGNATURE-----\n\niQEcBAABAgAGBQJTest123\n-----END PGP SIGNATURE-----';
+    const oid = repo.commitSigned(buffer, fakeSignature);
+    expect(repo.head().target()).toEqual(parent);
+
+    const commit = repo.getCommit(oid);
+    expect(commit.message()).toEqual('custom headers');
+    expect(commit.headerField('change-id')).toEqual('I0123456789abcdef');
+    expect(commit.headerField('multi')).toEqual('line 1\nline 2');
+    expect(commit.headerField('encoding')).toEqual('ISO-8859-1');
+    expect(commit.headerField('gpgsig')).toEqual(fakeSignature);
+    expect(commit.headerField('not-exists')).toBeNull();
+    expect(commit.rawHeader()).toMatch(/^tree [0-9a-f]{40}\nparent [0-9a-f]{40}\n/);
+    expect(repo.extractSignature(oid)?.signedData).toEqual(buffer);
+  });
+
+  it('reject invalid custom header names', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const tree = repo.head().peelToTree();
+    const parent = repo.head().target()!;
+    for (const name of ['', 'change id', 'change\nid']) {
+      expect(() =>
+        repo.createCommitBuffer(tree, [parent], signature, signature, 'invalid header', {
+          headers: [{ name, value: 'I0123456789abcdef' }],
+        })
+      ).toThrowError(/invalid commit header name/);
+    }
+  });
+
+  it('get parents of commit', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const commit = repo.getCommit('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+    expect(commit.parentCount()).toEqual(1);
+    expect(commit.parentIds()).toEqual(['b33e0101b828225f77eeff4dfa31259dcf379002']);
+    const parents = commit.parents();
+    expect(parents).toHaveLength(1);
+    expect(parents[0]!.id()).toEqual('b33e0101b828225f77eeff4dfa31259dcf379002');
+    const parent = commit.parent(0);
+    expect(parent.message()).toEqual('first\n');
+    expect(parent.parentCount()).toEqual(0);
+    expect(parent.parents()).toEqual([]);
+    expect(() => commit.parent(1)).toThrowError();
+    expect(commit.nthGenAncestor(0).id()).toEqual(commit.id());
+    expect(commit.nthGenAncestor(1).id()).toEqual('b33e0101b828225f77eeff4dfa31259dcf379002');
+    expect(() => commit.nthGenAncestor(2)).toThrowError();
+  });
+
+  it('get raw message, encoding and time offset of commit', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const commit = repo.getCommit('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+    expect(commit.messageRaw()).toEqual('second\n');
+    expect(commit.messageEncoding()).toBeNull();
+    expect(commit.timeOffset()).toEqual(540);
+  });
+
+  it('diff commit to parent', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const commit = repo.getCommit('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+    const diff = commit.diffToParent();
+    const deltas = [...diff.deltas()];
+    expect(deltas).toHaveLength(1);
+    expect(deltas[0]!.status()).toEqual('Added');
+    expect(deltas[0]!.newFile().path()).toEqual('second');
+
+    const rootDiff = commit.parent(0).diffToParent();
+    expect([...rootDiff.deltas()].map(x => x.newFile().path())).toEqual(['first']);
+  });
 });
@@ -1,6 +1,17 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
 import { describe, expect, it } from 'vitest';
-import { openDefaultConfig, openRepository, parseConfigBool, parseConfigI32, parseConfigI64 } from '../index';
+import {
+  openConfig,
+  openConfigStack,
+  openDefaultConfig,
+  openRepository,
+  parseConfigBool,
+  parseConfigI32,
+  parseConfigI64,
+} from '../index';
 import { useFixture } from './fixtures';
+import { makeTmpDir } from './tmp';
 
 describe('config', () => {
   it('open default config', () => {
@@ -74,4 +85,128 @@ describe('config', () => {
     expect(parseConfigI64('1024M')).toBe(1073741824);
     expect(() => parseConfigI64('not_a_i64')).toThrowError(/libgit2 error: failed to parse 'not_a_i64' as an integer;/);
   });
+
+  it('open config stack and write to chosen level', async () => {
+    const dir = await makeTmpDir('config');
+    const globalPath = path.join(dir, 'global');
+    const localPath = path.join(dir, 'local');
+    await fs.writeFile(globalPath, '[user]\n\tname = Global\n\temail = global@example.com\n');
+    await fs.writeFile(localPath, '[core]\n\tbare = false\n');
+    const config = openConfigStack([
+      { path: globalPath, level: 'Global' },
+      { path: localPath, level: 'Local' },
+    ]);
+    expect(config.getString('user.email')).toEqual('global@example.com');
+
+    config.openLevel('Local').setString('user.email', 'local@example.com');
+    expect(config.getString('user.email')).toEqual('local@example.com');
+    expect(config.getEntry('user.email').level).toEqual('Local');
+    expect(config.getEntry('user.name').level).toEqual('Global');
+    expect(openConfig(globalPath).getString('user.email')).toEqual('global@example.com');
+    expect(openConfig(localPath).getString('user.email')).toEqual('local@example.com');
+    expect(() => config.openLevel('System')).toThrowError();
+  });
+
+  it('add file to config', async () => {
+    const dir = await makeTmpDir('config');
+    const appPath = path.join(dir, 'app');
+    await fs.writeFile(appPath, '[app]\n\tkey = value\n');
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const config = repo.config();
+    config.addFile(appPath, 'App');
+    expect(config.getString('app.key')).toEqual('value');
+    expect(() => config.addFile(appPath, 'App')).toThrowError();
+    config.addFile(appPath, 'App', true);
+  });
+
+  it('snapshot config', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const config = repo.config();
+    config.setString('user.name', 'before');
+    const snapshot = config.snapshot();
+    config.setString('user.name', 'after');
+    expect(snapshot.getString('user.name')).toEqual('before');
+    expect(config.getString('user.name')).toEqual('after');
+  });
+
+  it('get color, path and duration values', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const config = repo.config().openLevel('Local');
+    config.setString('color.diff.new', 'bold green #000000 nobold');
+    config.setString('color.diff.old', 'not-a-color');
+    config.setString('gc.pruneExpire', '2.weeks.ago');
+    config.setString('gc.worktreePruneExpire', 'never');
+    config.setString('core.hooksPath', '~/hooks');
+    expect(config.getColor('color.diff.new')).toEqual({
+      foreground: 'green',
+      background: '#000000',
+      attributes: ['bold', 'nobold'],
+    });
+    expect(() => config.getColor('color.diff.old')).toThrowError(/failed to parse 'not-a-color' as a color/);
+    expect(config.findColor('color.diff.old')).toBeNull();
+    expect(config.getDuration('gc.pruneExpire')).toEqual(14 * 24 * 60 * 60);
+    expect(config.getDuration('gc.worktreePruneExpire')).toBeNull();
+    expect(config.findPath('core.hooksPath')).not.toContain('~');
+    expect(config.findPath('core.notExists')).toBeNull();
+  });
+
+  it('get typed values of multivar', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const config = repo.config().openLevel('Local');
+    config.setMultivar('test.value', '^$', '1k');
+    config.setMultivar('test.value', '^$', 'yes');
+    expect(config.multivarValues('test.value')).toEqual(['1k', 'yes']);
+    expect(config.multivarValues('test.value', '^1', 'Int')).toEqual([1024]);
+    expect(config.multivarValues('test.value', '^y', 'Bool')).toEqual([true]);
+    expect(() => config.multivarValues('test.value', null, 'Int')).toThrowError();
+  });
+
+  it('iterate config entries with glob', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const config = repo.config().openLevel('Local');
+    config.setString('remote.origin.url', 'https://github.com/toss/es-git');
+    config.setString('remote.upstream.url', 'git@github.com:toss/es-git.git');
+    config.setString('remote.upstream.pushurl', 'git@github.com:toss/es-git-push.git');
+    config.setString('branch.main.merge', 'refs/heads/main');
+    const urls = [...config.entries('remote\\..*\\.url')].map(x => x.value);
+    expect(urls).toEqual(['https://github.com/toss/es-git', 'git@github.com:toss/es-git.git']);
+    const merges = [...config.entries('branch\\..*\\.merge')].map(x => `${x.name}=${x.value}`);
+    expect(merges).toEqual(['branch.main.merge=refs/heads/main']);
+  });
+
+  it('evaluate includeIf conditions', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const dir = await makeTmpDir('config');
+    const includePath = path.join(dir, 'include');
+    await fs.writeFile(includePath, '[user]\n\temail = included@example.com\n');
+    const head = repo.findReference('HEAD')!.symbolicTarget()!.replace('refs/heads/', '');
+    const local = repo.config().openLevel('Local');
+    local.setString(`includeIf.onbranch:${head}.path`, includePath);
+    local.setString('remote.origin.url', 'https://github.com/toss/es-git');
+
+    const entry = repo.config().getEntry('user.email');
+    expect(entry.value).toEqual('included@example.com');
+    expect(entry.level).toEqual('Local');
+    expect(entry.includeDepth).toEqual(1);
+
+    expect(repo.matchesIncludeIf(`onbranch:${head}`)).toBe(true);
+    expect(repo.matchesIncludeIf('onbranch:not-exists')).toBe(false);
+    expect(repo.matchesIncludeIf(`gitdir:${p}/`)).toBe(true);
+    expect(repo.matchesIncludeIf(`gitdir/i:${p.toUpperCase()}/`)).toBe(true);
+    expect(repo.matchesIncludeIf('gitdir:/not/exists/')).toBe(false);
+    expect(repo.matchesIncludeIf('hasconfig:remote.*.url:https://github.com/toss/**')).toBe(true);
+    expect(repo.matchesIncludeIf('hasconfig:remote.*.url:git@gitlab.com:*/**')).toBe(false);
+    expect(repo.matchesIncludeIf('hasconfig:remote.*.url:https://github.com/**/es-git')).toBe(true);
+    // `*` and a `**` which is not a whole path component don't match `/`.
+    expect(repo.matchesIncludeIf('hasconfig:remote.*.url:https://github.com/toss*')).toBe(false);
+    expect(repo.matchesIncludeIf('hasconfig:remote.*.url:https://github.com/t**')).toBe(false);
+    expect(repo.matchesIncludeIf(`hasconfig:remote.*.url:${'*a'.repeat(30)}b`)).toBe(false);
+    expect(repo.matchesIncludeIf('unknown:value')).toBe(false);
+  });
 });
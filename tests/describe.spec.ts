@@ -1,3 +1,5 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
 import { describe, expect, it } from 'vitest';
 import { openRepository } from '../index';
 import { useFixture } from './fixtures';
@@ -17,4 +19,70 @@ describe('describe', () => {
     const describe = obj.describe({ describeAll: true });
     expect(describe.format()).toEqual('heads/main');
   });
+  it('get structured describe details', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    repo.createLightweightTag('v0.1', repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002').asObject());
+    expect(repo.describe({ describeTags: true }).details()).toEqual(
+      expect.objectContaining({ tag: 'v0.1', distance: 1, abbreviatedOid: 'a01e988', dirty: false })
+    );
+
+    await fs.writeFile(path.join(p, 'first'), 'modified');
+    const details = repo.describe({ describeTags: true }).details({ abbreviatedSize: 10 });
+    expect(details).toEqual(
+      expect.objectContaining({ tag: 'v0.1', distance: 1, abbreviatedOid: 'a01e9888e4', dirty: true })
+    );
+  });
+
+  it('get describe details of commit id fallback', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const details = repo.describe({ showCommitOidAsFallback: true }).details();
+    expect(details.tag).toBeFalsy();
+    expect(details.distance).toBeFalsy();
+    expect(details.abbreviatedOid).toEqual('a01e988');
+  });
+
+  it('get describe details of tags which look like the long format', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const parent = repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002').asObject();
+    repo.createLightweightTag('build-7-gb33e010', parent);
+    expect(repo.describe({ describeTags: true }).details()).toEqual(
+      expect.objectContaining({ tag: 'build-7-gb33e010', distance: 1, abbreviatedOid: 'a01e988', dirty: false })
+    );
+    expect(parent.describe({ describeTags: true }).details()).toEqual(
+      expect.objectContaining({ tag: 'build-7-gb33e010', distance: 0, abbreviatedOid: 'b33e010', dirty: false })
+    );
+  });
+
+  it('describe with contains', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    repo.createTag('v1.0', repo.getCommit('a01e9888e46729ef4aa68953ba19b02a7a64eb82').asObject(), 'v1.0', {
+      tagger: { name: 'Seokju Na', email: 'seokju.me@toss.im' },
+    });
+    const obj = repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002').asObject();
+    const describe = obj.describe({ contains: true });
+    expect(describe.format()).toEqual('v1.0~1');
+    expect(describe.details()).toEqual(
+      expect.objectContaining({ tag: 'v1.0', distance: 1, abbreviatedOid: 'b33e010', dirty: false })
+    );
+    expect(repo.describe({ contains: true }).format()).toEqual('v1.0^0');
+    expect(() => obj.describe({ contains: true, pattern: 'v2.*' })).toThrowError(/cannot describe/);
+  });
+
+  it('name commits relative to references', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const parent = 'b33e0101b828225f77eeff4dfa31259dcf379002';
+    expect(repo.nameRev(parent)).toEqual('main~1');
+    expect(repo.nameRev(parent, { tagsOnly: true })).toBeNull();
+
+    repo.createLightweightTag('v1.0', repo.getCommit('a01e9888e46729ef4aa68953ba19b02a7a64eb82').asObject());
+    expect(repo.nameRev(parent)).toEqual('tags/v1.0~1');
+    expect(repo.nameRev('a01e9888e46729ef4aa68953ba19b02a7a64eb82', { tagsOnly: true })).toEqual('tags/v1.0');
+    expect(repo.nameRev(parent, { refs: ['main'] })).toEqual('main~1');
+    expect(repo.nameRev(parent, { refs: ['v2.*'] })).toBeNull();
+  });
 });
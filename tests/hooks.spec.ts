@@ -0,0 +1,199 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { describe, expect, it } from 'vitest';
+import { initRepository, openRepository } from '../index';
+import { isTarget } from './env';
+import { useFixture } from './fixtures';
+import { makeTmpDir } from './tmp';
+
+const signature = { name: 'Seokju Na', email: 'seokju.me@toss.im' };
+
+async function writeHook(dir: string, name: string, script: string) {
+  await fs.mkdir(dir, { recursive: true });
+  await fs.writeFile(path.join(dir, name), `#!/bin/sh\n${script}\n`, { mode: 0o755 });
+}
+
+describe.skipIf(isTarget('win32'))('hooks', () => {
+  it('run hook with arguments and stdin', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    expect(repo.runHook('pre-push')).toBeNull();
+
+    await writeHook(path.join(p, '.git', 'hooks'), 'pre-push', 'echo "$1 $2"; cat; echo error >&2; exit 3');
+    const result = repo.runHook('pre-push', ['origin', 'url'], { stdin: 'input\n' });
+    expect(result).toEqual({
+      name: 'pre-push',
+      path: expect.stringContaining(path.join('.git', 'hooks', 'pre-push')),
+      exitCode: 3,
+      stdout: 'origin url\ninput\n',
+      stderr: 'error\n',
+    });
+  });
+
+  it('resolve hooks from core.hooksPath', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    repo.config().setString('core.hooksPath', 'custom-hooks');
+    await writeHook(path.join(p, 'custom-hooks'), 'custom', 'echo "$FOO" && pwd');
+    const result = repo.runHook('custom', [], { env: { FOO: 'bar' } });
+    expect(result?.exitCode).toBe(0);
+    expect(result?.stdout).toEqual(`bar\n${await fs.realpath(p)}\n`);
+  });
+
+  it('commit runs hooks which may edit the message', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const log = path.join(p, '.git', 'hooks.log');
+    const hooks = path.join(p, '.git', 'hooks');
+    await writeHook(hooks, 'pre-commit', `echo pre-commit >> ${log}`);
+    await writeHook(hooks, 'prepare-commit-msg', `echo "prepare-commit-msg $2" >> ${log}`);
+    await writeHook(hooks, 'commit-msg', `echo commit-msg >> ${log}; echo "Signed-off-by: Seokju Na" >> "$1"`);
+    await writeHook(hooks, 'post-commit', `echo post-commit >> ${log}`);
+    const oid = repo.commit(repo.head().peelToTree(), 'test commit\n', {
+      updateRef: 'HEAD',
+      author: signature,
+      committer: signature,
+      parents: [repo.head().target()!],
+      runHooks: true,
+    });
+    expect(repo.getCommit(oid).message()).toEqual('test commit\nSigned-off-by: Seokju Na\n');
+    expect(await fs.readFile(log, 'utf8')).toEqual(
+      'pre-commit\nprepare-commit-msg message\ncommit-msg\npost-commit\n'
+    );
+  });
+
+  it('commit includes index changes of pre-commit hook', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const gitDir = path.join(p, '.git');
+    const index = repo.index();
+    await fs.copyFile(path.join(gitDir, 'index'), path.join(gitDir, 'index.orig'));
+    await fs.writeFile(path.join(p, 'first'), 'fixed\n');
+    index.addPath('first');
+    index.write();
+    const fixedId = index.getByPath('first')!.id;
+    await fs.copyFile(path.join(gitDir, 'index'), path.join(gitDir, 'index.fixed'));
+    await fs.copyFile(path.join(gitDir, 'index.orig'), path.join(gitDir, 'index'));
+    index.read(true);
+
+    await writeHook(path.join(gitDir, 'hooks'), 'pre-commit', 'cp "$GIT_DIR/index.fixed" "$GIT_INDEX_FILE"');
+    const oid = repo.commit(repo.getTree(index.writeTree()), 'test commit\n', {
+      updateRef: 'HEAD',
+      author: signature,
+      committer: signature,
+      parents: [repo.head().target()!],
+      runHooks: true,
+    });
+    const commit = repo.getCommit(oid);
+    expect(commit.message()).toEqual('test commit\n');
+    expect(commit.tree().getPath('first')?.id()).toEqual(fixedId);
+  });
+
+  it('commit cleans up the message edited by hooks', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const hooks = path.join(p, '.git', 'hooks');
+    const commit = (message: string) => {
+      const oid = repo.commit(repo.head().peelToTree(), message, {
+        updateRef: 'HEAD',
+        author: signature,
+        committer: signature,
+        parents: [repo.head().target()!],
+        runHooks: true,
+      });
+      return repo.getCommit(oid).message();
+    };
+    expect(commit('#123 fix crash  \n\n')).toEqual('#123 fix crash  \n\n');
+
+    await writeHook(hooks, 'commit-msg', 'printf "body  \\n\\n" >> "$1"');
+    expect(commit('#123 fix crash\n')).toEqual('#123 fix crash\nbody\n');
+
+    repo.config().setString('commit.cleanup', 'strip');
+    expect(commit('#123 fix crash\n')).toEqual('body\n');
+
+    repo.config().setString('commit.cleanup', 'verbatim');
+    expect(commit('#123 fix crash\n')).toEqual('#123 fix crash\nbody  \n\n');
+  });
+
+  it('commit is aborted if pre-commit hook fails', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const head = repo.head().target();
+    await writeHook(path.join(p, '.git', 'hooks'), 'pre-commit', 'echo "lint failed"; exit 1');
+    expect(() =>
+      repo.commit(repo.head().peelToTree(), 'test commit', {
+        updateRef: 'HEAD',
+        author: signature,
+        committer: signature,
+        parents: [head!],
+        runHooks: true,
+      })
+    ).toThrowError(/pre-commit hook declined with exit code 1: lint failed/);
+    expect(repo.head().target()).toEqual(head);
+  });
+
+  it('amend includes index changes of pre-commit hook', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const gitDir = path.join(p, '.git');
+    const index = repo.index();
+    await fs.copyFile(path.join(gitDir, 'index'), path.join(gitDir, 'index.orig'));
+    await fs.writeFile(path.join(p, 'first'), 'fixed\n');
+    index.addPath('first');
+    index.write();
+    const fixedId = index.getByPath('first')!.id;
+    await fs.copyFile(path.join(gitDir, 'index'), path.join(gitDir, 'index.fixed'));
+    await fs.copyFile(path.join(gitDir, 'index.orig'), path.join(gitDir, 'index'));
+    index.read(true);
+
+    await writeHook(path.join(gitDir, 'hooks'), 'pre-commit', 'cp "$GIT_DIR/index.fixed" "$GIT_INDEX_FILE"');
+    const commit = repo.getCommit(repo.head().target()!);
+    const oid = commit.amend({ updateRef: 'HEAD', message: 'amended\n', runHooks: true });
+    const amended = repo.getCommit(oid);
+    expect(amended.message()).toEqual('amended\n');
+    expect(amended.tree().getPath('first')?.id()).toEqual(fixedId);
+  });
+
+  it('amend runs post-rewrite hook', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const log = path.join(p, '.git', 'hooks.log');
+    await writeHook(path.join(p, '.git', 'hooks'), 'post-rewrite', `echo "$1" >> ${log}; cat >> ${log}`);
+    const commit = repo.getCommit(repo.head().target()!);
+    const oid = commit.amend({ updateRef: 'HEAD', message: 'amended', runHooks: true });
+    expect(await fs.readFile(log, 'utf8')).toEqual(`amend\n${commit.id()} ${oid}\n`);
+  });
+
+  it('checkout runs post-checkout hook', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const log = path.join(p, '.git', 'hooks.log');
+    await writeHook(path.join(p, '.git', 'hooks'), 'post-checkout', `echo "$1 $2 $3" >> ${log}`);
+    const parent = repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002');
+    repo.checkoutTree(parent.asObject(), { force: true, runHooks: true });
+    repo.checkoutIndex(undefined, { force: true, runHooks: true });
+    repo.checkoutHead({ force: true });
+    const head = 'a01e9888e46729ef4aa68953ba19b02a7a64eb82';
+    expect(await fs.readFile(log, 'utf8')).toEqual(
+`${head} ${head} 0\n${head} ${head} 0\n`
+    );
+  });
+
+  it('push is aborted if pre-push hook fails', async () => {
+    const remotePath = await makeTmpDir('push');
+    await initRepository(remotePath, { bare: true });
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const log = path.join(p, '.git', 'hooks.log');
+    await writeHook(path.join(p, '.git', 'hooks'), 'pre-push', `echo "$1 $2" >> ${log}; cat >> ${log}; exit 1`);
+    const remote = repo.createRemote('target', remotePath);
+    await expect(remote.push(['refs/heads/main:refs/heads/main'], { runHooks: true })).rejects.toThrowError(
+      /pre-push hook declined/
+    );
+    expect(await fs.readFile(log, 'utf8')).toEqual(
+      `target ${remotePath}\nrefs/heads/main a01e9888e46729ef4aa68953ba19b02a7a64eb82 refs/heads/main 0000000000000000000000000000000000000000\n`
+    );
+    const pushed = await openRepository(remotePath);
+    expect(pushed.findReference('refs/heads/main')).toBeNull();
+  });
+});
@@ -2,6 +2,7 @@ import fs from 'node:fs/promises';
 import path from 'node:path';
 import { describe, expect, it, vi } from 'vitest';
 import { type IndexEntry, openRepository } from '../index';
+import { isTarget } from './env';
 import { useFixture } from './fixtures';
 
 function entryPath(entry: IndexEntry): string {
@@ -75,4 +76,121 @@ describe('index', () => {
     index.updateAll(['*']);
     expect(index.getByPath('A')?.fileSize).toBe(2);
   });
+
+  it('stage selected hunks and lines', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const index = repo.index();
+    const lines = Array.from({ length: 20 }, (_, i) => `line${i + 1}`);
+    await fs.writeFile(path.join(p, 'A'), `${lines.join('\n')}\n`);
+    index.addPath('A');
+    const modified = [...lines];
+    modified[1] = 'line2 modified';
+    modified[17] = 'line18 modified';
+    await fs.writeFile(path.join(p, 'A'), `${modified.join('\n')}\n`);
+
+    index.stageHunks(repo, 'A', [{ hunk: 1 }]);
+    const staged = repo.getObject(index.getByPath('A')!.id).peelToBlob();
+    const expected = [...lines];
+    expected[17] = 'line18 modified';
+    expect(Buffer.from(staged.content()).toString('utf8')).toEqual(`${expected.join('\n')}\n`);
+    expect(await fs.readFile(path.join(p, 'A'), 'utf8')).toEqual(`${modified.join('\n')}\n`);
+  });
+
+  it('stage only selected lines in hunk', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const index = repo.index();
+    await fs.writeFile(path.join(p, 'A'), 'a\nb\n');
+    index.addPath('A');
+    await fs.writeFile(path.join(p, 'A'), 'a\nb\nc\nd\n');
+
+    // Lines of hunk: " a", " b", "+c", "+d"
+    index.stageHunks(repo, 'A', [{ hunk: 0, lines: [3] }]);
+    const staged = repo.getObject(index.getByPath('A')!.id).peelToBlob();
+    expect(Buffer.from(staged.content()).toString('utf8')).toEqual('a\nb\nd\n');
+  });
+
+  it('stage selected lines after a line without newline at end of file', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const index = repo.index();
+    await fs.writeFile(path.join(p, 'A'), 'a');
+    index.addPath('A');
+    await fs.writeFile(path.join(p, 'A'), 'a\nb\n');
+
+    // Lines of hunk: "-a", "\ No newline at end of file", "+a", "+b"
+    index.stageHunks(repo, 'A', [{ hunk: 0, lines: [3] }]);
+    const staged = repo.getObject(index.getByPath('A')!.id).peelToBlob();
+    expect(Buffer.from(staged.content()).toString('utf8')).toEqual('a\nb\n');
+  });
+
+  it('unstage selected lines', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const index = repo.index();
+    const original = await fs.readFile(path.join(p, 'first'), 'utf8');
+    await fs.writeFile(path.join(p, 'first'), `${original}added1\nadded2\n`);
+    index.addPath('first');
+
+    const headEntry = repo.head().peelToTree().getPath('first')!;
+    expect(index.getByPath('first')?.id).not.toEqual(headEntry.id());
+    index.unstageHunks(repo, 'first', [{ hunk: 0 }]);
+    expect(index.getByPath('first')?.id).toEqual(headEntry.id());
+    expect(await fs.readFile(path.join(p, 'first'), 'utf8')).toEqual(`${original}added1\nadded2\n`);
+  });
+
+  it('unstage every line of a new file', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const index = repo.index();
+    await fs.writeFile(path.join(p, 'third'), 'third\n');
+    index.addPath('third');
+
+    index.unstageHunks(repo, 'third', [{ hunk: 0 }]);
+    expect(index.getByPath('third')).toBeNull();
+  });
+
+  it('discard selected lines in workdir', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const index = repo.index();
+    await fs.writeFile(path.join(p, 'A'), 'a\nb\n');
+    index.addPath('A');
+    index.write();
+    await fs.writeFile(path.join(p, 'A'), 'a\nb\nc\nd\n');
+
+    repo.discardHunks('A', [{ hunk: 0, lines: [2] }]);
+    expect(await fs.readFile(path.join(p, 'A'), 'utf8')).toEqual('a\nb\nd\n');
+    expect(index.getByPath('A')?.fileSize).toBe(4);
+  });
+
+  it('stage and discard selected lines with autocrlf', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    repo.config().setBool('core.autocrlf', true);
+    const index = repo.index();
+    await fs.writeFile(path.join(p, 'A'), 'a\r\nb\r\n');
+    index.addPath('A');
+    index.write();
+    await fs.writeFile(path.join(p, 'A'), 'a\r\nb\r\nc\r\nd\r\n');
+
+    // Lines of hunk: " a", " b", "+c", "+d"
+    index.stageHunks(repo, 'A', [{ hunk: 0, lines: [2] }]);
+    const staged = repo.getObject(index.getByPath('A')!.id).peelToBlob();
+    expect(Buffer.from(staged.content()).toString('utf8')).toEqual('a\nb\nc\n');
+
+    repo.discardHunks('A', [{ hunk: 0, lines: [3] }]);
+    expect(await fs.readFile(path.join(p, 'A'), 'utf8')).toEqual('a\r\nb\r\nc\r\n');
+  });
+
+  it.skipIf(isTarget('win32'))('stage lines of a new executable file', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const index = repo.index();
+    await fs.writeFile(path.join(p, 'run.sh'), '#!/bin/sh\necho run\n', { mode: 0o755 });
+
+    index.stageHunks(repo, 'run.sh', [{ hunk: 0, lines: [0] }]);
+    expect(index.getByPath('run.sh')?.mode).toBe(0o100755);
+  });
 });
@@ -316,4 +316,58 @@ describe('rebase', () => {
 
     expect(repo.state()).toBe('Clean');
   });
+
+  it('sign rebased commits', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const sig = { name: 'Test User', email: 'test@example.com' };
+
+    let index = repo.index();
+    const baseOid = repo.commit(repo.getTree(index.writeTree()), 'init', {
+      updateRef: 'HEAD',
+      author: sig,
+      committer: sig,
+      parents: [repo.head().target()!],
+    });
+    const baseCommit = repo.getCommit(baseOid);
+
+    repo.setHead('refs/heads/main');
+    repo.checkoutHead();
+    await fs.writeFile(path.join(p, 'A'), 'A');
+    index = repo.index();
+    index.addPath('A');
+    repo.commit(repo.getTree(index.writeTree()), 'A', {
+      updateRef: 'refs/heads/main',
+      author: sig,
+      committer: sig,
+      parents: [baseOid],
+    });
+
+    repo.createBranch('feature', baseCommit);
+    repo.setHead('refs/heads/feature');
+    repo.checkoutHead({ force: true });
+    await fs.writeFile(path.join(p, 'B'), 'B');
+    index = repo.index();
+    index.addPath('B');
+    repo.commit(repo.getTree(index.writeTree()), 'B', {
+      updateRef: 'refs/heads/feature',
+      author: sig,
+      committer: sig,
+      parents: [baseOid],
+    });
+
+    const rebase = repo.rebase(
+      repo.getAnnotatedCommitFromReference(repo.getReference('refs/heads/feature')),
+      repo.getAnnotatedCommitFromReference(repo.getReference('refs/heads/main'))
+    );
+    const fakeSignature = '-----BEGIN PGP SIGNATURE-----\n\niQEcBAABAgAGBQJTest123\n-----END PGP SIGNATURE-----';
+    expect(rebase.next()).not.toBeNull();
+    const oid = rebase.commit({ committer: sig, signer: { callback: () => fakeSignature } });
+    expect(repo.head().target()).toEqual(oid);
+    expect(repo.extractSignature(oid)?.signature).toEqual(fakeSignature);
+    expect(repo.reflog('HEAD').get(0)?.idNew()).toEqual(oid);
+    expect(rebase.next()).toBeNull();
+    rebase.finish(sig);
+    expect(repo.getReference('refs/heads/feature').target()).toEqual(oid);
+  });
 });
@@ -1,5 +1,5 @@
-import { describe, expect, it } from 'vitest';
-import { cloneRepository, openRepository } from '../index';
+import { describe, expect, it, vi } from 'vitest';
+import { cloneRepository, initRepository, openRepository } from '../index';
 import { isTarget } from './env';
 import { useFixture } from './fixtures';
 import { makeTmpDir } from './tmp';
@@ -36,6 +36,175 @@ describe('remote', () => {
     expect(remote.name()).toEqual('origin');
   });
 
+  it('rename and delete remote', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    repo.createRemote('origin', 'git@github.com:toss/empty.git');
+    expect(repo.renameRemote('origin', 'upstream')).toEqual([]);
+    expect(repo.remoteNames()).toEqual(['upstream']);
+    expect(repo.getRemote('upstream').url()).toEqual('git@github.com:toss/empty.git');
+    repo.deleteRemote('upstream');
+    expect(repo.remoteNames()).toEqual([]);
+    expect(() => repo.deleteRemote('upstream')).toThrowError(/remote 'upstream' does not exist/);
+  });
+
+  it('set remote urls and add refspecs', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    repo.createRemote('origin', 'git@github.com:toss/empty.git');
+    repo.remoteSetUrl('origin', 'https://github.com/toss/empty');
+    repo.remoteSetPushurl('origin', 'git@github.com:toss/empty-push.git');
+    repo.remoteAddFetch('origin', '+refs/tags/*:refs/tags/*');
+    repo.remoteAddPush('origin', 'refs/heads/main:refs/heads/main');
+    const remote = repo.getRemote('origin');
+    expect(remote.url()).toEqual('https://github.com/toss/empty');
+    expect(remote.pushurl()).toEqual('git@github.com:toss/empty-push.git');
+    expect(remote.refspecs()).toEqual([
+      { direction: 'Fetch', src: 'refs/heads/*', dst: 'refs/remotes/origin/*', force: true },
+      { direction: 'Fetch', src: 'refs/tags/*', dst: 'refs/tags/*', force: true },
+      { direction: 'Push', src: 'refs/heads/main', dst: 'refs/heads/main', force: false },
+    ]);
+    repo.remoteSetPushurl('origin');
+    expect(repo.getRemote('origin').pushurl()).toBeNull();
+  });
+
+  it('create anonymous and detached remotes', async () => {
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const anonymous = repo.createAnonymousRemote('https://github.com/toss/empty');
+    expect(anonymous.name()).toBeNull();
+    expect(anonymous.url()).toEqual('https://github.com/toss/empty');
+    const detached = repo.createDetachedRemote('https://github.com/toss/empty');
+    expect(detached.name()).toBeNull();
+    expect(detached.url()).toEqual('https://github.com/toss/empty');
+    expect(repo.remoteNames()).toEqual([]);
+  });
+
+  it('connect to remote and list advertised references', async () => {
+    const remotePath = await useFixture('tag');
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const remote = repo.createAnonymousRemote(remotePath);
+    expect(() => remote.list()).toThrowError(/never connected/);
+    await remote.connect('Fetch');
+    const heads = remote.list();
+    expect(heads.find(x => x.name === 'HEAD')).toEqual({
+      name: 'HEAD',
+      oid: '26cf7ebc61494e892188a3ceab38702b2ecdc353',
+      symrefTarget: 'refs/heads/main',
+    });
+    expect(heads.find(x => x.name === 'refs/tags/v0')).toEqual({
+      name: 'refs/tags/v0',
+      oid: 'aa0040546ed22b8bb33f3bd621e8d10ed849b02c',
+      peeled: '828954df9f08dc8e172447cdacf0ddea1adf9e63',
+    });
+    expect(heads.map(x => x.name)).not.toContain('refs/tags/v0^{}');
+  });
+
+  it('push to remote and get results of references', async () => {
+    const remotePath = await makeTmpDir('push');
+    await initRepository(remotePath, { bare: true });
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const remote = repo.createRemote('target', remotePath);
+    await expect(remote.push(['refs/heads/main:refs/heads/main'])).resolves.toEqual([
+      { refname: 'refs/heads/main', status: 'Ok' },
+    ]);
+    await expect(remote.push(['refs/heads/main:refs/heads/main'])).resolves.toEqual([
+      { refname: 'refs/heads/main', status: 'UpToDate' },
+    ]);
+  });
+
+  it('reject atomic push', async () => {
+    const remotePath = await makeTmpDir('push');
+    await initRepository(remotePath, { bare: true });
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const remote = repo.createRemote('target', remotePath);
+    expect(() => remote.push(['refs/heads/main:refs/heads/main'], { atomic: true })).toThrowError(
+      /atomic push is not supported/
+    );
+    const pushed = await openRepository(remotePath);
+    expect(pushed.findReference('refs/heads/main')).toBeNull();
+  });
+
+  it('push with lease', async () => {
+    const remotePath = await makeTmpDir('push');
+    await initRepository(remotePath, { bare: true });
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    const remote = repo.createRemote('target', remotePath);
+    await remote.push(['refs/heads/main:refs/heads/main']);
+    repo.createBranch('first', repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002'));
+    await expect(
+      remote.push(['+refs/heads/first:refs/heads/main'], {
+        leases: [{ refname: 'refs/heads/main', expected: 'b33e0101b828225f77eeff4dfa31259dcf379002' }],
+      })
+    ).rejects.toThrowError(/stale info: refs\/heads\/main/);
+    await expect(
+      remote.push(['refs/heads/main:refs/heads/other'], {
+        leases: [{ refname: 'refs/heads/other', expected: '0000000000000000000000000000000000000000' }],
+      })
+    ).resolves.toEqual([{ refname: 'refs/heads/other', status: 'Ok' }]);
+    await expect(
+      remote.push(['refs/heads/main:refs/heads/third'], {
+        leases: [{ refname: 'refs/heads/fourth', expected: '0000000000000000000000000000000000000000' }],
+      })
+    ).rejects.toThrowError(/lease for 'refs\/heads\/fourth' does not match any pushed reference/);
+    const pushed = await openRepository(remotePath);
+    expect(pushed.findReference('refs/heads/main')?.target()).toEqual('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+    expect(pushed.findReference('refs/heads/third')).toBeNull();
+  });
+
+  it('fetch from remote and get updated tips', async () => {
+    const remotePath = await useFixture('commits');
+    const source = await openRepository(remotePath);
+    source.createBranch('feature', source.getCommit('a01e9888e46729ef4aa68953ba19b02a7a64eb82'));
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const remote = repo.createRemote('origin', remotePath);
+    const tips = await remote.fetch([]);
+    expect(tips).toHaveLength(2);
+    expect(tips).toContainEqual({
+      refname: 'refs/remotes/origin/main',
+      newOid: 'a01e9888e46729ef4aa68953ba19b02a7a64eb82',
+      status: 'New',
+    });
+    expect(remote.stats().receivedObjects).toBeGreaterThan(0);
+
+    source.createBranch('feature', source.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002'), { force: true });
+    await expect(remote.fetch([])).resolves.toEqual([
+      {
+        refname: 'refs/remotes/origin/feature',
+        oldOid: 'a01e9888e46729ef4aa68953ba19b02a7a64eb82',
+        newOid: 'b33e0101b828225f77eeff4dfa31259dcf379002',
+        status: 'Forced',
+      },
+    ]);
+  });
+
+  it('foreach FETCH_HEAD entries', async () => {
+    const remotePath = await useFixture('commits');
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    expect(() => repo.fetchheadForeach(() => true)).toThrowError();
+    await repo.createRemote('origin', remotePath).fetch([]);
+    const foreach = vi.fn().mockReturnValue(true);
+    repo.fetchheadForeach(foreach);
+    expect(foreach).toHaveBeenCalledTimes(1);
+    expect(foreach.mock.calls[0]).toEqual([
+      'refs/heads/main',
+      remotePath,
+      'a01e9888e46729ef4aa68953ba19b02a7a64eb82',
+      expect.any(Boolean),
+    ]);
+    expect(() =>
+      repo.fetchheadForeach(() => {
+        throw new Error('callback error');
+      })
+    ).toThrowError(/callback error/);
+  });
+
   it('fetch remote', { skip: isTarget('linux', undefined, 'gnu') }, async () => {
     const p = await makeTmpDir('clone');
     const repo = await cloneRepository('https://github.com/seokju-na/dummy-repo', p);
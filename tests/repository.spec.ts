@@ -1,11 +1,50 @@
+import { execFileSync } from 'node:child_process';
 import fs from 'node:fs/promises';
 import path from 'node:path';
-import { describe, expect, it } from 'vitest';
-import { cloneRepository, initRepository, openRepository } from '../index';
+import { describe, expect, it, vi } from 'vitest';
+import {
+  cloneRepository,
+  initRepository,
+  openRepository,
+  openRepositoryFromWorktree,
+  registerTransport,
+} from '../index';
 import { isTarget } from './env';
 import { useFixture } from './fixtures';
 import { makeTmpDir } from './tmp';
 
+/**
+ * Serve a repository with six commits on `main` and a `base` branch at the
+ * third one with `git upload-pack`, which supports shallow fetches unlike the
+ * local transport of libgit2.
+ */
+async function serveHistory() {
+  const p = await makeTmpDir('history');
+  const repo = await initRepository(p, { initialHead: 'main' });
+  const tree = repo.getTree(repo.index().writeTree());
+  const commits: string[] = [];
+  for (let i = 0; i < 6; i += 1) {
+    const signature = { name: 'Seokju Na', email: 'seokju.me@toss.im', timestamp: 1700000000 + i * 86400 };
+    commits.push(
+      repo.commit(tree, `commit ${i}`, {
+        updateRef: 'HEAD',
+        author: signature,
+        committer: signature,
+        parents: commits.slice(-1),
+      })
+    );
+  }
+  repo.createBranch('base', repo.getCommit(commits[2]));
+  registerTransport('upload-pack', (_url, service, body) => {
+    if (service === 'UploadPackLs') {
+      const refs = execFileSync('git', ['upload-pack', '--stateless-rpc', '--advertise-refs', p]);
+      return Buffer.concat([Buffer.from('001e# service=git-upload-pack\n0000'), refs]);
+    }
+    return execFileSync('git', ['upload-pack', '--stateless-rpc', p], { input: body });
+  });
+  return { url: 'upload-pack://history', commits };
+}
+
 describe('Repository', () => {
   it('init git repository', async () => {
     const p = await useFixture('notgit');
@@ -51,12 +90,122 @@ describe('Repository', () => {
     await expect(fs.readFile(path.join(p, 'first'), 'utf8')).resolves.toEqual(expect.stringContaining('first'));
   });
 
+  it('reject partial clone filters', async () => {
+    const localPath = await useFixture('commits');
+    const p = await makeTmpDir('clone');
+    await expect(cloneRepository(localPath, p, { fetch: { filter: 'blob:none' } })).rejects.toThrowError(
+      /partial clone filter 'blob:none' is not supported/
+    );
+  });
+
+  it('clone as mirror', async () => {
+    const localPath = await useFixture('tag');
+    const p = await makeTmpDir('clone');
+    const repo = await cloneRepository(localPath, p, { mirror: true });
+    expect(repo.isBare()).toBe(true);
+    expect(repo.config().getBool('remote.origin.mirror')).toBe(true);
+    expect(repo.getRemote('origin').refspecs()).toEqual([
+      { direction: 'Fetch', src: 'refs/*', dst: 'refs/*', force: true },
+    ]);
+    expect(repo.findReference('refs/tags/v1')?.target()).toEqual('674e3327707fcf32a348ecfc0cb6b93e57398b8c');
+    expect(repo.findReference('refs/remotes/origin/main')).toBeNull();
+  });
+
+  it('clone from local with hardlinks', { skip: isTarget('win32') }, async () => {
+    const localPath = await useFixture('commits');
+    const p = await makeTmpDir('clone');
+    await cloneRepository(localPath, p, { local: 'Local' });
+    const object = path.join('objects', 'a0', '1e9888e46729ef4aa68953ba19b02a7a64eb82');
+    const [source, cloned] = await Promise.all([
+      fs.stat(path.join(localPath, '.git', object)),
+      fs.stat(path.join(p, '.git', object)),
+    ]);
+    expect(cloned.ino).toEqual(source.ino);
+  });
+
+  it('clone without checkout', async () => {
+    const localPath = await useFixture('commits');
+    const p = await makeTmpDir('clone');
+    const repo = await cloneRepository(localPath, p, { noCheckout: true });
+    expect(repo.head().target()).toEqual('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+    await expect(fs.access(path.join(p, 'first'))).rejects.toThrow();
+  });
+
+  it('clone with custom remote', async () => {
+    const localPath = await useFixture('commits');
+    const p = await makeTmpDir('clone');
+    const remoteCreate = vi.fn(() => ({
+      fetchRefspecs: ['+refs/heads/main:refs/remotes/upstream/main'],
+      pushurl: 'git@github.com:toss/empty.git',
+    }));
+    const repo = await cloneRepository(localPath, p, { remoteName: 'upstream', remoteCreate });
+    expect(remoteCreate).toHaveBeenCalledWith('upstream', localPath);
+    expect(repo.remoteNames()).toEqual(['upstream']);
+    const remote = repo.getRemote('upstream');
+    expect(remote.pushurl()).toEqual('git@github.com:toss/empty.git');
+    expect(remote.refspecs()).toEqual([
+      { direction: 'Fetch', src: 'refs/heads/main', dst: 'refs/remotes/upstream/main', force: true },
+    ]);
+    expect(repo.findReference('refs/remotes/upstream/main')?.target()).toEqual(
+      'a01e9888e46729ef4aa68953ba19b02a7a64eb82'
+    );
+  });
+
   it('clone from remote', { skip: isTarget('linux', undefined, 'gnu') }, async () => {
     const p = await makeTmpDir('clone');
     const repo = await cloneRepository('https://github.com/seokju-na/dummy-repo', p);
     expect(repo.state()).toBe('Clean');
   });
 
+  it('get shallow roots', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+    expect(repo.shallowRoots()).toEqual([]);
+    await fs.writeFile(path.join(p, '.git', 'shallow'), 'b33e0101b828225f77eeff4dfa31259dcf379002\n');
+    const shallow = await openRepository(p);
+    expect(shallow.isShallow()).toBe(true);
+    expect(shallow.shallowRoots()).toEqual(['b33e0101b828225f77eeff4dfa31259dcf379002']);
+    const worktree = shallow.worktree('linked', path.join(await makeTmpDir('worktree'), 'linked'));
+    expect(openRepositoryFromWorktree(worktree).shallowRoots()).toEqual(['b33e0101b828225f77eeff4dfa31259dcf379002']);
+  });
+
+  it('shallow clone, deepen and unshallow', async () => {
+    const { url, commits } = await serveHistory();
+    const repo = await cloneRepository(url, await makeTmpDir('shallow'), {
+      fetch: { depth: 1 },
+    });
+    expect(repo.isShallow()).toBe(true);
+    expect(repo.shallowRoots().sort()).toEqual([commits[5], commits[2]].sort());
+    await repo.getRemote('origin').fetch([], { fetch: { deepen: 2 } });
+    expect(repo.shallowRoots().sort()).toEqual([commits[3], commits[0]].sort());
+    await repo.getRemote('origin').fetch([], { fetch: { unshallow: true } });
+    expect(repo.isShallow()).toBe(false);
+    expect(repo.shallowRoots()).toEqual([]);
+  });
+
+  it('deepen shallow clone until a date', async () => {
+    const { url, commits } = await serveHistory();
+    const repo = await cloneRepository(url, await makeTmpDir('shallow'), {
+      fetch: { depth: 1 },
+    });
+    expect(repo.findCommit(commits[4])).toBeNull();
+    const since = new Date('2023-11-16T00:00:00Z');
+    await repo.getRemote('origin').fetch([], { fetch: { shallowSince: since } });
+    expect(repo.findCommit(commits[4])).not.toBeNull();
+    for (const root of repo.shallowRoots()) {
+      expect(repo.getCommit(root).time().getTime()).toBeLessThan(since.getTime());
+    }
+  });
+
+  it('deepen shallow clone until an excluded revision', async () => {
+    const { url, commits } = await serveHistory();
+    const repo = await cloneRepository(url, await makeTmpDir('shallow'), {
+      fetch: { shallowExclude: ['refs/remotes/origin/base'] },
+    });
+    expect(repo.findCommit(commits[4])).not.toBeNull();
+    expect(repo.findCommit(commits[3])).not.toBeNull();
+  });
+
   it('get head', async () => {
     const p = await useFixture('commits');
     const repo = await openRepository(p);
@@ -0,0 +1,143 @@
+import { describe, expect, it } from 'vitest';
+import {
+  type Repository,
+  type ServeReceivePackOptions,
+  cloneRepository,
+  initRepository,
+  openRepository,
+  registerTransport,
+} from '../index';
+import { useFixture } from './fixtures';
+import { makeTmpDir } from './tmp';
+
+function serve(server: Repository, options?: ServeReceivePackOptions) {
+  registerTransport('es-git-server', (_url, service, body) => {
+    switch (service) {
+      case 'UploadPackLs':
+      case 'UploadPack':
+        return server.serveUploadPack(body);
+      case 'ReceivePackLs':
+      case 'ReceivePack':
+        return server.serveReceivePack(body, options);
+    }
+  });
+}
+
+function pkt(line: string) {
+  return `${(line.length + 4).toString(16).padStart(4, '0')}${line}`;
+}
+
+describe('server', () => {
+  it('advertise references', async () => {
+    const p = await useFixture('commits');
+    const server = await openRepository(p);
+    const advertisement = server.serveUploadPack().toString();
+    expect(advertisement.startsWith('001e# service=git-upload-pack\n0000')).toBe(true);
+    expect(advertisement).toContain('a01e9888e46729ef4aa68953ba19b02a7a64eb82 HEAD\0');
+    expect(advertisement).toContain('symref=HEAD:refs/heads/main');
+    expect(advertisement).toContain('a01e9888e46729ef4aa68953ba19b02a7a64eb82 refs/heads/main\n');
+    expect(advertisement.endsWith('0000')).toBe(true);
+  });
+
+  it('clone and fetch from upload-pack', async () => {
+    const p = await useFixture('commits');
+    const server = await openRepository(p);
+    serve(server);
+    const repo = await cloneRepository('es-git-server://server', await makeTmpDir('server-clone'));
+    expect(repo.head().target()).toEqual('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+    expect(repo.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002').summary()).toBeTruthy();
+  });
+
+  it('push to receive-pack', async () => {
+    const server = await initRepository(await makeTmpDir('server'), { bare: true });
+    serve(server);
+    const repo = await openRepository(await useFixture('commits'));
+    const remote = repo.createAnonymousRemote('es-git-server://server');
+    await expect(remote.push(['refs/heads/main:refs/heads/main'])).resolves.toEqual([
+      { refname: 'refs/heads/main', status: 'Ok' },
+    ]);
+    expect(server.getReference('refs/heads/main').target()).toEqual('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+    expect(server.getCommit('b33e0101b828225f77eeff4dfa31259dcf379002').summary()).toBeTruthy();
+  });
+
+  it('reject updates with onUpdate', async () => {
+    const server = await initRepository(await makeTmpDir('server'), { bare: true });
+    const updates: unknown[] = [];
+    serve(server, {
+      onUpdate: update => {
+        updates.push(update);
+        if (update.refname === 'refs/heads/protected') {
+          return 'protected branch';
+        }
+      },
+    });
+    const repo = await openRepository(await useFixture('commits'));
+    const remote = repo.createAnonymousRemote('es-git-server://server');
+    await expect(remote.push(['refs/heads/main:refs/heads/protected'])).rejects.toThrowError(
+      /failed to push some refs: refs\/heads\/protected \(protected branch\)/
+    );
+    const results = await remote.push(['refs/heads/main:refs/heads/main', 'refs/heads/main:refs/heads/protected'], {
+      throwOnRejected: false,
+    });
+    expect(results).toContainEqual({ refname: 'refs/heads/main', status: 'Ok' });
+    expect(results).toContainEqual({ refname: 'refs/heads/protected', status: 'Rejected', message: 'protected branch' });
+    expect(updates).toContainEqual({
+      refname: 'refs/heads/main',
+      oldOid: '0000000000000000000000000000000000000000',
+      newOid: 'a01e9888e46729ef4aa68953ba19b02a7a64eb82',
+    });
+    expect(server.findReference('refs/heads/protected')).toBeNull();
+  });
+
+  it('list references with protocol version 2', async () => {
+    const p = await useFixture('commits');
+    const server = await openRepository(p);
+    const capabilities = server.serveUploadPack(null, { protocolVersion: 2 }).toString();
+    expect(capabilities.startsWith(pkt('version 2\n'))).toBe(true);
+    expect(capabilities).toContain(pkt('ls-refs\n'));
+    expect(capabilities).toContain(pkt('fetch\n'));
+
+    const request = `${pkt('command=ls-refs\n')}0001${pkt('symrefs\n')}${pkt('ref-prefix HEAD\n')}0000`;
+    const response = server.serveUploadPack(Buffer.from(request), { protocolVersion: 2 }).toString();
+    expect(response).toEqual(
+      `${pkt('a01e9888e46729ef4aa68953ba19b02a7a64eb82 HEAD symref-target:refs/heads/main\n')}0000`
+    );
+  });
+
+  it('fetch with protocol version 2 and stream the response', async () => {
+    const p = await useFixture('commits');
+    const server = await openRepository(p);
+    const request = [
+      pkt('command=fetch\n'),
+      '0001',
+      pkt('want a01e9888e46729ef4aa68953ba19b02a7a64eb82\n'),
+      pkt('have b33e0101b828225f77eeff4dfa31259dcf379002\n'),
+      pkt('done\n'),
+      '0000',
+    ].join('');
+    const chunks: Buffer[] = [];
+    const response = server.serveUploadPack(Buffer.from(request), {
+      protocolVersion: 2,
+      onData: chunk => {
+        chunks.push(chunk);
+      },
+    });
+    expect(response.length).toBe(0);
+    const body = Buffer.concat(chunks);
+    expect(body.subarray(0, 13).toString()).toEqual(pkt('packfile\n'));
+    expect(body[17]).toBe(1);
+    expect(body.subarray(18, 22).toString()).toEqual('PACK');
+    expect(body.subarray(body.length - 4).toString()).toEqual('0000');
+  });
+
+  it('reject wants which are not reachable from references', async () => {
+    const p = await useFixture('commits');
+    const server = await openRepository(p);
+    const head = server.getCommit('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+    const signature = { name: 'Seokju Na', email: 'seokju.me@toss.im' };
+    const dangling = server.commit(head.tree(), 'dangling', { author: signature, committer: signature });
+    const want = (oid: string) => Buffer.from(`${pkt(`want ${oid}\n`)}0000${pkt('done\n')}`);
+    expect(() => server.serveUploadPack(want(dangling))).toThrowError(/not our ref/);
+    expect(server.serveUploadPack(want('b33e0101b828225f77eeff4dfa31259dcf379002')).toString()).toContain('PACK');
+  });
+});
@@ -0,0 +1,100 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { describe, expect, it } from 'vitest';
+import { initRepository } from '../index';
+import { makeTmpDir } from './tmp';
+
+const FILES = ['README', 'a/x', 'a/b/y', 'a/c/z', 'd/w'];
+
+async function prepareRepository() {
+  const p = await makeTmpDir('sparse-checkout');
+  const repo = await initRepository(p);
+  const index = repo.index();
+  for (const file of FILES) {
+    await fs.mkdir(path.dirname(path.join(p, file)), { recursive: true });
+    await fs.writeFile(path.join(p, file), file);
+    index.addPath(file);
+  }
+  index.write();
+  const sig = { name: 'Seokju Na', email: 'seokju.me@toss.im' };
+  repo.commit(repo.getTree(index.writeTree()), 'init', {
+    updateRef: 'HEAD',
+    author: sig,
+    committer: sig,
+    parents: [],
+  });
+  return { p, repo };
+}
+
+async function listFiles(p: string) {
+  const exists = await Promise.all(
+    FILES.map(file =>
+      fs
+        .access(path.join(p, file))
+        .then(() => true)
+        .catch(() => false)
+    )
+  );
+  return FILES.filter((_, i) => exists[i]);
+}
+
+describe('sparse-checkout', () => {
+  it('set cone patterns, add and disable', async () => {
+    const { p, repo } = await prepareRepository();
+    expect(repo.sparseCheckout.isEnabled()).toBe(false);
+
+    repo.sparseCheckout.set(['a/b/']);
+    expect(repo.sparseCheckout.isEnabled()).toBe(true);
+    expect(repo.sparseCheckout.list()).toEqual(['a/b']);
+    expect(await listFiles(p)).toEqual(['README', 'a/x', 'a/b/y']);
+    await expect(fs.access(path.join(p, 'd'))).rejects.toThrow();
+    expect(repo.statuses().isEmpty()).toBe(true);
+
+    repo.sparseCheckout.add(['d']);
+    expect(repo.sparseCheckout.list()).toEqual(['a/b', 'd']);
+    expect(await listFiles(p)).toEqual(['README', 'a/x', 'a/b/y', 'd/w']);
+
+    repo.sparseCheckout.disable();
+    expect(repo.sparseCheckout.isEnabled()).toBe(false);
+    expect(await listFiles(p)).toEqual(FILES);
+    expect(repo.statuses().isEmpty()).toBe(true);
+  });
+
+  it('set non-cone patterns', async () => {
+    const { p, repo } = await prepareRepository();
+    repo.sparseCheckout.set(['/*', '!/*/', 'd/'], { cone: false });
+    expect(repo.sparseCheckout.list()).toEqual(['/*', '!/*/', 'd/']);
+    expect(await listFiles(p)).toEqual(['README', 'd/w']);
+    expect(repo.statuses().isEmpty()).toBe(true);
+  });
+
+  it('checkout honors sparse checkout', async () => {
+    const { p, repo } = await prepareRepository();
+    repo.sparseCheckout.set(['d']);
+    repo.checkoutHead({ force: true });
+    expect(await listFiles(p)).toEqual(['README', 'd/w']);
+    expect(repo.statuses().isEmpty()).toBe(true);
+  });
+
+  it('dry run checkout does not apply sparse checkout', async () => {
+    const { p, repo } = await prepareRepository();
+    repo.sparseCheckout.set(['d']);
+    await fs.mkdir(path.join(p, 'a'));
+    await fs.writeFile(path.join(p, 'a/x'), 'a/x');
+    repo.checkoutHead({ dryRun: true });
+    expect(await listFiles(p)).toEqual(['README', 'a/x', 'd/w']);
+  });
+
+  it('keeps modified files outside of the patterns', async () => {
+    const { p, repo } = await prepareRepository();
+    await fs.writeFile(path.join(p, 'a/c/z'), 'modified');
+    repo.sparseCheckout.set(['d']);
+    expect(await listFiles(p)).toEqual(['README', 'a/c/z', 'd/w']);
+    expect([...repo.statuses().iter()].map(x => x.path())).toEqual(['a/c/z']);
+  });
+
+  it('throws error when listing patterns if not enabled', async () => {
+    const { repo } = await prepareRepository();
+    expect(() => repo.sparseCheckout.list()).toThrowError(/sparse checkout is not enabled/);
+  });
+});
@@ -296,4 +296,120 @@ describe('stash', () => {
     const stashList = repo.stashList();
     expect(stashList.get(999)).toBeNull();
   });
+
+  it('stash with paths', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+
+    await fs.writeFile(path.join(p, 'first'), 'Stashed first');
+    await fs.writeFile(path.join(p, 'second'), 'Kept second');
+    repo.stashSave({ stasher: signature, message: 'first only', paths: ['first'] });
+
+    expect(await fs.readFile(path.join(p, 'first'), 'utf8')).toBe('first\n');
+    expect(await fs.readFile(path.join(p, 'second'), 'utf8')).toBe('Kept second');
+    const paths = [...repo.stashShow(0).deltas()].map(x => x.newFile().path());
+    expect(paths).toEqual(['first']);
+    const [entry] = [...repo.stashList().iter()];
+    expect(entry?.message()).toBe('On main: first only');
+    expect([...entry!.diff().deltas()].map(x => x.newFile().path())).toEqual(['first']);
+  });
+
+  it('throw error of apply progress callback', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+
+    await fs.writeFile(path.join(p, 'second'), 'Stashed');
+    repo.stashSave({ stasher: signature });
+    expect(() =>
+      repo.stashApply(0, {
+        progress: () => {
+          throw new Error('progress failed');
+        },
+      })
+    ).toThrowError(/progress failed/);
+    expect(repo.stashList().len()).toBe(1);
+  });
+
+  it('stash staged changes only', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+
+    await fs.writeFile(path.join(p, 'second'), 'Staged changes');
+    const index = repo.index();
+    index.addPath('second');
+    index.write();
+    await fs.writeFile(path.join(p, 'first'), 'Unstaged changes');
+
+    expect(() => repo.stashSave({ stasher: signature, stagedOnly: true, includeUntracked: true })).toThrow();
+    repo.stashSave({ stasher: signature, message: 'staged', stagedOnly: true });
+
+    expect(await fs.readFile(path.join(p, 'second'), 'utf8')).toBe('second\n');
+    expect(repo.getStatusFile('second').indexModified).toBe(false);
+    expect(await fs.readFile(path.join(p, 'first'), 'utf8')).toBe('Unstaged changes');
+    expect(repo.stashList().get(0)?.message()).toBe('On main: staged');
+    const paths = [...repo.stashShow(0).deltas()].map(x => x.newFile().path());
+    expect(paths).toEqual(['second']);
+
+    repo.stashPop(0, { reinstantiateIndex: true });
+    expect(await fs.readFile(path.join(p, 'second'), 'utf8')).toBe('Staged changes');
+    expect(repo.getStatusFile('second').indexModified).toBe(true);
+  });
+
+  it('show stash with untracked files', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+
+    await fs.writeFile(path.join(p, 'second'), 'Modified content');
+    await fs.writeFile(path.join(p, 'untracked.txt'), 'Untracked content');
+    repo.stashSave({ stasher: signature, includeUntracked: true });
+
+    const paths = [...repo.stashShow(0).deltas()].map(x => x.newFile().path());
+    expect(paths).toEqual(['second']);
+    const withUntracked = [...repo.stashShow(0, { includeUntracked: true }).deltas()].map(x => ({
+      path: x.newFile().path(),
+      status: x.status(),
+    }));
+    expect(withUntracked).toEqual([
+      { path: 'second', status: 'Modified' },
+      { path: 'untracked.txt', status: 'Added' },
+    ]);
+    expect(() => repo.stashShow(1)).toThrow();
+  });
+
+  it('stash branch', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+
+    await fs.writeFile(path.join(p, 'second'), 'Stashed content');
+    repo.stashSave({ stasher: signature });
+
+    repo.stashBranch('stash-branch', 0);
+    expect(repo.head().name()).toBe('refs/heads/stash-branch');
+    expect(await fs.readFile(path.join(p, 'second'), 'utf8')).toBe('Stashed content');
+    expect(repo.stashList().isEmpty()).toBe(true);
+  });
+
+  it('apply stash with progress and conflicts', async () => {
+    const p = await useFixture('commits');
+    const repo = await openRepository(p);
+
+    await fs.writeFile(path.join(p, 'second'), 'Stashed content');
+    repo.stashSave({ stasher: signature });
+
+    await fs.writeFile(path.join(p, 'second'), 'Conflicting content');
+    expect(() => repo.stashApply(0)).toThrow(/second/);
+
+    await fs.writeFile(path.join(p, 'second'), 'second\n');
+    const stages: string[] = [];
+    repo.stashApply(0, {
+      progress: stage => {
+        stages.push(stage);
+      },
+    });
+    expect(stages[0]).toBe('LoadingStash');
+    expect(stages.at(-1)).toBe('Done');
+
+    await fs.writeFile(path.join(p, 'second'), 'second\n');
+    expect(() => repo.stashApply(0, { progress: () => false })).toThrow();
+  });
 });
@@ -2,7 +2,7 @@ import fs from 'node:fs/promises';
 import path from 'node:path';
 import { pathToFileURL } from 'node:url';
 import { describe, expect, it } from 'vitest';
-import { cloneRepository, openRepository } from '../index';
+import { type SubmoduleForeachEntry, cloneRepository, openRepository } from '../index';
 import { useFixture } from './fixtures';
 import { makeTmpDir } from './tmp';
 
@@ -137,4 +137,123 @@ describe('submodule', () => {
     await childSubmodule.repoInit(true);
     await expect(childSubmodule.open()).resolves.toBeTruthy();
   });
+
+  it('add a submodule with options', async () => {
+    const childPath = await useFixture('commits');
+    const parentPath = await useFixture('commits');
+    const parentRepo = await openRepository(parentPath);
+    const childUrl = pathToFileURL(childPath).toString();
+
+    const submodule = await parentRepo.submoduleAdd(childUrl, 'child', { branch: 'main', depth: 1 });
+    expect(submodule.branch()).toEqual('main');
+    expect(submodule.indexId()).toEqual('a01e9888e46729ef4aa68953ba19b02a7a64eb82');
+    const childRepo = await submodule.open();
+    expect(childRepo.head().name()).toEqual('refs/heads/main');
+    await expect(fs.readFile(path.join(parentPath, '.gitmodules'), 'utf8')).resolves.toContain('branch = main');
+
+    const entries: SubmoduleForeachEntry[] = [];
+    parentRepo.submoduleForeach(entry => {
+      entries.push(entry);
+    });
+    expect(entries).toEqual([
+      {
+        name: 'child',
+        path: 'child',
+        oid: 'a01e9888e46729ef4aa68953ba19b02a7a64eb82',
+        toplevel: parentRepo.workdir(),
+      },
+    ]);
+  });
+
+  it('deinit a submodule', async () => {
+    const childPath = await useFixture('commits');
+    const parentPath = await useFixture('commits');
+    const parentRepo = await openRepository(parentPath);
+
+    const submodule = await parentRepo.submoduleAdd(pathToFileURL(childPath).toString(), 'child');
+    await fs.writeFile(path.join(parentPath, 'child', 'untracked'), 'untracked');
+    expect(() => submodule.deinit()).toThrowError(/local modifications/);
+
+    submodule.deinit({ force: true });
+    await expect(fs.readdir(path.join(parentPath, 'child'))).resolves.toEqual([]);
+    expect(parentRepo.config().findString('submodule.child.url')).toBeNull();
+    let visited = false;
+    parentRepo.submoduleForeach(() => {
+      visited = true;
+    });
+    expect(visited).toBe(false);
+  });
+
+  it('refuse to deinit a submodule with an embedded .git directory', async () => {
+    const childPath = await useFixture('commits');
+    const parentPath = await useFixture('commits');
+    const parentRepo = await openRepository(parentPath);
+
+    const submodule = await parentRepo.submoduleAdd(pathToFileURL(childPath).toString(), 'child');
+    await fs.rm(path.join(parentPath, 'child', '.git'), { recursive: true });
+    await fs.cp(path.join(childPath, '.git'), path.join(parentPath, 'child', '.git'), { recursive: true });
+    expect(() => submodule.deinit({ force: true })).toThrowError(/contains a \.git directory/);
+    await expect(fs.stat(path.join(parentPath, 'child', '.git'))).resolves.toBeTruthy();
+  });
+
+  it('update all submodules', async () => {
+    const childPath = await useFixture('commits');
+    const parentPath = await useFixture('commits');
+    const parentRepo = await openRepository(parentPath);
+
+    await parentRepo.submoduleAdd(pathToFileURL(childPath).toString(), 'child');
+    const signature = { name: 'Seokju Na', email: 'seokju.me@toss.im' };
+    const index = parentRepo.index();
+    parentRepo.commit(parentRepo.getTree(index.writeTree()), 'add child', {
+      updateRef: 'HEAD',
+      author: signature,
+      committer: signature,
+      parents: [parentRepo.head().target()!],
+    });
+
+    const repo = await cloneRepository(pathToFileURL(parentPath).toString(), await makeTmpDir('submodule'));
+    await repo.submoduleUpdateAll();
+    await expect(fs.readdir(path.join(repo.workdir()!, 'child'))).resolves.toEqual([]);
+
+    await repo.submoduleUpdateAll({ init: true, recursive: true });
+    await expect(fs.readFile(path.join(repo.workdir()!, 'child', 'first'), 'utf8')).resolves.toBeTruthy();
+    const oids: Array<string | undefined> = [];
+    repo.submoduleForeach(({ oid }) => {
+      oids.push(oid);
+      return false;
+    });
+    expect(oids).toEqual(['a01e9888e46729ef4aa68953ba19b02a7a64eb82']);
+  });
+
+  it('summarize submodules', async () => {
+    const childPath = await useFixture('commits');
+    const parentPath = await useFixture('commits');
+    const parentRepo = await openRepository(parentPath);
+
+    const submodule = await parentRepo.submoduleAdd(pathToFileURL(childPath).toString(), 'child');
+    const childRepo = await submodule.open();
+    const signature = { name: 'Seokju Na', email: 'seokju.me@toss.im' };
+    const oid = childRepo.commit(childRepo.head().peelToTree(), 'ahead', {
+      updateRef: 'HEAD',
+      author: signature,
+      committer: signature,
+      parents: [childRepo.head().target()!],
+    });
+    await fs.writeFile(path.join(parentPath, 'child', 'untracked'), 'untracked');
+
+    expect(parentRepo.submoduleSummary()).toEqual([
+      {
+        name: 'child',
+        path: 'child',
+        recordedOid: 'a01e9888e46729ef4aa68953ba19b02a7a64eb82',
+        checkedOutOid: oid,
+        ahead: 1,
+        behind: 0,
+        initialized: true,
+        checkedOut: true,
+        modified: false,
+        untracked: true,
+      },
+    ]);
+  });
 });
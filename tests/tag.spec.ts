@@ -1,6 +1,11 @@
+import { execFileSync } from 'node:child_process';
+import fs from 'node:fs/promises';
+import path from 'node:path';
 import { describe, expect, it, vi } from 'vitest';
 import { ObjectType, isValidTagName, openRepository } from '../index';
+import { isTarget } from './env';
 import { useFixture } from './fixtures';
+import { makeTmpDir } from './tmp';
 
 describe('tag', () => {
   // Reference: https://git-scm.com/docs/git-check-ref-format
@@ -118,4 +123,74 @@ describe('tag', () => {
     const tag = repo.getTag(tagId);
     expect(tag.name()).toEqual('a0');
   });
+
+  it('create signed tag with signer callback', async () => {
+    const p = await useFixture('tag');
+    const repo = await openRepository(p);
+    const commit = repo.getCommit('828954d');
+    const fakeSignature = '-----BEGIN PGP SIGNATURE-----\n\niQEcBAABAgAGBQJTest123\n-----END PGP SIGNATURE-----\n';
+    const callback = vi.fn().mockReturnValue(fakeSignature);
+    const tagId = repo.createTag('signed', commit.asObject(), 'signed tag', {
+      tagger: {
+        name: 'Seokju Na',
+        email: 'seokju.me@toss.im',
+      },
+      signer: { callback },
+    });
+    expect(callback).toHaveBeenCalledTimes(1);
+    expect(repo.tagNames()).toContain('signed');
+    const tag = repo.getTag(tagId);
+    expect(tag.name()).toEqual('signed');
+    expect(tag.target().id()).toEqual('828954df9f08dc8e172447cdacf0ddea1adf9e63');
+    expect(tag.signature()).toEqual(fakeSignature);
+    expect(tag.signedPayload()).toEqual(callback.mock.calls[0]![0]);
+    expect(tag.signedPayload()).toMatch(/^object 828954df9f08dc8e172447cdacf0ddea1adf9e63\ntype commit\ntag signed\n/);
+    expect(repo.extractSignature(tagId)).toEqual({
+      signature: fakeSignature,
+      signedData: callback.mock.calls[0]![0],
+    });
+    expect(() =>
+      repo.createTag('signed', commit.asObject(), 'signed tag', {
+        signer: { callback },
+      })
+    ).toThrowError(/tag already exists/);
+  });
+
+  it('unsigned tag has no signature', async () => {
+    const p = await useFixture('tag');
+    const repo = await openRepository(p);
+    const tag = repo.getTag('aa00405');
+    expect(tag.signature()).toBeNull();
+    expect(tag.signedPayload()).toBeNull();
+    expect(repo.extractSignature(tag.id())).toBeNull();
+    expect(repo.verifyTagSignature('v0')).toBeNull();
+  });
+
+  it.skipIf(isTarget('win32'))('sign and verify tag with ssh key', async () => {
+    const p = await useFixture('tag');
+    const repo = await openRepository(p);
+    const keyDir = await makeTmpDir('ssh-key');
+    const keyPath = path.join(keyDir, 'id_ed25519');
+    execFileSync('ssh-keygen', ['-q', '-t', 'ed25519', '-N', '', '-C', 'release@example.com', '-f', keyPath]);
+    const publicKey = await fs.readFile(`${keyPath}.pub`, 'utf8');
+    const allowedSigners = path.join(keyDir, 'allowed_signers');
+    await fs.writeFile(allowedSigners, `release@example.com ${publicKey}`);
+
+    const commit = repo.getCommit('828954d');
+    repo.createTag('v3', commit.asObject(), 'release v3', {
+      tagger: {
+        name: 'Seokju Na',
+        email: 'seokju.me@toss.im',
+      },
+      signer: { format: 'Ssh', key: keyPath },
+    });
+    expect(repo.verifyTagSignature('v3', { allowedSigners })).toEqual(
+      expect.objectContaining({
+        format: 'Ssh',
+        valid: true,
+        signer: 'release@example.com',
+        trust: 'fully',
+      })
+    );
+  });
 });
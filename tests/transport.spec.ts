@@ -0,0 +1,34 @@
+import { describe, expect, it, vi } from 'vitest';
+import { openRepository, registerTransport, unregisterTransport } from '../index';
+import { useFixture } from './fixtures';
+
+function pktLine(line: string) {
+  const len = Buffer.byteLength(line) + 4;
+  return `${len.toString(16).padStart(4, '0')}${line}`;
+}
+
+const EMPTY_ADVERTISEMENT = Buffer.from(
+  `${pktLine('# service=git-upload-pack\n')}0000${pktLine(`${'0'.repeat(40)} capabilities^{}\0\n`)}0000`
+);
+
+describe('transport', () => {
+  it('register custom transport', async () => {
+    const handler = vi.fn(() => EMPTY_ADVERTISEMENT);
+    registerTransport('es-git-test', handler);
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const remote = repo.createAnonymousRemote('es-git-test://example/repo');
+    await remote.connect('Fetch');
+    expect(remote.list()).toEqual([]);
+    expect(handler).toHaveBeenCalledWith('es-git-test://example/repo', 'UploadPackLs', Buffer.alloc(0));
+  });
+
+  it('remote operations fail after unregistering transport', async () => {
+    registerTransport('es-git-unregistered', () => EMPTY_ADVERTISEMENT);
+    unregisterTransport('es-git-unregistered');
+    const p = await useFixture('empty');
+    const repo = await openRepository(p);
+    const remote = repo.createAnonymousRemote('es-git-unregistered://example/repo');
+    await expect(remote.connect('Fetch')).rejects.toThrowError(/unregistered/);
+  });
+});
@@ -261,4 +261,81 @@ describe('worktree', () => {
       })
     ).toThrow();
   });
+
+  it('list worktrees with details', async () => {
+    const locked = repo.worktree('locked', path.join(baseWorktreePath, 'locked-dir'), {
+      refName: 'refs/heads/worktree-branch',
+    });
+    locked.lock('test reason');
+    const gonePath = path.join(baseWorktreePath, 'gone-dir');
+    repo.worktree('gone', gonePath);
+    await fs.rm(gonePath, { recursive: true });
+
+    const [main, ...worktrees] = repo.worktreeList();
+    expect(main).toMatchObject({
+      branch: repo.head().name(),
+      head: repo.head().target(),
+      bare: false,
+      detached: false,
+      locked: false,
+      prunable: false,
+    });
+    expect(main!.name).toBeUndefined();
+    expect(await fs.realpath(main!.path)).toBe(await fs.realpath(repo.workdir()!));
+    expect(worktrees).toEqual([
+      expect.objectContaining({
+        name: 'gone',
+        branch: 'refs/heads/gone',
+        locked: false,
+        prunable: true,
+        prunableReason: expect.any(String),
+      }),
+      expect.objectContaining({
+        name: 'locked',
+        head: repo.head().target(),
+        branch: 'refs/heads/worktree-branch',
+        detached: false,
+        locked: true,
+        lockReason: 'test reason',
+        prunable: false,
+      }),
+    ]);
+  });
+
+  it('move worktree', async () => {
+    const oldPath = path.join(baseWorktreePath, 'old-dir');
+    const newPath = path.join(baseWorktreePath, 'new-dir');
+    const worktree = repo.worktree('test-worktree', oldPath);
+    worktree.move(newPath);
+    expect(await fs.realpath(worktree.path())).toBe(await fs.realpath(newPath));
+    await expect(fs.access(oldPath)).rejects.toThrow();
+    expect(() => repo.findWorktree('test-worktree').validate()).not.toThrow();
+    expect(openRepositoryFromWorktree(worktree).head().name()).toBe('refs/heads/test-worktree');
+
+    worktree.lock();
+    expect(() => worktree.move(oldPath)).toThrow(/locked/);
+  });
+
+  it('remove worktree', async () => {
+    const worktreePath = path.join(baseWorktreePath, 'test-worktree-remove');
+    const worktree = repo.worktree('test-worktree', worktreePath);
+    await fs.writeFile(path.join(worktreePath, 'untracked'), 'untracked');
+    expect(() => worktree.remove()).toThrow(/modified or untracked/);
+
+    worktree.remove({ force: true });
+    expect(repo.worktrees()).not.toContain('test-worktree');
+    await expect(fs.access(worktreePath)).rejects.toThrow();
+  });
+
+  it('repair worktree moved manually', async () => {
+    const oldPath = path.join(baseWorktreePath, 'old-dir');
+    const newPath = path.join(baseWorktreePath, 'new-dir');
+    repo.worktree('test-worktree', oldPath);
+    await fs.rename(oldPath, newPath);
+    expect(() => repo.findWorktree('test-worktree').validate()).toThrow();
+
+    expect(repo.worktreeRepair([newPath])).toEqual(['test-worktree']);
+    expect(() => repo.findWorktree('test-worktree').validate()).not.toThrow();
+    expect(repo.worktreeRepair()).toEqual([]);
+  });
 });